@@ -0,0 +1,129 @@
+//! Byron era transactions and blocks. CSL only knows the Shelley and later formats, so these
+//! are decoded with the pallas primitives.
+
+use cryptoxide::ed25519;
+use cryptoxide::hashing::blake2b_256;
+use pallas_codec::minicbor::{self, data::Type, Decoder, Encoder};
+use pallas_primitives::byron;
+use std::convert::TryFrom;
+
+/// Protocol magics of the networks that had a Byron era
+pub const BYRON_PROTOCOL_MAGICS: [u32; 4] = [764824073, 1, 2, 1097911063];
+
+const SIGN_TX_TAG: u8 = 0x01;
+const SIGN_REDEEM_TX_TAG: u8 = 0x02;
+
+pub enum ByronBlock<'b> {
+    Main(Box<byron::MintedBlock<'b>>),
+    EpochBoundary(Box<byron::MintedEbBlock<'b>>),
+}
+
+impl<'b> ByronBlock<'b> {
+    /// Decodes a main or epoch boundary block, with or without the `[era, block]` wrapper
+    /// (era 0 is an epoch boundary block, era 1 a main block).
+    pub fn decode(block_bytes: &'b [u8]) -> Result<Self, String> {
+        let mut decoder = Decoder::new(block_bytes);
+        let length = decoder.array().map_err(|e| e.to_string())?;
+        if length == Some(2) && decoder.datatype().map_err(|e| e.to_string())? == Type::U8 {
+            let era = decoder.u8().map_err(|e| e.to_string())?;
+            let block_bytes = &block_bytes[decoder.position()..];
+            return match era {
+                0 => Self::decode_epoch_boundary(block_bytes),
+                1 => Self::decode_main(block_bytes),
+                _ => Err(format!("Era {} is not a Byron block", era)),
+            };
+        }
+        Self::decode_main(block_bytes).or_else(|_| Self::decode_epoch_boundary(block_bytes))
+    }
+
+    fn decode_main(block_bytes: &'b [u8]) -> Result<Self, String> {
+        minicbor::decode(block_bytes)
+            .map(|block| ByronBlock::Main(Box::new(block)))
+            .map_err(|e| format!("Cannot decode Byron block: {}", e))
+    }
+
+    fn decode_epoch_boundary(block_bytes: &'b [u8]) -> Result<Self, String> {
+        minicbor::decode(block_bytes)
+            .map(|block| ByronBlock::EpochBoundary(Box::new(block)))
+            .map_err(|e| format!("Cannot decode Byron epoch boundary block: {}", e))
+    }
+
+    /// Block hash: blake2b-256 of the header prefixed with its `[era, header]` wrapper
+    pub fn hash(&self) -> [u8; 32] {
+        let (era, header) = match self {
+            ByronBlock::EpochBoundary(block) => (0u8, block.header.raw_cbor()),
+            ByronBlock::Main(block) => (1u8, block.header.raw_cbor()),
+        };
+        let mut bytes = vec![0x82, era];
+        bytes.extend_from_slice(header);
+        blake2b_256(&bytes)
+    }
+
+    pub fn protocol_magic(&self) -> u32 {
+        match self {
+            ByronBlock::EpochBoundary(block) => block.header.protocol_magic,
+            ByronBlock::Main(block) => block.header.protocol_magic,
+        }
+    }
+
+    pub fn transactions(&self) -> &[byron::MintedTxPayload<'b>] {
+        match self {
+            ByronBlock::EpochBoundary(_) => &[],
+            ByronBlock::Main(block) => &block.body.tx_payload,
+        }
+    }
+}
+
+/// Decodes a transaction with its witnesses (`[tx, [* twit]]`)
+pub fn decode_tx_payload(tx_bytes: &[u8]) -> Result<byron::MintedTxPayload<'_>, String> {
+    minicbor::decode(tx_bytes).map_err(|e| format!("Cannot decode Byron transaction: {}", e))
+}
+
+/// Transaction id: blake2b-256 of the transaction without its witnesses
+pub fn tx_hash(tx: &byron::MintedTxPayload) -> [u8; 32] {
+    blake2b_256(tx.transaction.raw_cbor())
+}
+
+/// CBOR of a Byron address, as expected by `csl::ByronAddress::from_bytes`
+pub fn address_bytes(address: &byron::Address) -> Vec<u8> {
+    minicbor::to_vec(address).unwrap_or_default()
+}
+
+/// Verifies a key or redeem witness against the transaction id. The signed message is
+/// the witness tag, the CBOR protocol magic and the CBOR encoded transaction id.
+/// Script witnesses carry no signature and are reported as valid.
+pub fn verify_witness(witness: &byron::Twit, tx_hash: &[u8; 32], protocol_magic: u32) -> bool {
+    let (tag, public_key, signature) = match witness {
+        byron::Twit::PkWitness(wrapped) => (SIGN_TX_TAG, &wrapped.0 .0, &wrapped.0 .1),
+        byron::Twit::RedeemWitness(wrapped) => (SIGN_REDEEM_TX_TAG, &wrapped.0 .0, &wrapped.0 .1),
+        byron::Twit::ScriptWitness(_) | byron::Twit::Other(..) => return true,
+    };
+    // Key witnesses hold an extended public key: the key followed by the chain code
+    let public_key = match public_key
+        .get(..32)
+        .and_then(|k| <&[u8; 32]>::try_from(k).ok())
+    {
+        Some(public_key) => public_key,
+        None => return false,
+    };
+    let signature = match <&[u8; 64]>::try_from(&signature[..]) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+
+    let mut encoder = Encoder::new(vec![tag]);
+    if encoder.u32(protocol_magic).and_then(|e| e.bytes(tx_hash)).is_err() {
+        return false;
+    }
+    ed25519::verify(&encoder.into_writer(), public_key, signature)
+}
+
+/// Signature of a key or redeem witness
+pub fn witness_signature(witness: &byron::Twit) -> Option<&[u8]> {
+    match witness {
+        byron::Twit::PkWitness(wrapped) | byron::Twit::RedeemWitness(wrapped) => {
+            Some(&wrapped.0 .1)
+        }
+        byron::Twit::ScriptWitness(_) | byron::Twit::Other(..) => None,
+    }
+}
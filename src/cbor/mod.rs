@@ -3,7 +3,7 @@ use crate::cbor::cbor_decoder::{get_tokenizer, get_value};
 use crate::js_error::JsError;
 use crate::js_value::{from_serde_json_value, JsValue};
 
-mod cbor_decoder;
+pub(crate) mod cbor_decoder;
 
 #[wasm_bindgen]
 pub fn cbor_to_json(cbor_hex: &str) -> Result<JsValue, JsError> {
@@ -1,14 +1,19 @@
 use cardano_serialization_lib::{
-    AuxiliaryData, Ed25519Signature, GeneralTransactionMetadata, Int, PublicKey,
-    TransactionHash, TransactionMetadatum, TransactionWitnessSet, BigNum, FixedBlock,
-    FixedTransactionBody, FixedTransaction,
+    AuxiliaryData, Ed25519Signature, PublicKey, TransactionHash, TransactionWitnessSet,
+    FixedBlock, FixedTransactionBody, FixedTransaction,
 };
-use cryptoxide::hashing::blake2b_256;
 use hex;
 use serde::{Deserialize, Serialize};
 use crate::bingen::wasm_bindgen;
+use crate::byron::{self, ByronBlock, BYRON_PROTOCOL_MAGICS};
+use crate::header_crypto::{verify_sum_kes, vrf_verify, CARDANO_KES_DEPTH};
 use crate::js_error::JsError;
 use crate::js_value::{from_serde_json_value, JsValue};
+use crate::validators::block_validator::RawBlock;
+use crate::validators::phase_1::validation::voting_registration::VotingRegistration;
+use cryptoxide::hashing::blake2b_256;
+use pallas_codec::minicbor;
+use pallas_primitives::{alonzo, babbage};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckResult {
@@ -94,6 +99,24 @@ pub fn check_block_or_tx_signatures(hex_str: &str) -> Result<JsValue, JsError> {
         return to_js_value(&result).map_err(|e| JsError::new(&e));
     }
 
+    let bytes = from_hex_string(hex_str).map_err(|e| JsError::new(&e))?;
+    if let Ok(block) = ByronBlock::decode(&bytes) {
+        for tx in block.transactions() {
+            let result = check_byron_tx_signatures(tx, &[block.protocol_magic()]);
+            if !result.valid {
+                return to_js_value(&result).map_err(|e| JsError::new(&e));
+            }
+        }
+        let res = CheckResult::valid("All block txs are valid");
+        return to_js_value(&res).map_err(|e| JsError::new(&e));
+    }
+
+    if let Ok(tx) = byron::decode_tx_payload(&bytes) {
+        // A lone transaction doesn't tell its network, so any known protocol magic is accepted
+        let result = check_byron_tx_signatures(&tx, &BYRON_PROTOCOL_MAGICS);
+        return to_js_value(&result).map_err(|e| JsError::new(&e));
+    }
+
     Err(JsError::new("cannot parse block or transaction from given hex"))
 }
 
@@ -123,6 +146,31 @@ fn check_tx_signatures_internal(tx: &FixedTransaction) -> CheckResult {
     check_body_signatures(&tx_hash, &auxiliary_data, &witness_set)
 }
 
+/// Byron witnesses sign the transaction id together with the network protocol magic
+fn check_byron_tx_signatures(
+    tx: &pallas_primitives::byron::MintedTxPayload,
+    protocol_magics: &[u32],
+) -> CheckResult {
+    let tx_hash = byron::tx_hash(tx);
+    let invalid_witnesses: Vec<String> = tx
+        .witness
+        .iter()
+        .filter(|witness| {
+            !protocol_magics
+                .iter()
+                .any(|magic| byron::verify_witness(witness, &tx_hash, *magic))
+        })
+        .filter_map(byron::witness_signature)
+        .map(hex::encode)
+        .collect();
+
+    if invalid_witnesses.is_empty() {
+        CheckResult::valid(&hex::encode(tx_hash))
+    } else {
+        CheckResult::invalid(&hex::encode(tx_hash), vec![], invalid_witnesses)
+    }
+}
+
 pub fn check_block_txs_signatures(block_hex: &str) -> Result<Vec<CheckResult>, String> {
     let block = decode_block_from_hex(block_hex)?;
     check_block_txs_signatures_internal(&block)
@@ -188,45 +236,28 @@ struct PubKeySignature {
     pub signature: Ed25519Signature,
 }
 
-/// Read the stake key and signature from Catalyst registration metadata, if present.
+/// Read the stake key and signature from a Catalyst (CIP-15 or CIP-36) registration, if present.
 fn get_catalyst_witnesses(aux_data_opt: &Option<AuxiliaryData>) -> Vec<PubKeySignature> {
-    if aux_data_opt.is_none() {
-        return vec![];
-    }
-    let aux_data = aux_data_opt.as_ref().unwrap();
-    let metadata = match aux_data.metadata() {
+    let registration = match get_voting_registration(aux_data_opt) {
         None => return vec![],
-        Some(md) => md,
-    };
-    let label_61284 = BigNum::from_str("61284").unwrap();
-    let label_61285 = BigNum::from_str("61285").unwrap();
-
-    let catalyst_meta = metadata.get(&label_61284);
-    let catalyst_meta_sign = metadata.get(&label_61285);
-
-    if catalyst_meta.is_none() || catalyst_meta_sign.is_none() {
-        return vec![];
-    }
-
-    // We expect them to be maps containing certain fields.
-    let stake_pub_key_bytes = match extract_map_bytes(&catalyst_meta.unwrap(), 2) {
-        Ok(b) => b,
-        Err(_) => return vec![],
-    };
-
-    let signature_bytes = match extract_map_bytes(&catalyst_meta_sign.unwrap(), 1) {
-        Ok(b) => b,
-        Err(_) => return vec![],
+        Some(registration) => registration,
     };
 
-    let stake_pub_key = match PublicKey::from_bytes(&stake_pub_key_bytes) {
-        Ok(pk) => pk,
-        Err(_) => return vec![],
+    let stake_pub_key = match registration
+        .stake_public_key
+        .as_ref()
+        .and_then(|bytes| PublicKey::from_bytes(bytes).ok())
+    {
+        Some(pk) => pk,
+        None => return vec![],
     };
 
-    let signature = match Ed25519Signature::from_bytes(signature_bytes) {
-        Ok(sig) => sig,
-        Err(_) => return vec![],
+    let signature = match registration
+        .signature
+        .and_then(|bytes| Ed25519Signature::from_bytes(bytes).ok())
+    {
+        Some(sig) => sig,
+        None => return vec![],
     };
 
     vec![PubKeySignature {
@@ -236,38 +267,14 @@ fn get_catalyst_witnesses(aux_data_opt: &Option<AuxiliaryData>) -> Vec<PubKeySig
 }
 
 fn get_catalyst_registration_hash(aux_data_opt: &Option<AuxiliaryData>) -> Option<Vec<u8>> {
-    if aux_data_opt.is_none() {
-        return None;
-    }
-    let aux_data = aux_data_opt.as_ref().unwrap();
-    let metadata = match aux_data.metadata() {
-        None => return None,
-        Some(md) => md,
-    };
-    let label_61284 = BigNum::from_str("61284").unwrap();
-
-    let catalyst_meta = metadata.get(&label_61284);
-    if catalyst_meta.is_none() {
-        return None;
-    }
-
-    // Build a minimal general metadata that only has the 61284 label.
-    let mut general_meta = GeneralTransactionMetadata::new();
-    general_meta.insert(&label_61284, &catalyst_meta.unwrap());
-
-    let general_meta_bytes = general_meta.to_bytes();
-    Some(blake2b_256(&general_meta_bytes).to_vec())
+    get_voting_registration(aux_data_opt)
+        .map(|registration| registration.registration_hash)
+        .filter(|hash| !hash.is_empty())
 }
 
-fn extract_map_bytes(
-    meta_datum: &TransactionMetadatum,
-    int_key: i32,
-) -> Result<Vec<u8>, String> {
-    let map = meta_datum.as_map().map_err(|_| "not a map".to_string())?;
-    let val_key = TransactionMetadatum::new_int(&Int::new_i32(int_key));
-    let val = map.get(&val_key).map_err(|_| "key not found in map".to_string())?;
-    let bytes = val.as_bytes().map_err(|_| "value not bytes".to_string())?;
-    Ok(bytes)
+fn get_voting_registration(aux_data_opt: &Option<AuxiliaryData>) -> Option<VotingRegistration> {
+    let metadata = aux_data_opt.as_ref()?.metadata()?;
+    VotingRegistration::from_metadata(&metadata)
 }
 
 fn get_vkey_witnesses(
@@ -322,4 +329,243 @@ fn witnesses_list_to_signatures_list(
 fn to_js_value(check: &CheckResult) -> Result<JsValue, String> {
     let json_obj = serde_json::to_value(check).map_err(|e| e.to_string())?;
     from_serde_json_value(&json_obj)
-}
\ No newline at end of file
+}
+
+fn default_slots_per_kes_period() -> u64 {
+    129600
+}
+
+fn default_max_kes_evolutions() -> u64 {
+    62
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderCheckParams {
+    /// Epoch nonce as hex. The VRF proofs are only checked when it is given
+    #[serde(default)]
+    pub epoch_nonce: Option<String>,
+    #[serde(default = "default_slots_per_kes_period")]
+    pub slots_per_kes_period: u64,
+    #[serde(default = "default_max_kes_evolutions")]
+    pub max_kes_evolutions: u64,
+}
+
+impl Default for HeaderCheckParams {
+    fn default() -> Self {
+        HeaderCheckParams {
+            epoch_nonce: None,
+            slots_per_kes_period: default_slots_per_kes_period(),
+            max_kes_evolutions: default_max_kes_evolutions(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderCheckResult {
+    pub valid: bool,
+    pub block_hash: String,
+    pub slot: u64,
+    pub kes_period: u64,
+    pub kes_signature_valid: bool,
+    pub opcert_signature_valid: bool,
+    /// `None` when no epoch nonce was given
+    pub vrf_valid: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Input of a VRF proof in the header
+enum VrfInput {
+    /// Babbage and later: a single proof, the input doesn't depend on its use
+    Praos,
+    /// Shelley to Alonzo: separate proofs with the seed xor-ed with a per use constant
+    TPraos(u64),
+}
+
+struct VrfProof {
+    name: &'static str,
+    input: VrfInput,
+    output: Vec<u8>,
+    proof: Vec<u8>,
+}
+
+struct HeaderFields<'a> {
+    body_bytes: &'a [u8],
+    body_signature: Vec<u8>,
+    slot: u64,
+    issuer_vkey: Vec<u8>,
+    vrf_vkey: Vec<u8>,
+    vrf_proofs: Vec<VrfProof>,
+    hot_vkey: Vec<u8>,
+    sequence_number: u64,
+    kes_period: u64,
+    opcert_signature: Vec<u8>,
+}
+
+fn decode_header_fields(header_bytes: &[u8]) -> Result<HeaderFields<'_>, String> {
+    if let Ok(header) = minicbor::decode::<babbage::MintedHeader>(header_bytes) {
+        let body_bytes = header.header_body.raw_cbor();
+        let body = &header.header_body;
+        return Ok(HeaderFields {
+            body_bytes,
+            body_signature: header.body_signature.to_vec(),
+            slot: body.slot,
+            issuer_vkey: body.issuer_vkey.to_vec(),
+            vrf_vkey: body.vrf_vkey.to_vec(),
+            vrf_proofs: vec![VrfProof {
+                name: "VRF",
+                input: VrfInput::Praos,
+                output: body.vrf_result.0.to_vec(),
+                proof: body.vrf_result.1.to_vec(),
+            }],
+            hot_vkey: body.operational_cert.operational_cert_hot_vkey.to_vec(),
+            sequence_number: body.operational_cert.operational_cert_sequence_number,
+            kes_period: body.operational_cert.operational_cert_kes_period,
+            opcert_signature: body.operational_cert.operational_cert_sigma.to_vec(),
+        });
+    }
+    let header = minicbor::decode::<alonzo::MintedHeader>(header_bytes)
+        .map_err(|e| format!("Cannot decode block header: {}", e))?;
+    let body_bytes = header.header_body.raw_cbor();
+    let body = &header.header_body;
+    Ok(HeaderFields {
+        body_bytes,
+        body_signature: header.body_signature.to_vec(),
+        slot: body.slot,
+        issuer_vkey: body.issuer_vkey.to_vec(),
+        vrf_vkey: body.vrf_vkey.to_vec(),
+        vrf_proofs: vec![
+            VrfProof {
+                name: "Nonce VRF",
+                input: VrfInput::TPraos(0),
+                output: body.nonce_vrf.0.to_vec(),
+                proof: body.nonce_vrf.1.to_vec(),
+            },
+            VrfProof {
+                name: "Leader VRF",
+                input: VrfInput::TPraos(1),
+                output: body.leader_vrf.0.to_vec(),
+                proof: body.leader_vrf.1.to_vec(),
+            },
+        ],
+        hot_vkey: body.operational_cert_hot_vkey.to_vec(),
+        sequence_number: body.operational_cert_sequence_number,
+        kes_period: body.operational_cert_kes_period,
+        opcert_signature: body.operational_cert_sigma.to_vec(),
+    })
+}
+
+fn vrf_input(input: &VrfInput, slot: u64, epoch_nonce: &[u8]) -> Vec<u8> {
+    let mut seed_bytes = slot.to_be_bytes().to_vec();
+    seed_bytes.extend_from_slice(epoch_nonce);
+    let seed = blake2b_256(&seed_bytes);
+    match input {
+        VrfInput::Praos => seed.to_vec(),
+        VrfInput::TPraos(use_constant) => {
+            let use_nonce = blake2b_256(&use_constant.to_be_bytes());
+            seed.iter().zip(use_nonce.iter()).map(|(a, b)| a ^ b).collect()
+        }
+    }
+}
+
+/// Checks the KES signature, the operational certificate and, when the epoch nonce is given,
+/// the VRF proofs of a block header. Accepts a header or a whole block.
+pub fn check_header_signatures(
+    hex_str: &str,
+    params: &HeaderCheckParams,
+) -> Result<HeaderCheckResult, String> {
+    let bytes = from_hex_string(hex_str)?;
+    let header_bytes = match RawBlock::decode(&bytes) {
+        Ok(block) => block.header,
+        Err(_) => &bytes[..],
+    };
+    let header = decode_header_fields(header_bytes)?;
+    let mut errors = vec![];
+
+    let current_kes_period = header.slot / params.slots_per_kes_period;
+    if current_kes_period < header.kes_period {
+        errors.push(format!(
+            "KES period {} is before the operational certificate start period {}",
+            current_kes_period, header.kes_period
+        ));
+    } else if current_kes_period >= header.kes_period + params.max_kes_evolutions {
+        errors.push(format!(
+            "KES period {} is past the end of the operational certificate (start period {}, {} evolutions)",
+            current_kes_period, header.kes_period, params.max_kes_evolutions
+        ));
+    }
+
+    let kes_signature_valid = current_kes_period >= header.kes_period
+        && verify_sum_kes(
+            CARDANO_KES_DEPTH,
+            &header.hot_vkey,
+            current_kes_period - header.kes_period,
+            header.body_bytes,
+            &header.body_signature,
+        );
+    if !kes_signature_valid {
+        errors.push("KES signature over the header body is invalid".to_string());
+    }
+
+    let mut opcert_message = header.hot_vkey.clone();
+    opcert_message.extend_from_slice(&header.sequence_number.to_be_bytes());
+    opcert_message.extend_from_slice(&header.kes_period.to_be_bytes());
+    let opcert_signature_valid = match (
+        PublicKey::from_bytes(&header.issuer_vkey),
+        Ed25519Signature::from_bytes(header.opcert_signature.clone()),
+    ) {
+        (Ok(issuer_vkey), Ok(signature)) => issuer_vkey.verify(&opcert_message, &signature),
+        _ => false,
+    };
+    if !opcert_signature_valid {
+        errors.push("Operational certificate signature is invalid for the issuer key".to_string());
+    }
+
+    let vrf_valid = match &params.epoch_nonce {
+        Some(epoch_nonce) => {
+            let epoch_nonce = from_hex_string(epoch_nonce)?;
+            let mut all_valid = true;
+            for vrf_proof in &header.vrf_proofs {
+                let alpha = vrf_input(&vrf_proof.input, header.slot, &epoch_nonce);
+                match vrf_verify(&header.vrf_vkey, &alpha, &vrf_proof.proof) {
+                    Some(output) if output[..] == vrf_proof.output[..] => {}
+                    Some(_) => {
+                        all_valid = false;
+                        errors.push(format!("{} output doesn't match the proof", vrf_proof.name));
+                    }
+                    None => {
+                        all_valid = false;
+                        errors.push(format!("{} proof is invalid", vrf_proof.name));
+                    }
+                }
+            }
+            Some(all_valid)
+        }
+        None => None,
+    };
+
+    Ok(HeaderCheckResult {
+        valid: errors.is_empty(),
+        block_hash: hex::encode(blake2b_256(header_bytes)),
+        slot: header.slot,
+        kes_period: current_kes_period,
+        kes_signature_valid,
+        opcert_signature_valid,
+        vrf_valid,
+        errors,
+    })
+}
+
+#[wasm_bindgen]
+pub fn check_block_header_signatures(hex_str: &str, params_json: &str) -> Result<JsValue, JsError> {
+    let params: HeaderCheckParams = if params_json.trim().is_empty() {
+        HeaderCheckParams::default()
+    } else {
+        serde_json::from_str(params_json).map_err(|e| JsError::new(&e.to_string()))?
+    };
+    let result = check_header_signatures(hex_str, &params).map_err(|e| JsError::new(&e))?;
+    let json_obj = serde_json::to_value(&result).map_err(|e| JsError::new(&e.to_string()))?;
+    from_serde_json_value(&json_obj).map_err(|e| JsError::new(&e))
+}
@@ -7,6 +7,10 @@ use serde_json::Value;
 use crate::js_value::JsValue;
 use crate::js_value::from_serde_json_value;
 use crate::plutus::plutus_script_normalizer::{normalize_plutus_script, OutputEncoding};
+use crate::byron;
+use cryptoxide::hashing::blake2b_256;
+use pallas_codec::minicbor;
+use pallas_primitives::byron as pallas_byron;
 
 pub fn decode_address(input: &str, is_hex: bool, is_bech32: bool, is_base58: bool) -> Result<JsValue, String> {
     let decoded = decode_address_internal(input, is_hex, is_bech32, is_base58)?;
@@ -17,8 +21,13 @@ pub fn decode_transaction(input: &str, is_hex: bool, _is_bech32: bool, _is_base5
     if !is_hex {
         Err("Only hex encoding is supported".to_string())?;
     }
-    let fixed_tx = csl::FixedTransaction::from_hex(input)
-        .map_err(|e| format!("Failed to decode Transaction: {:?}", e))?;
+    let fixed_tx = match csl::FixedTransaction::from_hex(input) {
+        Ok(fixed_tx) => fixed_tx,
+        Err(e) => {
+            return decode_byron_transaction(input, is_hex, _is_bech32, _is_base58)
+                .map_err(|_| format!("Failed to decode Transaction: {:?}", e))
+        }
+    };
     let parsed_tx: Value = csl::Transaction::from_hex(input)
         .map_err(|e| format!("Failed to decode Transaction: {:?}", e))
         .and_then(|tx| tx.to_json().map_err(|e| format!("Failed to convert to JSON: {:?}", e)))
@@ -300,3 +309,159 @@ pub fn map_schema(schema: Option<PlutusDataSchema>) -> csl::PlutusDatumSchema {
         None => csl::PlutusDatumSchema::BasicConversions,
     }
 }
+
+pub fn decode_byron_transaction(input: &str, is_hex: bool, _is_bech32: bool, _is_base58: bool) -> Result<JsValue, String> {
+    if !is_hex {
+        Err("Only hex encoding is supported".to_string())?;
+    }
+    let bytes = hex::decode(input).map_err(|e| format!("Failed to decode hex: {}", e))?;
+    let value = if let Ok(payload) = byron::decode_tx_payload(&bytes) {
+        format_byron_transaction(&payload.transaction, byron::tx_hash(&payload), Some(&payload.witness))?
+    } else {
+        // A transaction without its witnesses
+        let tx: pallas_byron::Tx = minicbor::decode(&bytes)
+            .map_err(|e| format!("Failed to decode Byron transaction: {}", e))?;
+        format_byron_transaction(&tx, blake2b_256(&bytes), None)?
+    };
+    from_serde_json_value(&value).map_err(|e| format!("Failed to convert to JsValue: {}", e))
+}
+
+pub fn decode_byron_witness(input: &str, is_hex: bool, _is_bech32: bool, _is_base58: bool) -> Result<JsValue, String> {
+    if !is_hex {
+        Err("Only hex encoding is supported".to_string())?;
+    }
+    let bytes = hex::decode(input).map_err(|e| format!("Failed to decode hex: {}", e))?;
+    let witness: pallas_byron::Twit = minicbor::decode(&bytes)
+        .map_err(|e| format!("Failed to decode Byron witness: {}", e))?;
+    from_serde_json_value(&format_byron_witness(&witness))
+        .map_err(|e| format!("Failed to convert to JsValue: {}", e))
+}
+
+pub fn decode_byron_block(input: &str, is_hex: bool, _is_bech32: bool, _is_base58: bool) -> Result<JsValue, String> {
+    if !is_hex {
+        Err("Only hex encoding is supported".to_string())?;
+    }
+    let bytes = hex::decode(input).map_err(|e| format!("Failed to decode hex: {}", e))?;
+    let block = byron::ByronBlock::decode(&bytes)?;
+    let value = match &block {
+        byron::ByronBlock::Main(main_block) => {
+            let header = &main_block.header;
+            let consensus = &header.consensus_data;
+            let transactions = block
+                .transactions()
+                .iter()
+                .map(|payload| {
+                    format_byron_transaction(&payload.transaction, byron::tx_hash(payload), Some(&payload.witness))
+                })
+                .collect::<Result<Vec<Value>, String>>()?;
+            serde_json::json!({
+                "block_type": "Main",
+                "block_hash": hex::encode(block.hash()),
+                "protocol_magic": header.protocol_magic,
+                "prev_block": header.prev_block.to_string(),
+                "epoch": consensus.0.epoch,
+                "slot": consensus.0.slot,
+                "issuer_public_key": hex::encode(&consensus.1[..]),
+                "block_version": format!(
+                    "{}.{}.{}",
+                    header.extra_data.block_version.0,
+                    header.extra_data.block_version.1,
+                    header.extra_data.block_version.2
+                ),
+                "software_version": {
+                    "name": header.extra_data.software_version.0,
+                    "version": header.extra_data.software_version.1,
+                },
+                "transactions": transactions,
+            })
+        }
+        byron::ByronBlock::EpochBoundary(boundary_block) => {
+            let header = &boundary_block.header;
+            serde_json::json!({
+                "block_type": "EpochBoundary",
+                "block_hash": hex::encode(block.hash()),
+                "protocol_magic": header.protocol_magic,
+                "prev_block": header.prev_block.to_string(),
+                "epoch": header.consensus_data.epoch_id,
+                "stakeholders": boundary_block.body.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+            })
+        }
+    };
+    from_serde_json_value(&value).map_err(|e| format!("Failed to convert to JsValue: {}", e))
+}
+
+fn format_byron_transaction(
+    tx: &pallas_byron::Tx,
+    tx_hash: [u8; 32],
+    witnesses: Option<&[pallas_byron::Twit]>,
+) -> Result<Value, String> {
+    let inputs: Vec<Value> = tx
+        .inputs
+        .iter()
+        .map(|input| match input {
+            pallas_byron::TxIn::Variant0(wrapped) => serde_json::json!({
+                "transaction_id": wrapped.0 .0.to_string(),
+                "index": wrapped.0 .1,
+            }),
+            pallas_byron::TxIn::Other(tag, bytes) => serde_json::json!({
+                "type": tag,
+                "bytes": hex::encode(&bytes[..]),
+            }),
+        })
+        .collect();
+    let outputs = tx
+        .outputs
+        .iter()
+        .map(|output| {
+            let address_bytes = byron::address_bytes(&output.address);
+            let address = match ByronAddress::from_bytes(address_bytes.clone()) {
+                Ok(address) => format_byron_address(address)?,
+                Err(_) => serde_json::json!({ "bytes": hex::encode(address_bytes) }),
+            };
+            Ok(serde_json::json!({
+                "address": address,
+                "amount": output.amount.to_string(),
+            }))
+        })
+        .collect::<Result<Vec<Value>, String>>()?;
+
+    let mut value = serde_json::json!({
+        "transaction_hash": hex::encode(tx_hash),
+        "transaction": {
+            "inputs": inputs,
+            "outputs": outputs,
+        },
+    });
+    if let Some(witnesses) = witnesses {
+        value["witnesses"] = Value::Array(witnesses.iter().map(format_byron_witness).collect());
+    }
+    Ok(value)
+}
+
+fn format_byron_witness(witness: &pallas_byron::Twit) -> Value {
+    match witness {
+        pallas_byron::Twit::PkWitness(wrapped) => serde_json::json!({
+            "type": "PkWitness",
+            "public_key": hex::encode(&wrapped.0 .0[..]),
+            "signature": hex::encode(&wrapped.0 .1[..]),
+        }),
+        pallas_byron::Twit::RedeemWitness(wrapped) => serde_json::json!({
+            "type": "RedeemWitness",
+            "public_key": hex::encode(&wrapped.0 .0[..]),
+            "signature": hex::encode(&wrapped.0 .1[..]),
+        }),
+        pallas_byron::Twit::ScriptWitness(wrapped) => {
+            let (validator, redeemer) = &wrapped.0;
+            serde_json::json!({
+                "type": "ScriptWitness",
+                "validator": { "version": validator.0, "script": hex::encode(&validator.1[..]) },
+                "redeemer": { "version": redeemer.0, "script": hex::encode(&redeemer.1[..]) },
+            })
+        }
+        pallas_byron::Twit::Other(tag, bytes) => serde_json::json!({
+            "type": "Other",
+            "tag": tag,
+            "bytes": hex::encode(&bytes[..]),
+        }),
+    }
+}
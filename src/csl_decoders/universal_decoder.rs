@@ -1,7 +1,8 @@
 use crate::bingen::wasm_bindgen;
 use crate::csl_decoders::params::DecodingParams;
 use crate::csl_decoders::specific_decoders::{
-    decode_address, decode_plutus_data, decode_plutus_script, decode_transaction,
+    decode_address, decode_byron_block, decode_byron_transaction, decode_byron_witness,
+    decode_plutus_data, decode_plutus_script, decode_transaction,
 };
 use crate::js_value::{empty_js_value, from_js_value, from_serde_json_value, JsValue};
 use bech32;
@@ -172,6 +173,9 @@ pub fn get_decodable_types() -> Vec<String> {
         String::from("VotingProposals"),
         String::from("Withdrawals"),
         String::from("ByronAddress"),
+        String::from("ByronBlock"),
+        String::from("ByronTransaction"),
+        String::from("ByronTxWitness"),
         String::from("KESSignature"),
         String::from("LegacyDaedalusPrivateKey"),
         String::from("RewardAddress"),
@@ -864,6 +868,9 @@ pub fn decode_specific_type(
                     return from_serde_json_value(&value)
                         .map_err(|e| format!("Failed to convert to JsValue: {}", e));
                 }
+                if let Ok(value) = decode_byron_block(input, is_hex, is_bech32, is_base58) {
+                    return Ok(value);
+                }
             }
 
             Err("Failed to decode".to_string())
@@ -2895,6 +2902,12 @@ pub fn decode_specific_type(
 
         "ByronAddress" => decode_address(input, is_hex, is_bech32, is_base58),
 
+        "ByronBlock" => decode_byron_block(input, is_hex, is_bech32, is_base58),
+
+        "ByronTransaction" => decode_byron_transaction(input, is_hex, is_bech32, is_base58),
+
+        "ByronTxWitness" => decode_byron_witness(input, is_hex, is_bech32, is_base58),
+
         "KESSignature" => {
             if is_hex {
                 if let Ok(bytes) = hex::decode(input) {
@@ -3534,6 +3547,18 @@ pub fn get_possible_types_for_input(input: &str) -> Vec<String> {
         type_names.push("ByronAddress".to_string());
     }
 
+    if decode_specific_type(input, "ByronBlock", empty_js_value()).is_ok() {
+        type_names.push("ByronBlock".to_string());
+    }
+
+    if decode_specific_type(input, "ByronTransaction", empty_js_value()).is_ok() {
+        type_names.push("ByronTransaction".to_string());
+    }
+
+    if decode_specific_type(input, "ByronTxWitness", empty_js_value()).is_ok() {
+        type_names.push("ByronTxWitness".to_string());
+    }
+
     if decode_specific_type(input, "KESSignature", empty_js_value()).is_ok() {
         type_names.push("KESSignature".to_string());
     }
@@ -0,0 +1,190 @@
+//! Consensus cryptography used by block headers: the binary sum composition KES
+//! (`Sum6KES` over Ed25519 with Blake2b-256) and ECVRF-ED25519-SHA512-Elligator2
+//! as specified in draft-irtf-cfrg-vrf-03, which is what Cardano nodes use.
+
+use cryptoxide::curve25519::{Fe, Ge, Scalar};
+use cryptoxide::ed25519;
+use cryptoxide::hashing::{blake2b_256, sha2::Sha512};
+use std::convert::TryFrom;
+
+/// Depth of the KES tree used by Cardano (2^6 = 64 periods)
+pub const CARDANO_KES_DEPTH: u32 = 6;
+
+const VRF_SUITE: u8 = 0x04;
+const VRF_PROOF_SIZE: usize = 80;
+
+/// Size of a sum composition KES signature of the given depth
+pub fn sum_kes_signature_size(depth: u32) -> usize {
+    64 + 64 * depth as usize
+}
+
+/// Verifies a sum composition KES signature. The signature is the Ed25519 signature
+/// followed by the two verification keys of every level, from the leaf to the root.
+pub fn verify_sum_kes(
+    depth: u32,
+    public_key: &[u8],
+    period: u64,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    if signature.len() != sum_kes_signature_size(depth) || public_key.len() != 32 {
+        return false;
+    }
+    if depth == 0 {
+        let public_key = <&[u8; 32]>::try_from(public_key).unwrap();
+        let signature = <&[u8; 64]>::try_from(signature).unwrap();
+        return period == 0 && ed25519::verify(message, public_key, signature);
+    }
+
+    let (inner_signature, verification_keys) = signature.split_at(signature.len() - 64);
+    if blake2b_256(verification_keys)[..] != public_key[..] {
+        return false;
+    }
+    let half = 1u64 << (depth - 1);
+    if period < half {
+        verify_sum_kes(
+            depth - 1,
+            &verification_keys[..32],
+            period,
+            message,
+            inner_signature,
+        )
+    } else if period < 2 * half {
+        verify_sum_kes(
+            depth - 1,
+            &verification_keys[32..],
+            period - half,
+            message,
+            inner_signature,
+        )
+    } else {
+        false
+    }
+}
+
+/// Verifies a VRF proof and returns its 64 bytes output when the proof is valid
+pub fn vrf_verify(public_key: &[u8], alpha: &[u8], proof: &[u8]) -> Option<[u8; 64]> {
+    let public_key = <&[u8; 32]>::try_from(public_key).ok()?;
+    if proof.len() != VRF_PROOF_SIZE || !is_canonical_point(public_key) {
+        return None;
+    }
+    let y_point = decode_point(public_key)?;
+    if has_small_order(&y_point) {
+        return None;
+    }
+
+    let gamma = decode_point(<&[u8; 32]>::try_from(&proof[0..32]).unwrap())?;
+    let mut c = [0u8; 32];
+    c[..16].copy_from_slice(&proof[32..48]);
+    let s = Scalar::from_bytes_canonical(<&[u8; 32]>::try_from(&proof[48..80]).unwrap())?;
+    let s_bytes = <[u8; 32]>::try_from(&proof[48..80]).unwrap();
+
+    let h_point = hash_to_curve_elligator2(public_key, alpha)?;
+
+    // U = s*B - c*Y
+    let u_point = (&Ge::scalarmult_base(&s) - &scalar_mult(&c, &y_point).to_cached()).to_full();
+    // V = s*H - c*Gamma
+    let v_point =
+        (&scalar_mult(&s_bytes, &h_point) - &scalar_mult(&c, &gamma).to_cached()).to_full();
+
+    let mut hasher = Sha512::new();
+    hasher.update_mut(&[VRF_SUITE, 0x02]);
+    hasher.update_mut(&h_point.to_bytes());
+    hasher.update_mut(&proof[0..32]);
+    hasher.update_mut(&u_point.to_bytes());
+    hasher.update_mut(&v_point.to_bytes());
+    let c_prime = hasher.finalize();
+    if c_prime[..16] != proof[32..48] {
+        return None;
+    }
+
+    vrf_proof_to_hash(proof)
+}
+
+/// Output of a VRF proof: SHA-512 of the cofactor cleared Gamma point
+pub fn vrf_proof_to_hash(proof: &[u8]) -> Option<[u8; 64]> {
+    if proof.len() != VRF_PROOF_SIZE {
+        return None;
+    }
+    let gamma = decode_point(<&[u8; 32]>::try_from(&proof[0..32]).unwrap())?;
+    let mut hasher = Sha512::new();
+    hasher.update_mut(&[VRF_SUITE, 0x03]);
+    hasher.update_mut(&clear_cofactor(&gamma).to_bytes());
+    Some(hasher.finalize())
+}
+
+/// Decodes a compressed point. `Ge::from_bytes` follows ref10 and returns the negated point,
+/// which suits Ed25519 verification but not the VRF equations.
+fn decode_point(bytes: &[u8; 32]) -> Option<Ge> {
+    let negated = Ge::from_bytes(bytes)?;
+    Some((&Ge::ZERO - &negated.to_cached()).to_full())
+}
+
+/// Variable time double-and-add, the scalar is given as 32 little endian bytes
+fn scalar_mult(scalar: &[u8; 32], point: &Ge) -> Ge {
+    let cached = point.to_cached();
+    let mut result = Ge::ZERO;
+    for byte in scalar.iter().rev() {
+        for bit in (0..8).rev() {
+            result = result.double();
+            if (byte >> bit) & 1 == 1 {
+                result = (&result + &cached).to_full();
+            }
+        }
+    }
+    result
+}
+
+fn clear_cofactor(point: &Ge) -> Ge {
+    point.double().double().double()
+}
+
+fn has_small_order(point: &Ge) -> bool {
+    clear_cofactor(point).to_bytes() == Ge::ZERO.to_bytes()
+}
+
+fn is_canonical_point(bytes: &[u8; 32]) -> bool {
+    let mut y = *bytes;
+    y[31] &= 0x7f;
+    Fe::from_bytes(&y).to_bytes() == y
+}
+
+/// Legendre symbol `z^((p-1)/2)`, computed as `(z^((p-5)/8))^4 * z^2`
+fn chi(z: &Fe) -> Fe {
+    &z.pow25523().square_repeatdly(2) * &z.square()
+}
+
+fn hash_to_curve_elligator2(public_key: &[u8; 32], alpha: &[u8]) -> Option<Ge> {
+    let mut hasher = Sha512::new();
+    hasher.update_mut(&[VRF_SUITE, 0x01]);
+    hasher.update_mut(public_key);
+    hasher.update_mut(alpha);
+    let hash = hasher.finalize();
+    let mut r = <[u8; 32]>::try_from(&hash[..32]).unwrap();
+    r[31] &= 0x7f;
+
+    let mut a_bytes = [0u8; 32];
+    a_bytes[..4].copy_from_slice(&486662u32.to_le_bytes());
+    let curve_a = Fe::from_bytes(&a_bytes);
+    let r = Fe::from_bytes(&r);
+
+    // x = -A / (1 + 2r^2)
+    let r2 = r.square();
+    let denominator = &(&r2 + &r2) + &Fe::ONE;
+    let x = -&(&curve_a * &denominator.invert());
+
+    // e = chi(x^3 + A x^2 + x)
+    let x2 = x.square();
+    let e = chi(&(&(&(&x2 * &x) + &x) + &(&x2 * &curve_a)));
+    let minus_one = -&Fe::ONE;
+    let x = if e.to_bytes() == minus_one.to_bytes() {
+        &(-&x) - &curve_a
+    } else {
+        x
+    };
+
+    // Montgomery u to Edwards y = (u - 1) / (u + 1), positive x
+    let y = &(&x - &Fe::ONE) * &(&x + &Fe::ONE).invert();
+    let point = decode_point(&y.to_bytes())?;
+    Some(clear_cofactor(&point))
+}
@@ -4,6 +4,8 @@ pub mod plutus;
 pub mod cbor;
 mod bingen;
 pub mod check_signatures;
+pub mod byron;
+pub mod header_crypto;
 mod js_value;
 pub mod validators;
 pub mod common;
@@ -1,13 +1,16 @@
 use crate::bingen::wasm_bindgen;
 use crate::js_error::JsError;
-use crate::plutus::data_mapper::{to_pallas_cost_modesl, to_pallas_utxos};
-use crate::common::{CostModels, UTxO};
+use crate::validators::input_contexts::UtxoInputContext;
+use crate::validators::phase_2::data_mapper::{decode_conway_tx, to_pallas_cost_modesl, to_pallas_utxo};
+use crate::common::{CostModels, ExUnits as TxExUnits, UTxO};
 use pallas_primitives::conway::{MintedTx, Redeemer, RedeemerTag};
 use pallas_primitives::ExUnits;
-use pallas_traverse::{Era, MultiEraTx};
+use pallas_traverse::MultiEraTx;
 use serde_json::{Map, Value};
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use uplc::machine::cost_model::ExBudget;
+use uplc::machine::Error as MachineError;
 use uplc::tx::error::Error;
 use uplc::tx::{eval, eval_phase_one, ResolvedInput, SlotConfig};
 use uplc::tx::{iter_redeemers, DataLookupTable};
@@ -16,14 +19,16 @@ use crate::js_value::{from_js_value, from_serde_json_value, JsValue};
 #[wasm_bindgen]
 pub fn get_utxo_list_from_tx(tx_hex: &str) -> Result<Vec<String>, JsError> {
     let tx_bytes = hex::decode(tx_hex).map_err(|e| JsError::new(&e.to_string()))?;
-    let mtx = MultiEraTx::decode_for_era(Era::Conway, &tx_bytes)
-        .map_err(|e| JsError::new(&e.to_string()))?;
-    let tx = match mtx {
-        MultiEraTx::Conway(tx) => tx.into_owned(),
-        _ => return Err(JsError::new("Invalid transaction type")),
-    };
+    let mtx = MultiEraTx::decode(&tx_bytes).map_err(|e| JsError::new(&e.to_string()))?;
+    let inputs = mtx
+        .inputs()
+        .into_iter()
+        .chain(mtx.reference_inputs())
+        .chain(mtx.collateral())
+        .map(|input| format!("{}#{}", input.hash(), input.index()))
+        .collect();
 
-    Ok(collect_inputs(&tx))
+    Ok(inputs)
 }
 
 #[wasm_bindgen]
@@ -31,13 +36,15 @@ pub fn execute_tx_scripts(
     tx_hex: &str,
     utxo_json: JsValue,
     cost_models_json: JsValue,
+    max_tx_ex_units_json: JsValue,
 ) -> Result<JsValue, JsError> {
     let tx_bytes = hex::decode(tx_hex).map_err(|e| JsError::new(&e.to_string()))?;
-    let mtx = MultiEraTx::decode_for_era(Era::Conway, &tx_bytes)
-        .map_err(|e| JsError::new(&e.to_string()))?;
-    let tx = match mtx {
-        MultiEraTx::Conway(tx) => tx.into_owned(),
-        _ => return Err(JsError::new("Invalid transaction type")),
+    let tx = match decode_conway_tx(&tx_bytes)? {
+        Some(tx) => tx,
+        None => {
+            return from_serde_json_value(&build_response_object(Vec::new()))
+                .map_err(|e| JsError::new(&e.to_string()))
+        }
     };
 
     // Gather all input identifiers from the transaction.
@@ -45,7 +52,16 @@ pub fn execute_tx_scripts(
 
     // Deserialize UTxO data and convert to the internal representation.
     let decoded_utxos: Vec<UTxO> = from_js_value(&utxo_json).map_err(|e| JsError::new(&e.to_string()))?;
-    let utxos = to_pallas_utxos(&decoded_utxos)?;
+    let utxos = decoded_utxos
+        .iter()
+        .map(|utxo| {
+            to_pallas_utxo(&UtxoInputContext {
+                utxo: utxo.clone(),
+                is_spent: false,
+            })
+            .map_err(|err| JsError::new(&err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     check_missed_utxos(&request_utxos, &decoded_utxos)?;
 
@@ -54,7 +70,16 @@ pub fn execute_tx_scripts(
     let cost_models: CostModels =
         from_js_value(&cost_models_json).map_err(|e| JsError::new(&e.to_string()))?;
     let cost_models = to_pallas_cost_modesl(&cost_models);
-    let exec_result = eval_all_redeemers(&tx, &utxos, Some(&cost_models), &slot_config, false)?;
+    let max_tx_ex_units: TxExUnits =
+        from_js_value(&max_tx_ex_units_json).map_err(|e| JsError::new(&e.to_string()))?;
+    let exec_result = eval_all_redeemers(
+        &tx,
+        &utxos,
+        Some(&cost_models),
+        &slot_config,
+        &max_tx_ex_units,
+        false,
+    )?;
 
     from_serde_json_value(&build_response_object(exec_result)).map_err(|e| JsError::new(&e.to_string()))
 }
@@ -101,7 +126,7 @@ fn check_missed_utxos(request_utxos: &[String], utxos: &[UTxO]) -> Result<(), Js
 
 /// Builds a JSON response object from the evaluation results.
 fn build_response_object(
-    exec_result: Vec<Result<(Redeemer, Redeemer), (Redeemer, Error)>>,
+    exec_result: Vec<RedeemerEvaluation>,
 ) -> Value {
     let response: Vec<Value> = exec_result
         .into_iter()
@@ -117,10 +142,13 @@ fn build_response_object(
                 );
                 Value::Object(obj)
             }
-            Err((original, err)) => {
+            Err((original, err, required)) => {
                 let mut obj = Map::new();
                 obj.insert("original_ex_units".to_string(), exec_units_to_json(original.ex_units));
-                obj.insert("error".to_string(), Value::String(err.to_string()));
+                if let Some(required) = required {
+                    obj.insert("calculated_ex_units".to_string(), exec_units_to_json(required.ex_units));
+                }
+                obj.insert("error".to_string(), Value::String(err));
                 obj.insert("redeemer_index".to_string(),  Value::String(original.index.to_string()));
                 obj.insert(
                     "redeemer_tag".to_string(),
@@ -159,14 +187,19 @@ fn input_to_request_format(input: &pallas_primitives::TransactionInput) -> Strin
     format!("{}#{}", hex::encode(input.transaction_id), input.index)
 }
 
+/// The original and the evaluated redeemer, or the error with the evaluated redeemer when
+/// the script only failed for running out of its declared budget.
+type RedeemerEvaluation = Result<(Redeemer, Redeemer), (Redeemer, String, Option<Redeemer>)>;
+
 /// Evaluates all redeemers in the transaction.
 fn eval_all_redeemers(
     tx: &MintedTx,
     utxos: &[ResolvedInput],
     cost_mdls: Option<&pallas_primitives::conway::CostModels>,
     slot_config: &SlotConfig,
+    max_tx_ex_units: &TxExUnits,
     run_phase_one: bool,
-) -> Result<Vec<Result<(Redeemer, Redeemer), (Redeemer, Error)>>, JsError> {
+) -> Result<Vec<RedeemerEvaluation>, JsError> {
     let lookup_table = DataLookupTable::from_transaction(tx, utxos);
 
     if run_phase_one {
@@ -175,7 +208,6 @@ fn eval_all_redeemers(
     }
 
     if let Some(redeemers) = tx.transaction_witness_set.redeemer.as_ref() {
-        let remaining_budget = ExBudget::default();
         let results = iter_redeemers(redeemers)
             .map(|(r_key, r_value, r_ex_units)| {
                 let redeemer = Redeemer {
@@ -184,6 +216,11 @@ fn eval_all_redeemers(
                     data: r_value.clone(),
                     ex_units: r_ex_units,
                 };
+                // Like the ledger, the script may only use the ex units declared by its redeemer
+                let declared_budget = ExBudget {
+                    mem: i64::try_from(r_ex_units.mem).unwrap_or(i64::MAX),
+                    cpu: i64::try_from(r_ex_units.steps).unwrap_or(i64::MAX),
+                };
                 match eval::eval_redeemer(
                     tx,
                     utxos,
@@ -191,10 +228,37 @@ fn eval_all_redeemers(
                     &redeemer,
                     &lookup_table,
                     cost_mdls,
-                    &remaining_budget,
+                    &declared_budget,
                 ) {
                     Ok(new_redeemer) => Ok((redeemer.clone(), new_redeemer)),
-                    Err(err) => Err((redeemer.clone(), err)),
+                    Err(err @ Error::Machine(MachineError::OutOfExError(_), _, _)) => {
+                        // What the script would have cost within the transaction maximum
+                        let max_budget = ExBudget {
+                            mem: i64::try_from(max_tx_ex_units.mem).unwrap_or(i64::MAX),
+                            cpu: i64::try_from(max_tx_ex_units.steps).unwrap_or(i64::MAX),
+                        };
+                        match eval::eval_redeemer(
+                            tx,
+                            utxos,
+                            slot_config,
+                            &redeemer,
+                            &lookup_table,
+                            cost_mdls,
+                            &max_budget,
+                        ) {
+                            Ok(required) => Err((redeemer.clone(), err.to_string(), Some(required))),
+                            Err(Error::Machine(MachineError::OutOfExError(_), _, _)) => Err((
+                                redeemer.clone(),
+                                format!(
+                                    "{}. The script exceeds the tx maximum execution units: {:?}",
+                                    err, max_tx_ex_units
+                                ),
+                                None,
+                            )),
+                            Err(_) => Err((redeemer.clone(), err.to_string(), None)),
+                        }
+                    }
+                    Err(err) => Err((redeemer.clone(), err.to_string(), None)),
                 }
             })
             .collect();
@@ -2,4 +2,3 @@ pub mod plutus_decoder;
 pub mod execute_tx_scripts;
 pub mod decoder_tools;
 pub mod plutus_script_normalizer;
-mod data_mapper;
@@ -0,0 +1,446 @@
+use crate::bingen::wasm_bindgen;
+use crate::js_error::JsError;
+use crate::validators::phase_1::errors::{
+    Phase1Error, Phase1Warning, ValidationPhase1Error, ValidationPhase1Warning,
+};
+use crate::validators::validation_result::ValidationResult;
+use cardano_serialization_lib as csl;
+use cryptoxide::hashing::blake2b_256;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// CIP-6 pool metadata limits
+const MAX_POOL_METADATA_SIZE: usize = 512;
+const MAX_POOL_NAME_LENGTH: usize = 50;
+const MAX_POOL_DESCRIPTION_LENGTH: usize = 255;
+const MAX_POOL_HOMEPAGE_LENGTH: usize = 64;
+const POOL_TICKER_LENGTH: std::ops::RangeInclusive<usize> = 3..=5;
+
+/// CIP-108 / CIP-119 field limits
+const MAX_PROPOSAL_TITLE_LENGTH: usize = 80;
+const MAX_PROPOSAL_ABSTRACT_LENGTH: usize = 2500;
+const MAX_DREP_GIVEN_NAME_LENGTH: usize = 80;
+const MAX_DREP_TEXT_LENGTH: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorDocumentsInput {
+    /// Fetched documents: URL -> hex encoded document bytes, exactly as served
+    pub documents: HashMap<String, String>,
+    /// Also check the CIP-100/CIP-108/CIP-119 JSON-LD structure and the CIP-6 pool metadata limits
+    #[serde(default)]
+    pub check_structure: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AnchorKind {
+    DRep,
+    CommitteeResignation,
+    Proposal,
+    Constitution,
+    Vote,
+    PoolMetadata,
+}
+
+impl AnchorKind {
+    fn standard(&self) -> &'static str {
+        match self {
+            AnchorKind::DRep => "CIP-119",
+            AnchorKind::Proposal => "CIP-108",
+            AnchorKind::CommitteeResignation | AnchorKind::Vote => "CIP-100",
+            AnchorKind::Constitution => "Constitution",
+            AnchorKind::PoolMetadata => "CIP-6",
+        }
+    }
+}
+
+struct AnchorReference {
+    kind: AnchorKind,
+    url: String,
+    expected_hash: String,
+    location: String,
+}
+
+#[wasm_bindgen]
+pub fn verify_anchor_documents_js(hex: &str, documents: &str) -> Result<String, JsError> {
+    let documents = serde_json::from_str(documents).map_err(|e| JsError::new(&e.to_string()))?;
+    let validation_result = verify_anchor_documents(hex, &documents)?;
+    serde_json::to_string(&validation_result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Checks off-chain documents referenced by a transaction, a single certificate or a single
+/// voting proposal against the hashes committed on-chain.
+pub fn verify_anchor_documents(
+    hex: &str,
+    input: &AnchorDocumentsInput,
+) -> Result<ValidationResult, JsError> {
+    let anchors = collect_anchors(hex)?;
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    for anchor in anchors {
+        let document = match input.documents.get(&anchor.url) {
+            Some(document) => document,
+            None => {
+                warnings.push(ValidationPhase1Warning::new(
+                    Phase1Warning::AnchorDocumentMissing { url: anchor.url },
+                    anchor.location,
+                ));
+                continue;
+            }
+        };
+        let document = hex::decode(document).map_err(|e| {
+            JsError::new(&format!(
+                "Failed to decode document for {}: {}",
+                anchor.url, e
+            ))
+        })?;
+
+        let actual_hash = hex::encode(blake2b_256(&document));
+        if actual_hash != anchor.expected_hash {
+            errors.push(ValidationPhase1Error::new(
+                Phase1Error::AnchorDocumentHashMismatch {
+                    url: anchor.url.clone(),
+                    expected_hash: anchor.expected_hash.clone(),
+                    actual_hash,
+                },
+                anchor.location.clone(),
+            ));
+        }
+
+        if input.check_structure {
+            let issues = match anchor.kind {
+                AnchorKind::PoolMetadata => check_pool_metadata(&document),
+                // The constitution is a plain text document, only its hash is committed to
+                AnchorKind::Constitution => Vec::new(),
+                _ => check_governance_metadata(&document, anchor.kind),
+            };
+            for message in issues {
+                warnings.push(ValidationPhase1Warning::new(
+                    Phase1Warning::AnchorDocumentInvalid {
+                        url: anchor.url.clone(),
+                        standard: anchor.kind.standard().to_string(),
+                        message,
+                    },
+                    anchor.location.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(ValidationResult::new_phase_1(errors, warnings))
+}
+
+fn collect_anchors(hex: &str) -> Result<Vec<AnchorReference>, JsError> {
+    let mut anchors = Vec::new();
+    if let Ok(tx) = csl::FixedTransaction::from_hex(hex) {
+        let tx_body = tx.body();
+        if let Some(certs) = tx_body.certs() {
+            for i in 0..certs.len() {
+                collect_certificate_anchors(
+                    &certs.get(i),
+                    format!("transaction.body.certs.{}", i),
+                    &mut anchors,
+                );
+            }
+        }
+        if let Some(proposals) = tx_body.voting_proposals() {
+            for i in 0..proposals.len() {
+                collect_proposal_anchors(
+                    &proposals.get(i),
+                    format!("transaction.body.voting_proposals.{}", i),
+                    &mut anchors,
+                );
+            }
+        }
+        if let Some(voting_procedures) = tx_body.voting_procedures() {
+            collect_vote_anchors(&voting_procedures, &mut anchors);
+        }
+    } else if let Ok(cert) = csl::Certificate::from_hex(hex) {
+        collect_certificate_anchors(&cert, "certificate".to_string(), &mut anchors);
+    } else if let Ok(proposal) = csl::VotingProposal::from_hex(hex) {
+        collect_proposal_anchors(&proposal, "voting_proposal".to_string(), &mut anchors);
+    } else {
+        return Err(JsError::new(
+            "Failed to parse input as a transaction, certificate or voting proposal",
+        ));
+    }
+    Ok(anchors)
+}
+
+fn collect_certificate_anchors(
+    cert: &csl::Certificate,
+    location: String,
+    anchors: &mut Vec<AnchorReference>,
+) {
+    let (kind, anchor) = match cert.kind() {
+        csl::CertificateKind::DRepRegistration => (
+            AnchorKind::DRep,
+            cert.as_drep_registration().and_then(|cert| cert.anchor()),
+        ),
+        csl::CertificateKind::DRepUpdate => (
+            AnchorKind::DRep,
+            cert.as_drep_update().and_then(|cert| cert.anchor()),
+        ),
+        csl::CertificateKind::CommitteeColdResign => (
+            AnchorKind::CommitteeResignation,
+            cert.as_committee_cold_resign()
+                .and_then(|cert| cert.anchor()),
+        ),
+        csl::CertificateKind::PoolRegistration => {
+            let pool_metadata = cert
+                .as_pool_registration()
+                .and_then(|cert| cert.pool_params().pool_metadata());
+            if let Some(pool_metadata) = pool_metadata {
+                anchors.push(AnchorReference {
+                    kind: AnchorKind::PoolMetadata,
+                    url: pool_metadata.url().url(),
+                    expected_hash: pool_metadata.pool_metadata_hash().to_hex(),
+                    location,
+                });
+            }
+            return;
+        }
+        _ => return,
+    };
+    if let Some(anchor) = anchor {
+        anchors.push(anchor_reference(kind, &anchor, location));
+    }
+}
+
+fn collect_proposal_anchors(
+    proposal: &csl::VotingProposal,
+    location: String,
+    anchors: &mut Vec<AnchorReference>,
+) {
+    let constitution = proposal
+        .governance_action()
+        .as_new_constitution_action()
+        .map(|action| action.constitution().anchor());
+    if let Some(constitution) = constitution {
+        anchors.push(anchor_reference(
+            AnchorKind::Constitution,
+            &constitution,
+            format!("{}.governance_action.constitution", location),
+        ));
+    }
+    anchors.push(anchor_reference(
+        AnchorKind::Proposal,
+        &proposal.anchor(),
+        location,
+    ));
+}
+
+fn collect_vote_anchors(
+    voting_procedures: &csl::VotingProcedures,
+    anchors: &mut Vec<AnchorReference>,
+) {
+    let voters = voting_procedures.get_voters();
+    for i in 0..voters.len() {
+        let voter = match voters.get(i) {
+            Some(voter) => voter,
+            None => continue,
+        };
+        let action_ids = voting_procedures.get_governance_action_ids_by_voter(&voter);
+        for j in 0..action_ids.len() {
+            let anchor = action_ids
+                .get(j)
+                .and_then(|action_id| voting_procedures.get(&voter, &action_id))
+                .and_then(|procedure| procedure.anchor());
+            if let Some(anchor) = anchor {
+                anchors.push(anchor_reference(
+                    AnchorKind::Vote,
+                    &anchor,
+                    format!("transaction.body.voting_procedures.{}.{}", i, j),
+                ));
+            }
+        }
+    }
+}
+
+fn anchor_reference(kind: AnchorKind, anchor: &csl::Anchor, location: String) -> AnchorReference {
+    AnchorReference {
+        kind,
+        url: anchor.url().url(),
+        expected_hash: anchor.anchor_data_hash().to_hex(),
+        location,
+    }
+}
+
+/// CIP-6: a JSON object of at most 512 bytes with name, description, ticker and homepage
+fn check_pool_metadata(document: &[u8]) -> Vec<String> {
+    let mut issues = Vec::new();
+    if document.len() > MAX_POOL_METADATA_SIZE {
+        issues.push(format!(
+            "Document is {} bytes, larger than {} bytes",
+            document.len(),
+            MAX_POOL_METADATA_SIZE
+        ));
+    }
+    let json = match parse_json_object(document) {
+        Ok(json) => json,
+        Err(message) => {
+            issues.push(message);
+            return issues;
+        }
+    };
+
+    check_text_field(&json, "name", Some(MAX_POOL_NAME_LENGTH), &mut issues);
+    check_text_field(
+        &json,
+        "description",
+        Some(MAX_POOL_DESCRIPTION_LENGTH),
+        &mut issues,
+    );
+    check_text_field(
+        &json,
+        "homepage",
+        Some(MAX_POOL_HOMEPAGE_LENGTH),
+        &mut issues,
+    );
+    if let Some(ticker) = check_text_field(&json, "ticker", None, &mut issues) {
+        let ticker_length = ticker.chars().count();
+        if !POOL_TICKER_LENGTH.contains(&ticker_length) {
+            issues.push(format!(
+                "\"ticker\" must be {} to {} characters long",
+                POOL_TICKER_LENGTH.start(),
+                POOL_TICKER_LENGTH.end()
+            ));
+        }
+    }
+    issues
+}
+
+/// CIP-100 common structure plus the CIP-108 (proposals) and CIP-119 (DReps) bodies
+fn check_governance_metadata(document: &[u8], kind: AnchorKind) -> Vec<String> {
+    let mut issues = Vec::new();
+    let json = match parse_json_object(document) {
+        Ok(json) => json,
+        Err(message) => return vec![message],
+    };
+
+    match json.get("@context") {
+        Some(Value::Object(_)) | Some(Value::String(_)) | Some(Value::Array(_)) => {}
+        _ => issues.push("Missing JSON-LD \"@context\"".to_string()),
+    }
+    match json.get("hashAlgorithm").and_then(Value::as_str) {
+        Some("blake2b-256") => {}
+        Some(algorithm) => issues.push(format!(
+            "\"hashAlgorithm\" is \"{}\", expected \"blake2b-256\"",
+            algorithm
+        )),
+        None => issues.push("Missing \"hashAlgorithm\"".to_string()),
+    }
+    match json.get("authors") {
+        Some(Value::Array(authors)) => {
+            for (i, author) in authors.iter().enumerate() {
+                let witness = author.get("witness");
+                let is_valid_witness =
+                    ["witnessAlgorithm", "publicKey", "signature"]
+                        .iter()
+                        .all(|field| {
+                            witness
+                                .and_then(|witness| witness.get(field))
+                                .map(Value::is_string)
+                                .unwrap_or(false)
+                        });
+                if !is_valid_witness {
+                    issues.push(format!(
+                        "\"authors.{}.witness\" must have witnessAlgorithm, publicKey and signature",
+                        i
+                    ));
+                }
+            }
+        }
+        None => {}
+        Some(_) => issues.push("\"authors\" must be an array".to_string()),
+    }
+
+    let body = match json.get("body") {
+        Some(Value::Object(_)) => &json["body"],
+        _ => {
+            issues.push("Missing \"body\" object".to_string());
+            return issues;
+        }
+    };
+    match kind {
+        AnchorKind::Proposal => {
+            check_text_field(
+                body,
+                "body.title",
+                Some(MAX_PROPOSAL_TITLE_LENGTH),
+                &mut issues,
+            );
+            check_text_field(
+                body,
+                "body.abstract",
+                Some(MAX_PROPOSAL_ABSTRACT_LENGTH),
+                &mut issues,
+            );
+            check_text_field(body, "body.motivation", None, &mut issues);
+            check_text_field(body, "body.rationale", None, &mut issues);
+        }
+        AnchorKind::DRep => {
+            check_text_field(
+                body,
+                "body.givenName",
+                Some(MAX_DREP_GIVEN_NAME_LENGTH),
+                &mut issues,
+            );
+            for field in ["body.objectives", "body.motivations", "body.qualifications"].iter() {
+                if body.get(field_name(field)).is_some() {
+                    check_text_field(body, field, Some(MAX_DREP_TEXT_LENGTH), &mut issues);
+                }
+            }
+        }
+        _ => {}
+    }
+    issues
+}
+
+fn parse_json_object(document: &[u8]) -> Result<Value, String> {
+    match serde_json::from_slice::<Value>(document) {
+        Ok(json) if json.is_object() => Ok(json),
+        Ok(_) => Err("Document must be a JSON object".to_string()),
+        Err(e) => Err(format!("Document is not valid JSON: {}", e)),
+    }
+}
+
+/// Last segment of a dotted field path
+fn field_name(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+/// Checks that a field is a string no longer than `max_length` characters.
+/// `field` is the dotted path used in messages, its last segment is looked up in `json`.
+fn check_text_field(
+    json: &Value,
+    field: &str,
+    max_length: Option<usize>,
+    issues: &mut Vec<String>,
+) -> Option<String> {
+    let text = match json.get(field_name(field)) {
+        Some(Value::String(text)) => text.clone(),
+        Some(_) => {
+            issues.push(format!("\"{}\" must be a string", field));
+            return None;
+        }
+        None => {
+            issues.push(format!("Missing \"{}\"", field));
+            return None;
+        }
+    };
+    if let Some(max_length) = max_length {
+        let length = text.chars().count();
+        if length > max_length {
+            issues.push(format!(
+                "\"{}\" is {} characters long, longer than {}",
+                field, length, max_length
+            ));
+        }
+    }
+    Some(text)
+}
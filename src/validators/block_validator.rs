@@ -0,0 +1,328 @@
+use crate::bingen::wasm_bindgen;
+use crate::common::ExUnits;
+use crate::js_error::JsError;
+use crate::validators::context_update::apply_transaction_to_context;
+use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::validation_result::ValidationResult;
+use crate::validators::validator::validate_transaction;
+use cardano_serialization_lib as csl;
+use cryptoxide::hashing::blake2b_256;
+use pallas_codec::minicbor::{self, data::Type, Decoder};
+use pallas_primitives::{alonzo, babbage};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub enum BlockValidationError {
+    /// The block body is bigger than `max_block_body_size`
+    BlockBodyTooBig { actual_size: u64, max_size: u64 },
+    /// The body size declared in the header differs from the actual body size
+    WrongBlockBodySize { actual_size: u64, header_size: u64 },
+    /// The sum of the redeemer budgets exceeds `max_block_execution_units`
+    BlockExUnitsTooBig {
+        actual_ex_units: ExUnits,
+        max_ex_units: ExUnits,
+    },
+    /// An index in the invalid transactions list does not point to a transaction
+    InvalidTransactionIndexOutOfRange { index: u64, tx_count: u64 },
+    /// The `is_valid` flag of a transaction does not match the result of its scripts
+    ValidationTagMismatch { tx_index: u64, is_valid: bool },
+    /// The body hash declared in the header differs from the hash of the body
+    BlockBodyHashMismatch {
+        expected_hash: String,
+        actual_hash: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BlockTransactionValidationResult {
+    pub index: u64,
+    pub tx_hash: String,
+    pub is_valid: bool,
+    pub result: ValidationResult,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BlockValidationResult {
+    pub block_hash: String,
+    pub slot: u64,
+    pub block_errors: Vec<BlockValidationError>,
+    pub transactions: Vec<BlockTransactionValidationResult>,
+}
+
+/// Raw CBOR slices of a post-Alonzo block
+pub(crate) struct RawBlock<'a> {
+    pub(crate) header: &'a [u8],
+    transaction_bodies: &'a [u8],
+    transaction_witness_sets: &'a [u8],
+    auxiliary_data_set: &'a [u8],
+    invalid_transactions: &'a [u8],
+}
+
+impl<'a> RawBlock<'a> {
+    pub(crate) fn decode(block_bytes: &'a [u8]) -> Result<Self, String> {
+        let mut decoder = Decoder::new(block_bytes);
+        let mut length = decoder.array().map_err(|e| e.to_string())?;
+        // Blocks served by the node are wrapped into `[era, block]`
+        if length == Some(2) && decoder.datatype().map_err(|e| e.to_string())? == Type::U8 {
+            decoder.u8().map_err(|e| e.to_string())?;
+            length = decoder.array().map_err(|e| e.to_string())?;
+        }
+        if length != Some(5) {
+            return Err("Only Alonzo and later blocks are supported".to_string());
+        }
+
+        let mut items = Vec::with_capacity(5);
+        for _ in 0..5 {
+            let start = decoder.position();
+            decoder.skip().map_err(|e| e.to_string())?;
+            items.push(&block_bytes[start..decoder.position()]);
+        }
+
+        Ok(Self {
+            header: items[0],
+            transaction_bodies: items[1],
+            transaction_witness_sets: items[2],
+            auxiliary_data_set: items[3],
+            invalid_transactions: items[4],
+        })
+    }
+
+    fn body_size(&self) -> u64 {
+        (self.transaction_bodies.len()
+            + self.transaction_witness_sets.len()
+            + self.auxiliary_data_set.len()
+            + self.invalid_transactions.len()) as u64
+    }
+
+    /// Hash of the concatenated hashes of the four body components
+    fn body_hash(&self) -> Vec<u8> {
+        let mut hashes = Vec::with_capacity(4 * 32);
+        hashes.extend_from_slice(&blake2b_256(self.transaction_bodies));
+        hashes.extend_from_slice(&blake2b_256(self.transaction_witness_sets));
+        hashes.extend_from_slice(&blake2b_256(self.auxiliary_data_set));
+        hashes.extend_from_slice(&blake2b_256(self.invalid_transactions));
+        blake2b_256(&hashes).to_vec()
+    }
+}
+
+/// Header fields needed to check the block body
+struct HeaderInfo {
+    slot: u64,
+    block_body_size: u64,
+    block_body_hash: Vec<u8>,
+}
+
+fn decode_header_info(header_bytes: &[u8]) -> Result<HeaderInfo, String> {
+    if let Ok(header) = minicbor::decode::<babbage::Header>(header_bytes) {
+        return Ok(HeaderInfo {
+            slot: header.header_body.slot,
+            block_body_size: header.header_body.block_body_size,
+            block_body_hash: header.header_body.block_body_hash.to_vec(),
+        });
+    }
+    let header = minicbor::decode::<alonzo::Header>(header_bytes)
+        .map_err(|e| format!("Failed to decode block header: {}", e))?;
+    Ok(HeaderInfo {
+        slot: header.header_body.slot,
+        block_body_size: header.header_body.block_body_size,
+        block_body_hash: header.header_body.block_body_hash.to_vec(),
+    })
+}
+
+fn decode_array_items(bytes: &[u8]) -> Result<Vec<&[u8]>, String> {
+    let mut decoder = Decoder::new(bytes);
+    let length = decoder
+        .array()
+        .map_err(|e| e.to_string())?
+        .ok_or("Indefinite length arrays are not supported in blocks")?;
+    let mut items = Vec::with_capacity(length as usize);
+    for _ in 0..length {
+        let start = decoder.position();
+        decoder.skip().map_err(|e| e.to_string())?;
+        items.push(&bytes[start..decoder.position()]);
+    }
+    Ok(items)
+}
+
+fn decode_auxiliary_data_set(bytes: &[u8]) -> Result<HashMap<u64, &[u8]>, String> {
+    let mut decoder = Decoder::new(bytes);
+    let length = decoder
+        .map()
+        .map_err(|e| e.to_string())?
+        .ok_or("Indefinite length maps are not supported in blocks")?;
+    let mut items = HashMap::new();
+    for _ in 0..length {
+        let index = decoder.u64().map_err(|e| e.to_string())?;
+        let start = decoder.position();
+        decoder.skip().map_err(|e| e.to_string())?;
+        items.insert(index, &bytes[start..decoder.position()]);
+    }
+    Ok(items)
+}
+
+fn decode_invalid_transactions(bytes: &[u8]) -> Result<Vec<u64>, String> {
+    let mut decoder = Decoder::new(bytes);
+    if decoder.datatype().map_err(|e| e.to_string())? == Type::Tag {
+        decoder.tag().map_err(|e| e.to_string())?;
+    }
+    decoder
+        .array_iter::<u64>()
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+fn assemble_transaction(
+    body: &[u8],
+    witness_set: &[u8],
+    is_valid: bool,
+    aux: Option<&[u8]>,
+) -> Vec<u8> {
+    let mut tx_bytes = Vec::with_capacity(body.len() + witness_set.len() + 2);
+    tx_bytes.push(0x84);
+    tx_bytes.extend_from_slice(body);
+    tx_bytes.extend_from_slice(witness_set);
+    tx_bytes.push(if is_valid { 0xf5 } else { 0xf4 });
+    match aux {
+        Some(aux) => tx_bytes.extend_from_slice(aux),
+        None => tx_bytes.push(0xf6),
+    }
+    tx_bytes
+}
+
+#[wasm_bindgen]
+pub fn validate_block_js(block_hex: &str, validation_context: &str) -> Result<String, JsError> {
+    let validation_context =
+        serde_json::from_str(validation_context).map_err(|e| JsError::new(&e.to_string()))?;
+    let validation_result = validate_block(block_hex, validation_context)?;
+    serde_json::to_string(&validation_result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Validates every transaction of the block in order. Each transaction sees the UTxOs and the
+/// certificate state produced by the transactions before it.
+pub fn validate_block(
+    block_hex: &str,
+    validation_context: ValidationInputContext,
+) -> Result<BlockValidationResult, JsError> {
+    let block_bytes = hex::decode(block_hex)
+        .map_err(|e| JsError::new(&format!("Failed to decode block hex: {}", e)))?;
+    let raw_block = RawBlock::decode(&block_bytes)
+        .map_err(|e| JsError::new(&format!("Failed to parse block: {}", e)))?;
+    let header = decode_header_info(raw_block.header).map_err(|e| JsError::new(&e))?;
+
+    let bodies = decode_array_items(raw_block.transaction_bodies)
+        .map_err(|e| JsError::new(&format!("Failed to parse transaction bodies: {}", e)))?;
+    let witness_sets = decode_array_items(raw_block.transaction_witness_sets)
+        .map_err(|e| JsError::new(&format!("Failed to parse witness sets: {}", e)))?;
+    let auxiliary_data_set = decode_auxiliary_data_set(raw_block.auxiliary_data_set)
+        .map_err(|e| JsError::new(&format!("Failed to parse auxiliary data set: {}", e)))?;
+    let invalid_transactions = decode_invalid_transactions(raw_block.invalid_transactions)
+        .map_err(|e| JsError::new(&format!("Failed to parse invalid transactions: {}", e)))?;
+    if bodies.len() != witness_sets.len() {
+        return Err(JsError::new(&format!(
+            "Block has {} transaction bodies but {} witness sets",
+            bodies.len(),
+            witness_sets.len()
+        )));
+    }
+
+    let mut block_errors = Vec::new();
+    let tx_count = bodies.len() as u64;
+
+    let body_size = raw_block.body_size();
+    let max_body_size = validation_context.protocol_parameters.max_block_body_size as u64;
+    if body_size > max_body_size {
+        block_errors.push(BlockValidationError::BlockBodyTooBig {
+            actual_size: body_size,
+            max_size: max_body_size,
+        });
+    }
+    if body_size != header.block_body_size {
+        block_errors.push(BlockValidationError::WrongBlockBodySize {
+            actual_size: body_size,
+            header_size: header.block_body_size,
+        });
+    }
+
+    let body_hash = raw_block.body_hash();
+    if body_hash != header.block_body_hash {
+        block_errors.push(BlockValidationError::BlockBodyHashMismatch {
+            expected_hash: hex::encode(&header.block_body_hash),
+            actual_hash: hex::encode(&body_hash),
+        });
+    }
+
+    for index in &invalid_transactions {
+        if *index >= tx_count {
+            block_errors.push(BlockValidationError::InvalidTransactionIndexOutOfRange {
+                index: *index,
+                tx_count,
+            });
+        }
+    }
+
+    let mut context = validation_context;
+    context.slot = header.slot;
+    let mut total_ex_units = ExUnits { mem: 0, steps: 0 };
+    let mut transactions = Vec::with_capacity(bodies.len());
+
+    for (index, (body, witness_set)) in bodies.iter().zip(witness_sets.iter()).enumerate() {
+        let index = index as u64;
+        let is_valid = !invalid_transactions.contains(&index);
+        let tx_bytes = assemble_transaction(
+            body,
+            witness_set,
+            is_valid,
+            auxiliary_data_set.get(&index).copied(),
+        );
+        let tx = csl::FixedTransaction::from_bytes(tx_bytes.clone()).map_err(|e| {
+            JsError::new(&format!("Failed to parse transaction {}: {:?}", index, e))
+        })?;
+
+        if let Some(redeemers) = tx.witness_set().redeemers() {
+            if let Ok(ex_units) = redeemers.total_ex_units() {
+                total_ex_units.mem += u64::from(ex_units.mem());
+                total_ex_units.steps += u64::from(ex_units.steps());
+            }
+        }
+
+        let result = validate_transaction(&hex::encode(&tx_bytes), context.clone())?;
+        let scripts_succeeded = result
+            .eval_redeemer_results
+            .iter()
+            .all(|redeemer| redeemer.success);
+        if scripts_succeeded != is_valid {
+            block_errors.push(BlockValidationError::ValidationTagMismatch {
+                tx_index: index,
+                is_valid,
+            });
+        }
+
+        apply_transaction_to_context(&mut context, &tx, is_valid);
+
+        transactions.push(BlockTransactionValidationResult {
+            index,
+            tx_hash: tx.transaction_hash().to_hex(),
+            is_valid,
+            result,
+        });
+    }
+
+    let max_ex_units = &context.protocol_parameters.max_block_execution_units;
+    if total_ex_units.mem > max_ex_units.mem || total_ex_units.steps > max_ex_units.steps {
+        block_errors.push(BlockValidationError::BlockExUnitsTooBig {
+            actual_ex_units: total_ex_units,
+            max_ex_units: max_ex_units.clone(),
+        });
+    }
+
+    Ok(BlockValidationResult {
+        block_hash: hex::encode(blake2b_256(raw_block.header)),
+        slot: header.slot,
+        block_errors,
+        transactions,
+    })
+}
@@ -72,4 +72,54 @@ pub struct GovernanceActionId {
 pub struct ProtocolVersion {
     pub major: u64,
     pub minor: u64,
-}
\ No newline at end of file
+}
+/// Ledger era whose rules a transaction is validated against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionEra {
+    Shelley,
+    Allegra,
+    Mary,
+    Alonzo,
+    Babbage,
+    Conway,
+}
+
+impl TransactionEra {
+    /// Era introduced by the given protocol major version
+    pub fn from_protocol_version(major: u32) -> Self {
+        match major {
+            0..=2 => TransactionEra::Shelley,
+            3 => TransactionEra::Allegra,
+            4 => TransactionEra::Mary,
+            5..=6 => TransactionEra::Alonzo,
+            7..=8 => TransactionEra::Babbage,
+            _ => TransactionEra::Conway,
+        }
+    }
+
+    pub fn to_pallas_era(self) -> pallas_traverse::Era {
+        match self {
+            TransactionEra::Shelley => pallas_traverse::Era::Shelley,
+            TransactionEra::Allegra => pallas_traverse::Era::Allegra,
+            TransactionEra::Mary => pallas_traverse::Era::Mary,
+            TransactionEra::Alonzo => pallas_traverse::Era::Alonzo,
+            TransactionEra::Babbage => pallas_traverse::Era::Babbage,
+            TransactionEra::Conway => pallas_traverse::Era::Conway,
+        }
+    }
+}
+
+impl Display for TransactionEra {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TransactionEra::Shelley => "Shelley",
+            TransactionEra::Allegra => "Allegra",
+            TransactionEra::Mary => "Mary",
+            TransactionEra::Alonzo => "Alonzo",
+            TransactionEra::Babbage => "Babbage",
+            TransactionEra::Conway => "Conway",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,497 @@
+use crate::bingen::wasm_bindgen;
+use crate::common::{TxInput, UTxO};
+use crate::js_error::JsError;
+use crate::validators::common::{GovernanceActionId, GovernanceActionType, LocalCredential};
+use crate::validators::helpers::{
+    credential_to_bech32_reward_address, csl_credential_to_local_credential,
+    csl_output_to_tx_output,
+};
+use crate::validators::input_contexts::{
+    AccountInputContext, CommitteeInputContext, DrepInputContext, GovActionInputContext,
+    PoolInputContext, UtxoInputContext, ValidationInputContext, VrfKeyHashInputContext,
+};
+use crate::validators::phase_1::validation::balance::{DepositType, RefundType, Withdrawal};
+use crate::validators::phase_1::validation::BalanceValidator;
+use crate::validators::validation_result::ValidationResult;
+use crate::validators::validator::validate_transaction;
+use cardano_serialization_lib as csl;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// Ledger state changes caused by a transaction. Entity lists hold the state after the
+/// transaction of every account, DRep, pool, committee member or gov action it changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerDelta {
+    /// Phase 2 validity tag of the transaction, an invalid transaction only consumes collateral
+    pub is_valid: bool,
+    pub consumed_utxos: Vec<UTxO>,
+    /// Consumed inputs missing from the UTxO set of the context
+    pub unresolved_inputs: Vec<TxInput>,
+    pub produced_utxos: Vec<UTxO>,
+    pub deposits: Vec<DepositType>,
+    pub refunds: Vec<RefundType>,
+    pub withdrawals: Vec<Withdrawal>,
+    /// Fee collected by the ledger, the collateral for an invalid transaction
+    pub fee: u64,
+    pub treasury_donation: u64,
+    pub accounts: Vec<AccountInputContext>,
+    pub dreps: Vec<DrepInputContext>,
+    pub pools: Vec<PoolInputContext>,
+    pub committee_members: Vec<CommitteeInputContext>,
+    pub new_gov_actions: Vec<GovActionInputContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyTransactionResult {
+    pub result: ValidationResult,
+    pub delta: LedgerDelta,
+}
+
+#[wasm_bindgen]
+pub fn apply_transaction_js(tx_hex: &str, validation_context: &str) -> Result<String, JsError> {
+    let validation_context =
+        serde_json::from_str(validation_context).map_err(|e| JsError::new(&e.to_string()))?;
+    let (result, delta) = apply_transaction(tx_hex, validation_context)?;
+    serde_json::to_string(&ApplyTransactionResult { result, delta })
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Validates a transaction and computes the ledger changes it causes according to its
+/// validity tag, as a chain follower would apply it.
+pub fn apply_transaction(
+    tx_hex: &str,
+    validation_context: ValidationInputContext,
+) -> Result<(ValidationResult, LedgerDelta), JsError> {
+    let tx = csl::FixedTransaction::from_hex(tx_hex)
+        .map_err(|e| JsError::new(&format!("Failed to parse transaction: {:?}", e)))?;
+    let result = validate_transaction(tx_hex, validation_context.clone())?;
+    let mut context = validation_context;
+    let delta = apply_transaction_to_context(&mut context, &tx, tx.is_valid());
+    Ok((result, delta))
+}
+
+/// Applies the effects of a transaction to the context so that the next transaction of the
+/// block is validated against the updated state, and returns the applied changes.
+pub(crate) fn apply_transaction_to_context(
+    context: &mut ValidationInputContext,
+    tx: &csl::FixedTransaction,
+    is_valid: bool,
+) -> LedgerDelta {
+    let tx_body = tx.body();
+    let tx_hash = tx.transaction_hash().to_hex();
+    let mut delta = LedgerDelta {
+        is_valid,
+        ..LedgerDelta::default()
+    };
+
+    if !is_valid {
+        // A transaction failing phase 2 only consumes its collateral
+        if let Some(collateral) = tx_body.collateral() {
+            for i in 0..collateral.len() {
+                spend_utxo(context, &collateral.get(i), &mut delta);
+            }
+        }
+        let mut collected: u64 = delta
+            .consumed_utxos
+            .iter()
+            .map(|utxo| lovelace(&utxo.output))
+            .sum();
+        if let Some(collateral_return) = tx_body.collateral_return() {
+            let utxo = add_utxo(
+                context,
+                &tx_hash,
+                tx_body.outputs().len() as u32,
+                &collateral_return,
+            );
+            collected = collected.saturating_sub(lovelace(&utxo.output));
+            delta.produced_utxos.push(utxo);
+        }
+        delta.fee = tx_body
+            .total_collateral()
+            .map(u64::from)
+            .unwrap_or(collected);
+        return delta;
+    }
+
+    let balance = BalanceValidator::new(&tx_body, context);
+    delta.deposits = balance.outputs.deposits;
+    delta.refunds = balance.inputs.refunds;
+    delta.withdrawals = balance.inputs.withdrawals;
+    delta.fee = u64::from(tx_body.fee());
+    delta.treasury_donation = tx_body.donation().map(u64::from).unwrap_or(0);
+
+    let accounts_before = context.account_contexts.clone();
+    let dreps_before = context.drep_contexts.clone();
+    let pools_before = context.pool_contexts.clone();
+    let committee_before: Vec<CommitteeInputContext> = context
+        .current_committee_members
+        .iter()
+        .chain(context.potential_committee_members.iter())
+        .cloned()
+        .collect();
+
+    let inputs = tx_body.inputs();
+    for i in 0..inputs.len() {
+        spend_utxo(context, &inputs.get(i), &mut delta);
+    }
+    let outputs = tx_body.outputs();
+    for i in 0..outputs.len() {
+        let utxo = add_utxo(context, &tx_hash, i as u32, &outputs.get(i));
+        delta.produced_utxos.push(utxo);
+    }
+
+    if let Some(withdrawals) = tx_body.withdrawals() {
+        let reward_addresses = withdrawals.keys();
+        for i in 0..reward_addresses.len() {
+            let reward_address = reward_addresses.get(i);
+            let address = reward_address
+                .to_address()
+                .to_bech32(None)
+                .unwrap_or_default();
+            let amount = withdrawals.get(&reward_address).map(u64::from).unwrap_or(0);
+            if let Some(account) = context
+                .account_contexts
+                .iter_mut()
+                .find(|account| account.bech32_address == address)
+            {
+                account.balance = account
+                    .balance
+                    .map(|balance| balance.saturating_sub(amount));
+            }
+        }
+    }
+
+    if let Some(certs) = tx_body.certs() {
+        for i in 0..certs.len() {
+            apply_certificate_to_context(context, &certs.get(i));
+        }
+    }
+
+    if let Some(proposals) = tx_body.voting_proposals() {
+        for i in 0..proposals.len() {
+            let gov_action = GovActionInputContext {
+                action_id: GovernanceActionId {
+                    tx_hash: tx.transaction_hash().to_bytes(),
+                    index: i as u32,
+                },
+                action_type: gov_action_type(&proposals.get(i).governance_action()),
+                is_active: true,
+            };
+            context.gov_action_contexts.push(gov_action.clone());
+            delta.new_gov_actions.push(gov_action);
+        }
+    }
+
+    delta.accounts = changed_entries(&accounts_before, &context.account_contexts);
+    delta.dreps = changed_entries(&dreps_before, &context.drep_contexts);
+    delta.pools = changed_entries(&pools_before, &context.pool_contexts);
+    let committee_after: Vec<CommitteeInputContext> = context
+        .current_committee_members
+        .iter()
+        .chain(context.potential_committee_members.iter())
+        .cloned()
+        .collect();
+    delta.committee_members = changed_entries(&committee_before, &committee_after);
+    delta
+}
+
+/// Entries of `after` that are new or differ from their state in `before`
+fn changed_entries<T: Clone + PartialEq>(before: &[T], after: &[T]) -> Vec<T> {
+    after
+        .iter()
+        .filter(|entry| !before.contains(entry))
+        .cloned()
+        .collect()
+}
+
+fn lovelace(output: &crate::common::TxOutput) -> u64 {
+    output
+        .find_ada_asset()
+        .and_then(|asset| asset.quantity.parse().ok())
+        .unwrap_or(0)
+}
+
+pub(crate) fn gov_action_type(gov_action: &csl::GovernanceAction) -> GovernanceActionType {
+    match gov_action.kind() {
+        csl::GovernanceActionKind::ParameterChangeAction => {
+            GovernanceActionType::ParameterChangeAction
+        }
+        csl::GovernanceActionKind::HardForkInitiationAction => {
+            GovernanceActionType::HardForkInitiationAction
+        }
+        csl::GovernanceActionKind::TreasuryWithdrawalsAction => {
+            GovernanceActionType::TreasuryWithdrawalsAction
+        }
+        csl::GovernanceActionKind::NoConfidenceAction => GovernanceActionType::NoConfidenceAction,
+        csl::GovernanceActionKind::UpdateCommitteeAction => {
+            GovernanceActionType::UpdateCommitteeAction
+        }
+        csl::GovernanceActionKind::NewConstitutionAction => {
+            GovernanceActionType::NewConstitutionAction
+        }
+        csl::GovernanceActionKind::InfoAction => GovernanceActionType::InfoAction,
+    }
+}
+
+/// Adds the outputs of a transaction rejected in phase 1 as already spent, so that the
+/// transactions depending on it are reported as spending missing inputs
+pub(crate) fn add_rejected_transaction_outputs(
+    context: &mut ValidationInputContext,
+    tx: &csl::FixedTransaction,
+) {
+    let tx_body = tx.body();
+    let tx_hash = tx.transaction_hash().to_hex();
+    let outputs = tx_body.outputs();
+    let collateral_return = tx_body.collateral_return();
+    for (index, output) in outputs.into_iter().chain(collateral_return.iter()).enumerate() {
+        let input = add_utxo(context, &tx_hash, index as u32, output).input;
+        if let Some(utxo) = context
+            .utxo_set
+            .iter_mut()
+            .find(|utxo| utxo.utxo.input == input)
+        {
+            utxo.is_spent = true;
+        }
+    }
+}
+
+fn spend_utxo(
+    context: &mut ValidationInputContext,
+    input: &csl::TransactionInput,
+    delta: &mut LedgerDelta,
+) {
+    let tx_hash = input.transaction_id().to_hex();
+    match context.utxo_set.iter_mut().find(|utxo| {
+        utxo.utxo.input.tx_hash == tx_hash && utxo.utxo.input.output_index == input.index()
+    }) {
+        Some(utxo) if !utxo.is_spent => {
+            utxo.is_spent = true;
+            delta.consumed_utxos.push(utxo.utxo.clone());
+        }
+        _ => delta.unresolved_inputs.push(TxInput {
+            tx_hash,
+            output_index: input.index(),
+        }),
+    }
+}
+
+fn add_utxo(
+    context: &mut ValidationInputContext,
+    tx_hash: &str,
+    output_index: u32,
+    output: &csl::TransactionOutput,
+) -> UTxO {
+    let utxo = UtxoInputContext {
+        utxo: UTxO {
+            input: TxInput {
+                tx_hash: tx_hash.to_string(),
+                output_index,
+            },
+            output: csl_output_to_tx_output(output),
+        },
+        is_spent: false,
+    };
+    match context
+        .utxo_set
+        .iter_mut()
+        .find(|existing| existing.utxo.input == utxo.utxo.input)
+    {
+        Some(existing) => *existing = utxo.clone(),
+        None => context.utxo_set.push(utxo.clone()),
+    }
+    utxo.utxo
+}
+
+fn account_context<'a>(
+    context: &'a mut ValidationInputContext,
+    credential: &csl::Credential,
+) -> &'a mut AccountInputContext {
+    let address = credential_to_bech32_reward_address(credential, &context.network_type);
+    let position = context
+        .account_contexts
+        .iter()
+        .position(|account| account.bech32_address == address);
+    let position = match position {
+        Some(position) => position,
+        None => {
+            context.account_contexts.push(AccountInputContext {
+                bech32_address: address,
+                is_registered: false,
+                payed_deposit: None,
+                delegated_to_drep: None,
+                delegated_to_pool: None,
+                balance: None,
+                registration_epoch: None,
+            });
+            context.account_contexts.len() - 1
+        }
+    };
+    &mut context.account_contexts[position]
+}
+
+fn register_account<'a>(
+    context: &'a mut ValidationInputContext,
+    credential: &csl::Credential,
+    deposit: u64,
+) -> &'a mut AccountInputContext {
+    let epoch = context.current_epoch();
+    let account = account_context(context, credential);
+    account.is_registered = true;
+    account.payed_deposit = Some(deposit);
+    account.balance = Some(0);
+    account.delegated_to_drep = None;
+    account.delegated_to_pool = None;
+    account.registration_epoch = Some(epoch);
+    account
+}
+
+fn deregister_account(context: &mut ValidationInputContext, credential: &csl::Credential) {
+    let account = account_context(context, credential);
+    account.is_registered = false;
+    account.payed_deposit = None;
+    account.balance = None;
+    account.delegated_to_drep = None;
+    account.delegated_to_pool = None;
+    account.registration_epoch = None;
+}
+
+fn drep_context<'a>(
+    context: &'a mut ValidationInputContext,
+    credential: &csl::Credential,
+) -> &'a mut DrepInputContext {
+    let drep = csl::DRep::new_from_credential(credential)
+        .to_bech32(true)
+        .unwrap_or_default();
+    let position = context
+        .drep_contexts
+        .iter()
+        .position(|context| context.bech32_drep == drep);
+    let position = match position {
+        Some(position) => position,
+        None => {
+            context.drep_contexts.push(DrepInputContext {
+                bech32_drep: drep,
+                is_registered: false,
+                payed_deposit: None,
+                expiry_epoch: None,
+                registration_epoch: None,
+            });
+            context.drep_contexts.len() - 1
+        }
+    };
+    &mut context.drep_contexts[position]
+}
+
+fn pool_context(context: &mut ValidationInputContext, pool_id: String) -> &mut PoolInputContext {
+    let position = context
+        .pool_contexts
+        .iter()
+        .position(|pool| pool.pool_id == pool_id);
+    let position = match position {
+        Some(position) => position,
+        None => {
+            context.pool_contexts.push(PoolInputContext {
+                pool_id,
+                is_registered: false,
+                retirement_epoch: None,
+                reward_account: None,
+            });
+            context.pool_contexts.len() - 1
+        }
+    };
+    &mut context.pool_contexts[position]
+}
+
+fn committee_members<'a>(
+    context: &'a mut ValidationInputContext,
+    cold_credential: &'a LocalCredential,
+) -> impl Iterator<Item = &'a mut CommitteeInputContext> {
+    context
+        .current_committee_members
+        .iter_mut()
+        .chain(context.potential_committee_members.iter_mut())
+        .filter(move |member| &member.committee_member_cold == cold_credential)
+}
+
+fn drep_to_string(drep: &csl::DRep) -> String {
+    drep.to_bech32(true).unwrap_or_default()
+}
+
+fn apply_certificate_to_context(context: &mut ValidationInputContext, cert: &csl::Certificate) {
+    let epoch = context.current_epoch();
+    let stake_key_deposit = context.protocol_parameters.stake_key_deposit;
+
+    if let Some(cert) = cert.as_stake_registration().or_else(|| cert.as_reg_cert()) {
+        let deposit = cert.coin().map(u64::from).unwrap_or(stake_key_deposit);
+        register_account(context, &cert.stake_credential(), deposit);
+    } else if let Some(cert) = cert
+        .as_stake_deregistration()
+        .or_else(|| cert.as_unreg_cert())
+    {
+        deregister_account(context, &cert.stake_credential());
+    } else if let Some(cert) = cert.as_stake_delegation() {
+        account_context(context, &cert.stake_credential()).delegated_to_pool =
+            Some(cert.pool_keyhash().to_hex());
+    } else if let Some(cert) = cert.as_vote_delegation() {
+        account_context(context, &cert.stake_credential()).delegated_to_drep =
+            Some(drep_to_string(&cert.drep()));
+    } else if let Some(cert) = cert.as_stake_and_vote_delegation() {
+        let account = account_context(context, &cert.stake_credential());
+        account.delegated_to_pool = Some(cert.pool_keyhash().to_hex());
+        account.delegated_to_drep = Some(drep_to_string(&cert.drep()));
+    } else if let Some(cert) = cert.as_stake_registration_and_delegation() {
+        let account = register_account(context, &cert.stake_credential(), u64::from(cert.coin()));
+        account.delegated_to_pool = Some(cert.pool_keyhash().to_hex());
+    } else if let Some(cert) = cert.as_vote_registration_and_delegation() {
+        let account = register_account(context, &cert.stake_credential(), u64::from(cert.coin()));
+        account.delegated_to_drep = Some(drep_to_string(&cert.drep()));
+    } else if let Some(cert) = cert.as_stake_vote_registration_and_delegation() {
+        let account = register_account(context, &cert.stake_credential(), u64::from(cert.coin()));
+        account.delegated_to_pool = Some(cert.pool_keyhash().to_hex());
+        account.delegated_to_drep = Some(drep_to_string(&cert.drep()));
+    } else if let Some(cert) = cert.as_pool_registration() {
+        let pool_params = cert.pool_params();
+        let pool_id = pool_params.operator().to_hex();
+        let vrf_key_hash = pool_params.vrf_keyhash().to_hex();
+        let reward_account = pool_params.reward_account().to_address().to_bech32(None).ok();
+        let pool = pool_context(context, pool_id.clone());
+        pool.is_registered = true;
+        pool.retirement_epoch = None;
+        pool.reward_account = reward_account;
+        context
+            .vrf_key_hash_contexts
+            .retain(|vrf_context| vrf_context.pool_id != pool_id);
+        context.vrf_key_hash_contexts.push(VrfKeyHashInputContext {
+            vrf_key_hash,
+            pool_id,
+        });
+    } else if let Some(cert) = cert.as_pool_retirement() {
+        pool_context(context, cert.pool_keyhash().to_hex()).retirement_epoch =
+            Some(cert.epoch() as u64);
+    } else if let Some(cert) = cert.as_drep_registration() {
+        let drep = drep_context(context, &cert.voting_credential());
+        drep.is_registered = true;
+        drep.payed_deposit = Some(u64::from(cert.coin()));
+        drep.registration_epoch = Some(epoch);
+    } else if let Some(cert) = cert.as_drep_deregistration() {
+        let drep = drep_context(context, &cert.voting_credential());
+        drep.is_registered = false;
+        drep.payed_deposit = None;
+        drep.expiry_epoch = None;
+        drep.registration_epoch = None;
+    } else if let Some(cert) = cert.as_committee_hot_auth() {
+        let cold_credential = csl_credential_to_local_credential(&cert.committee_cold_credential());
+        let hot_credential = csl_credential_to_local_credential(&cert.committee_hot_credential());
+        for member in committee_members(context, &cold_credential) {
+            member.committee_member_hot = Some(hot_credential.clone());
+        }
+    } else if let Some(cert) = cert.as_committee_cold_resign() {
+        let cold_credential = csl_credential_to_local_credential(&cert.committee_cold_credential());
+        for member in committee_members(context, &cold_credential) {
+            member.is_resigned = true;
+        }
+    }
+}
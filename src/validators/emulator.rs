@@ -0,0 +1,580 @@
+use crate::common::{CostModels, ExUnitPrices, ExUnits, SubCoin};
+use crate::js_error::JsError;
+use crate::validators::common::GovernanceActionId;
+use crate::validators::context_update::{
+    apply_transaction_to_context, gov_action_type, LedgerDelta,
+};
+use crate::validators::helpers::{
+    csl_credential_to_local_credential, epoch_to_first_slot, slot_to_epoch, slots_per_epoch,
+};
+use crate::validators::input_contexts::{
+    CommitteeInputContext, GovActionInputContext, ValidationInputContext,
+};
+use crate::validators::protocol_params::ProtocolParameters;
+use crate::validators::validation_result::ValidationResult;
+use crate::validators::validator::validate_transaction;
+use cardano_serialization_lib as csl;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// Ledger parameters the emulator needs that are not part of `ProtocolParameters`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EmulatorParameters {
+    /// Number of epochs a governance action stays active
+    pub gov_action_lifetime: u64,
+    /// Number of epochs after which a DRep without activity becomes inactive
+    pub drep_activity: u64,
+}
+
+impl Default for EmulatorParameters {
+    fn default() -> Self {
+        Self {
+            gov_action_lifetime: 6,
+            drep_activity: 20,
+        }
+    }
+}
+
+/// A submitted governance proposal with what is needed to expire or enact it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulatorProposal {
+    pub action_id: GovernanceActionId,
+    /// Hex encoded governance action
+    pub governance_action: String,
+    pub deposit: u64,
+    pub return_address: String,
+    pub proposed_epoch: u64,
+    pub expiry_epoch: u64,
+    pub is_ratified: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulatorPool {
+    pub pool_id: String,
+    pub reward_address: String,
+    pub deposit: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulatorRefund {
+    pub reward_address: String,
+    pub amount: u64,
+    /// The reward account is not registered and the amount went to the treasury
+    pub to_treasury: bool,
+}
+
+/// Changes made while entering a new epoch
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochBoundaryResult {
+    pub epoch: u64,
+    pub enacted_gov_actions: Vec<GovernanceActionId>,
+    pub expired_gov_actions: Vec<GovernanceActionId>,
+    pub retired_pools: Vec<String>,
+    /// DReps whose expiry epoch has passed, they stay registered but no longer count as active
+    pub inactive_dreps: Vec<String>,
+    pub refunds: Vec<EmulatorRefund>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitResult {
+    pub accepted: bool,
+    pub result: ValidationResult,
+    /// Applied changes, `None` if the transaction was rejected
+    pub delta: Option<LedgerDelta>,
+}
+
+/// In-memory ledger built on `ValidationInputContext`. Transactions are admitted with the
+/// validators and applied to the context, epoch boundaries process pool retirements, DRep
+/// expiry, gov action expiry and the enactment of ratified actions. Rewards are not computed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEmulator {
+    pub context: ValidationInputContext,
+    pub parameters: EmulatorParameters,
+    pub proposals: Vec<EmulatorProposal>,
+    pub pools: Vec<EmulatorPool>,
+}
+
+impl LedgerEmulator {
+    pub fn new(context: ValidationInputContext, parameters: EmulatorParameters) -> Self {
+        Self {
+            context,
+            parameters,
+            proposals: Vec::new(),
+            pools: Vec::new(),
+        }
+    }
+
+    pub fn from_snapshot(snapshot: &str) -> Result<Self, JsError> {
+        serde_json::from_str(snapshot).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// JSON snapshot of the whole emulator state, loadable with `from_snapshot`
+    pub fn snapshot(&self) -> Result<String, JsError> {
+        serde_json::to_string(self).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    pub fn slot(&self) -> u64 {
+        self.context.slot
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.context.current_epoch()
+    }
+
+    /// Epoch length of the network of the context
+    pub fn slots_per_epoch(&self) -> u64 {
+        slots_per_epoch(&self.context.network_type)
+    }
+
+    /// Validates a transaction against the current state and applies it if it would be
+    /// accepted by a node: no phase 1 errors and script results matching the validity tag.
+    pub fn submit_transaction(&mut self, tx_hex: &str) -> Result<SubmitResult, JsError> {
+        let tx = csl::FixedTransaction::from_hex(tx_hex)
+            .map_err(|e| JsError::new(&format!("Failed to parse transaction: {:?}", e)))?;
+        let result = validate_transaction(tx_hex, self.context.clone())?;
+        let scripts_succeeded = result
+            .eval_redeemer_results
+            .iter()
+            .all(|redeemer| redeemer.success);
+        if !result.errors.is_empty() || scripts_succeeded != tx.is_valid() {
+            return Ok(SubmitResult {
+                accepted: false,
+                result,
+                delta: None,
+            });
+        }
+
+        let delta = apply_transaction_to_context(&mut self.context, &tx, tx.is_valid());
+        if tx.is_valid() {
+            self.track_transaction(&tx);
+        }
+        Ok(SubmitResult {
+            accepted: true,
+            result,
+            delta: Some(delta),
+        })
+    }
+
+    /// Marks a proposal as ratified, it is enacted at the next epoch boundary
+    pub fn ratify_gov_action(&mut self, action_id: &GovernanceActionId) -> bool {
+        match self
+            .proposals
+            .iter_mut()
+            .find(|proposal| &proposal.action_id == action_id)
+        {
+            Some(proposal) => {
+                proposal.is_ratified = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn advance_slots(&mut self, slots: u64) -> Vec<EpochBoundaryResult> {
+        self.advance_to_slot(self.context.slot.saturating_add(slots))
+    }
+
+    /// Moves the emulator to `slot`, processing every epoch boundary crossed on the way
+    pub fn advance_to_slot(&mut self, slot: u64) -> Vec<EpochBoundaryResult> {
+        let mut boundaries = Vec::new();
+        let network_type = self.context.network_type.clone();
+        while slot_to_epoch(slot, &network_type) > self.epoch() {
+            self.context.slot = epoch_to_first_slot(self.epoch() + 1, &network_type);
+            boundaries.push(self.process_epoch_boundary());
+        }
+        self.context.slot = self.context.slot.max(slot);
+        boundaries
+    }
+
+    fn track_transaction(&mut self, tx: &csl::FixedTransaction) {
+        let tx_body = tx.body();
+        let epoch = self.epoch();
+        let drep_expiry = epoch + self.parameters.drep_activity;
+
+        if let Some(certs) = tx_body.certs() {
+            for i in 0..certs.len() {
+                let cert = certs.get(i);
+                if let Some(cert) = cert.as_pool_registration() {
+                    let pool_params = cert.pool_params();
+                    let pool_id = pool_params.operator().to_hex();
+                    let reward_address = pool_params
+                        .reward_account()
+                        .to_address()
+                        .to_bech32(None)
+                        .unwrap_or_default();
+                    match self.pools.iter_mut().find(|pool| pool.pool_id == pool_id) {
+                        // Re-registration only updates the parameters, the deposit stays
+                        Some(pool) => pool.reward_address = reward_address,
+                        None => self.pools.push(EmulatorPool {
+                            pool_id,
+                            reward_address,
+                            deposit: self.context.protocol_parameters.stake_pool_deposit,
+                        }),
+                    }
+                } else if let Some(cert) = cert.as_drep_registration() {
+                    self.set_drep_expiry(&cert.voting_credential(), drep_expiry);
+                } else if let Some(cert) = cert.as_drep_update() {
+                    self.set_drep_expiry(&cert.voting_credential(), drep_expiry);
+                }
+            }
+        }
+
+        if let Some(voting_procedures) = tx_body.voting_procedures() {
+            let voters = voting_procedures.get_voters();
+            for i in 0..voters.len() {
+                if let Some(credential) = voters.get(i).and_then(|voter| voter.to_drep_credential())
+                {
+                    self.set_drep_expiry(&credential, drep_expiry);
+                }
+            }
+        }
+
+        if let Some(proposals) = tx_body.voting_proposals() {
+            let tx_hash = tx.transaction_hash();
+            for i in 0..proposals.len() {
+                let proposal = proposals.get(i);
+                self.proposals.push(EmulatorProposal {
+                    action_id: GovernanceActionId {
+                        tx_hash: tx_hash.to_bytes(),
+                        index: i as u32,
+                    },
+                    governance_action: proposal.governance_action().to_hex(),
+                    deposit: u64::from(proposal.deposit()),
+                    return_address: proposal
+                        .reward_account()
+                        .to_address()
+                        .to_bech32(None)
+                        .unwrap_or_default(),
+                    proposed_epoch: epoch,
+                    expiry_epoch: epoch + self.parameters.gov_action_lifetime,
+                    is_ratified: false,
+                });
+            }
+        }
+    }
+
+    fn set_drep_expiry(&mut self, credential: &csl::Credential, expiry_epoch: u64) {
+        let drep = csl::DRep::new_from_credential(credential)
+            .to_bech32(true)
+            .unwrap_or_default();
+        if let Some(drep) = self
+            .context
+            .drep_contexts
+            .iter_mut()
+            .find(|context| context.bech32_drep == drep)
+        {
+            drep.expiry_epoch = Some(expiry_epoch);
+        }
+    }
+
+    fn process_epoch_boundary(&mut self) -> EpochBoundaryResult {
+        let epoch = self.epoch();
+        let mut boundary = EpochBoundaryResult {
+            epoch,
+            enacted_gov_actions: Vec::new(),
+            expired_gov_actions: Vec::new(),
+            retired_pools: Vec::new(),
+            inactive_dreps: Vec::new(),
+            refunds: Vec::new(),
+        };
+
+        // Ratified actions are enacted before the remaining ones expire
+        let proposals = std::mem::take(&mut self.proposals);
+        for proposal in proposals {
+            if proposal.is_ratified {
+                self.enact(&proposal);
+                boundary
+                    .enacted_gov_actions
+                    .push(proposal.action_id.clone());
+            } else if proposal.expiry_epoch < epoch {
+                boundary
+                    .expired_gov_actions
+                    .push(proposal.action_id.clone());
+            } else {
+                self.proposals.push(proposal);
+                continue;
+            }
+            self.context
+                .gov_action_contexts
+                .retain(|context| context.action_id != proposal.action_id);
+            let refund = self.refund(&proposal.return_address, proposal.deposit);
+            boundary.refunds.push(refund);
+        }
+
+        let retiring: Vec<(String, Option<String>)> = self
+            .context
+            .pool_contexts
+            .iter()
+            .filter(|pool| pool.is_registered && pool.retirement_epoch == Some(epoch))
+            .map(|pool| (pool.pool_id.clone(), pool.reward_account.clone()))
+            .collect();
+        for (pool_id, reward_account) in retiring {
+            if let Some(pool) = self
+                .context
+                .pool_contexts
+                .iter_mut()
+                .find(|pool| pool.pool_id == pool_id)
+            {
+                pool.is_registered = false;
+                pool.retirement_epoch = None;
+            }
+            self.context
+                .vrf_key_hash_contexts
+                .retain(|context| context.pool_id != pool_id);
+            for account in self.context.account_contexts.iter_mut() {
+                if account.delegated_to_pool.as_ref() == Some(&pool_id) {
+                    account.delegated_to_pool = None;
+                }
+            }
+            // Pools registered before the emulator started are assumed to have paid the current
+            // deposit. Without a known reward account the deposit goes to the treasury.
+            let (reward_address, deposit) =
+                match self.pools.iter().position(|pool| pool.pool_id == pool_id) {
+                    Some(position) => {
+                        let pool = self.pools.remove(position);
+                        (pool.reward_address, pool.deposit)
+                    }
+                    None => (
+                        reward_account.unwrap_or_default(),
+                        self.context.protocol_parameters.stake_pool_deposit,
+                    ),
+                };
+            let refund = self.refund(&reward_address, deposit);
+            boundary.refunds.push(refund);
+            boundary.retired_pools.push(pool_id);
+        }
+
+        boundary.inactive_dreps = self
+            .context
+            .drep_contexts
+            .iter()
+            .filter(|drep| drep.is_registered)
+            .filter(|drep| drep.expiry_epoch.is_some_and(|expiry| expiry < epoch))
+            .map(|drep| drep.bech32_drep.clone())
+            .collect();
+
+        boundary
+    }
+
+    /// Returns a deposit to a reward account, or to the treasury if it is not registered
+    fn refund(&mut self, reward_address: &str, amount: u64) -> EmulatorRefund {
+        match self
+            .context
+            .account_contexts
+            .iter_mut()
+            .find(|account| account.bech32_address == reward_address && account.is_registered)
+        {
+            Some(account) => {
+                account.balance = Some(account.balance.unwrap_or(0) + amount);
+                EmulatorRefund {
+                    reward_address: reward_address.to_string(),
+                    amount,
+                    to_treasury: false,
+                }
+            }
+            None => {
+                self.context.treasury_value += amount;
+                EmulatorRefund {
+                    reward_address: reward_address.to_string(),
+                    amount,
+                    to_treasury: true,
+                }
+            }
+        }
+    }
+
+    fn enact(&mut self, proposal: &EmulatorProposal) {
+        let gov_action = match csl::GovernanceAction::from_hex(&proposal.governance_action) {
+            Ok(gov_action) => gov_action,
+            Err(_) => return,
+        };
+
+        if let Some(action) = gov_action.as_parameter_change_action() {
+            self.apply_parameter_update(&action.protocol_param_updates());
+        } else if let Some(action) = gov_action.as_hard_fork_initiation_action() {
+            let version = action.protocol_version();
+            self.context.protocol_parameters.protocol_version = (version.major(), version.minor());
+        } else if let Some(action) = gov_action.as_treasury_withdrawals_action() {
+            let withdrawals = action.withdrawals();
+            let reward_addresses = withdrawals.keys();
+            for i in 0..reward_addresses.len() {
+                let reward_address = reward_addresses.get(i);
+                let amount = withdrawals.get(&reward_address).map(u64::from).unwrap_or(0);
+                let address = reward_address
+                    .to_address()
+                    .to_bech32(None)
+                    .unwrap_or_default();
+                if let Some(account) = self
+                    .context
+                    .account_contexts
+                    .iter_mut()
+                    .find(|account| account.bech32_address == address && account.is_registered)
+                {
+                    let amount = amount.min(self.context.treasury_value);
+                    self.context.treasury_value -= amount;
+                    account.balance = Some(account.balance.unwrap_or(0) + amount);
+                }
+            }
+        } else if gov_action.as_no_confidence_action().is_some() {
+            self.context.current_committee_members.clear();
+        } else if let Some(action) = gov_action.as_new_committee_action() {
+            let members_to_remove = action.members_to_remove();
+            for i in 0..members_to_remove.len() {
+                let credential = csl_credential_to_local_credential(&members_to_remove.get(i));
+                self.context
+                    .current_committee_members
+                    .retain(|member| member.committee_member_cold != credential);
+            }
+            let new_members = action.committee().members_keys();
+            for i in 0..new_members.len() {
+                let credential = csl_credential_to_local_credential(&new_members.get(i));
+                if let Some(position) = self
+                    .context
+                    .potential_committee_members
+                    .iter()
+                    .position(|member| member.committee_member_cold == credential)
+                {
+                    let member = self.context.potential_committee_members.remove(position);
+                    self.context.current_committee_members.push(member);
+                } else if !self
+                    .context
+                    .current_committee_members
+                    .iter()
+                    .any(|member| member.committee_member_cold == credential)
+                {
+                    self.context
+                        .current_committee_members
+                        .push(CommitteeInputContext {
+                            committee_member_cold: credential,
+                            committee_member_hot: None,
+                            is_resigned: false,
+                        });
+                }
+            }
+        }
+
+        let enacted = GovActionInputContext {
+            action_id: proposal.action_id.clone(),
+            action_type: gov_action_type(&gov_action),
+            is_active: false,
+        };
+        self.context
+            .last_enacted_gov_action
+            .retain(|context| context.action_type != enacted.action_type);
+        self.context.last_enacted_gov_action.push(enacted);
+    }
+
+    fn apply_parameter_update(&mut self, update: &csl::ProtocolParamUpdate) {
+        let params: &mut ProtocolParameters = &mut self.context.protocol_parameters;
+        if let Some(value) = update.minfee_a() {
+            params.min_fee_coefficient_a = u64::from(value);
+        }
+        if let Some(value) = update.minfee_b() {
+            params.min_fee_constant_b = u64::from(value);
+        }
+        if let Some(value) = update.max_block_body_size() {
+            params.max_block_body_size = value;
+        }
+        if let Some(value) = update.max_tx_size() {
+            params.max_transaction_size = value;
+        }
+        if let Some(value) = update.max_block_header_size() {
+            params.max_block_header_size = value;
+        }
+        if let Some(value) = update.key_deposit() {
+            params.stake_key_deposit = u64::from(value);
+        }
+        if let Some(value) = update.pool_deposit() {
+            params.stake_pool_deposit = u64::from(value);
+        }
+        if let Some(value) = update.max_epoch() {
+            params.max_epoch_for_pool_retirement = value;
+        }
+        if let Some(value) = update.min_pool_cost() {
+            params.min_pool_cost = u64::from(value);
+        }
+        if let Some(value) = update.ada_per_utxo_byte() {
+            params.ada_per_utxo_byte = u64::from(value);
+        }
+        if let Some(value) = update.cost_models() {
+            apply_cost_models(&mut params.cost_models, &value);
+        }
+        if let Some(value) = update.execution_costs() {
+            params.execution_prices = ExUnitPrices {
+                mem_price: sub_coin(&value.mem_price()),
+                step_price: sub_coin(&value.step_price()),
+            };
+        }
+        if let Some(value) = update.max_tx_ex_units() {
+            params.max_tx_execution_units = ex_units(&value);
+        }
+        if let Some(value) = update.max_block_ex_units() {
+            params.max_block_execution_units = ex_units(&value);
+        }
+        if let Some(value) = update.max_value_size() {
+            params.max_value_size = value;
+        }
+        if let Some(value) = update.collateral_percentage() {
+            params.collateral_percentage = value;
+        }
+        if let Some(value) = update.max_collateral_inputs() {
+            params.max_collateral_inputs = value;
+        }
+        if let Some(value) = update.governance_action_deposit() {
+            params.governance_action_deposit = u64::from(value);
+        }
+        if let Some(value) = update.drep_deposit() {
+            params.drep_deposit = u64::from(value);
+        }
+        if let Some(value) = update.ref_script_coins_per_byte() {
+            params.reference_script_cost_per_byte = sub_coin(&value);
+        }
+        if let Some(value) = update.governance_action_validity_period() {
+            self.parameters.gov_action_lifetime = value as u64;
+        }
+        if let Some(value) = update.drep_inactivity_period() {
+            self.parameters.drep_activity = value as u64;
+        }
+    }
+}
+
+fn sub_coin(value: &csl::UnitInterval) -> SubCoin {
+    SubCoin {
+        numerator: u64::from(value.numerator()),
+        denominator: u64::from(value.denominator()),
+    }
+}
+
+fn ex_units(value: &csl::ExUnits) -> ExUnits {
+    ExUnits {
+        mem: u64::from(value.mem()),
+        steps: u64::from(value.steps()),
+    }
+}
+
+fn apply_cost_models(cost_models: &mut CostModels, update: &csl::Costmdls) {
+    let languages = update.keys();
+    for i in 0..languages.len() {
+        let language = languages.get(i);
+        let costs = match update.get(&language) {
+            Some(cost_model) => (0..cost_model.len())
+                .filter_map(|op| cost_model.get(op).ok())
+                .filter_map(|cost| cost.to_str().parse::<i64>().ok())
+                .collect(),
+            None => continue,
+        };
+        match language.kind() {
+            csl::LanguageKind::PlutusV1 => cost_models.plutus_v1 = Some(costs),
+            csl::LanguageKind::PlutusV2 => cost_models.plutus_v2 = Some(costs),
+            csl::LanguageKind::PlutusV3 => cost_models.plutus_v3 = Some(costs),
+        }
+    }
+}
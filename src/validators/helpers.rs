@@ -1,5 +1,6 @@
 use cardano_serialization_lib as csl;
 
+use crate::common::{Asset, TxOutput};
 use crate::validators::common::{LocalCredential, NetworkType};
 
 pub fn string_to_csl_address(address_str: &String) -> Result<csl::Address, String> {
@@ -15,6 +16,65 @@ pub fn string_to_csl_address(address_str: &String) -> Result<csl::Address, Strin
     }
 }
 
+/// Number of slots ahead of the current slot for which slot-to-time conversion is
+/// guaranteed to be stable (the `3k/f` safe zone of the hard fork combinator).
+pub fn stability_window_network(network_type: &NetworkType) -> u64 {
+    match network_type {
+        // k = 2160, f = 0.05
+        NetworkType::Mainnet | NetworkType::Preprod => 129600,
+        // k = 432, f = 0.05
+        NetworkType::Preview => 25920,
+    }
+}
+
+pub fn network_type_to_network_id(network_type: &NetworkType) -> u8 {
+    match network_type {
+        NetworkType::Mainnet => csl::NetworkInfo::mainnet().network_id(),
+        NetworkType::Preview => csl::NetworkInfo::testnet_preview().network_id(),
+        NetworkType::Preprod => csl::NetworkInfo::testnet_preprod().network_id(),
+    }
+}
+
+/// Shelley epoch length of the network, in slots
+pub fn slots_per_epoch(network_type: &NetworkType) -> u64 {
+    match network_type {
+        NetworkType::Mainnet | NetworkType::Preprod => 432000,
+        NetworkType::Preview => 86400,
+    }
+}
+
+/// Byron epoch length, in slots
+const BYRON_SLOTS_PER_EPOCH: u64 = 21600;
+
+/// First slot and epoch of the Shelley era on the network
+fn shelley_start(network_type: &NetworkType) -> (u64, u64) {
+    match network_type {
+        NetworkType::Mainnet => (4492800, 208),
+        NetworkType::Preprod => (86400, 4),
+        NetworkType::Preview => (0, 0),
+    }
+}
+
+/// Epoch of the slot, counting the Byron epochs before the Shelley era
+pub fn slot_to_epoch(slot: u64, network_type: &NetworkType) -> u64 {
+    let (shelley_slot, shelley_epoch) = shelley_start(network_type);
+    if slot < shelley_slot {
+        slot / BYRON_SLOTS_PER_EPOCH
+    } else {
+        shelley_epoch + (slot - shelley_slot) / slots_per_epoch(network_type)
+    }
+}
+
+/// First slot of the epoch, the inverse of `slot_to_epoch`
+pub fn epoch_to_first_slot(epoch: u64, network_type: &NetworkType) -> u64 {
+    let (shelley_slot, shelley_epoch) = shelley_start(network_type);
+    if epoch < shelley_epoch {
+        epoch * BYRON_SLOTS_PER_EPOCH
+    } else {
+        shelley_slot + (epoch - shelley_epoch) * slots_per_epoch(network_type)
+    }
+}
+
 pub fn csl_tx_input_to_string(tx_input: &csl::TransactionInput) -> String {
     format!("{}#{}", tx_input.transaction_id().to_hex(), tx_input.index())
 }
@@ -29,6 +89,21 @@ pub fn credential_to_bech32_reward_address(credential: &csl::Credential, network
     address.unwrap_or_else(|_| "".to_string())
 }
 
+/// Normalizes a DRep id given in CIP-105 or CIP-129 bech32 form to CIP-129 bech32.
+pub fn normalize_drep_id(drep_id: &str) -> String {
+    csl::DRep::from_bech32(drep_id)
+        .ok()
+        .and_then(|drep| drep.to_bech32(true).ok())
+        .unwrap_or_else(|| drep_id.to_string())
+}
+
+/// Normalizes a pool id given as bech32 (`pool1...`) or hex to lowercase hex.
+pub fn normalize_pool_id(pool_id: &str) -> String {
+    csl::Ed25519KeyHash::from_bech32(pool_id)
+        .map(|key_hash| key_hash.to_hex())
+        .unwrap_or_else(|_| pool_id.to_lowercase())
+}
+
 pub fn csl_credential_to_local_credential(credential: &csl::Credential) -> LocalCredential {
     match credential.kind() {
         csl::CredKind::Key => {
@@ -79,4 +154,56 @@ pub fn normalize_script_ref_raw(
     script_ref: &String,
 ) -> Result<Vec<u8>, String> {
     normalize_script_ref(script_ref).map(|script_ref| script_ref.to_bytes())
-}
\ No newline at end of file
+}
+/// Converts a CSL transaction output into the `TxOutput` shape used by the input context.
+pub fn csl_output_to_tx_output(output: &csl::TransactionOutput) -> TxOutput {
+    let address = output.address();
+    let address = match csl::ByronAddress::from_address(&address) {
+        Some(byron_address) => byron_address.to_base58(),
+        None => address.to_bech32(None).unwrap_or_else(|_| address.to_hex()),
+    };
+
+    let amount = output.amount();
+    let mut assets = vec![Asset {
+        unit: "lovelace".to_string(),
+        quantity: amount.coin().to_str(),
+    }];
+    if let Some(multiasset) = amount.multiasset() {
+        let policy_ids = multiasset.keys();
+        for i in 0..policy_ids.len() {
+            let policy_id = policy_ids.get(i);
+            if let Some(policy_assets) = multiasset.get(&policy_id) {
+                let asset_names = policy_assets.keys();
+                for j in 0..asset_names.len() {
+                    let asset_name = asset_names.get(j);
+                    if let Some(quantity) = policy_assets.get(&asset_name) {
+                        assets.push(Asset {
+                            unit: format!("{}{}", policy_id.to_hex(), hex::encode(asset_name.name())),
+                            quantity: quantity.to_str(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let script_ref = output.script_ref();
+    let script_hash = script_ref.as_ref().map(|script_ref| {
+        if let Some(native_script) = script_ref.native_script() {
+            native_script.hash().to_hex()
+        } else if let Some(plutus_script) = script_ref.plutus_script() {
+            plutus_script.hash().to_hex()
+        } else {
+            String::new()
+        }
+    });
+
+    TxOutput {
+        address,
+        amount: assets,
+        data_hash: output.data_hash().map(|data_hash| data_hash.to_hex()),
+        plutus_data: output.plutus_data().map(|plutus_data| plutus_data.to_hex()),
+        script_ref: script_ref.map(|script_ref| script_ref.to_hex()),
+        script_hash,
+    }
+}
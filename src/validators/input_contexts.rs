@@ -1,6 +1,7 @@
 use crate::{
     common::{TxInput, UTxO},
-    validators::common::{GovernanceActionId, GovernanceActionType, NetworkType},
+    validators::common::{GovernanceActionId, GovernanceActionType, NetworkType, TransactionEra},
+    validators::helpers::slot_to_epoch,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -21,7 +22,7 @@ pub struct NecessaryInputData {
     pub committee_members_hot: Vec<LocalCredential>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GovActionInputContext {
     pub action_id: GovernanceActionId,
@@ -29,15 +30,19 @@ pub struct GovActionInputContext {
     pub is_active: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DrepInputContext {
     pub bech32_drep: String,
     pub is_registered: bool,
     pub payed_deposit: Option<u64>,
+    pub expiry_epoch: Option<u64>,
+    /// Epoch of the registration, used with `deposit_history` when `payed_deposit` is unknown
+    #[serde(default)]
+    pub registration_epoch: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountInputContext {
     pub bech32_address: String,
@@ -46,14 +51,36 @@ pub struct AccountInputContext {
     pub delegated_to_drep: Option<String>,
     pub delegated_to_pool: Option<String>,
     pub balance: Option<u64>,
+    /// Epoch of the registration, used with `deposit_history` when `payed_deposit` is unknown
+    #[serde(default)]
+    pub registration_epoch: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PoolInputContext {
     pub pool_id: String,
     pub is_registered: bool,
     pub retirement_epoch: Option<u64>,
+    /// Reward account the deposit is refunded to when the pool retires
+    #[serde(default)]
+    pub reward_account: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VrfKeyHashInputContext {
+    pub vrf_key_hash: String,
+    pub pool_id: String,
+}
+
+/// Deposit parameters in effect from `epoch` until the next entry
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositParametersInputContext {
+    pub epoch: u64,
+    pub stake_key_deposit: u64,
+    pub drep_deposit: u64,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, JsonSchema)]
@@ -63,7 +90,7 @@ pub struct UtxoInputContext {
     pub is_spent: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CommitteeInputContext {
     pub committee_member_cold: LocalCredential,
@@ -71,6 +98,30 @@ pub struct CommitteeInputContext {
     pub is_resigned: bool,
 }
 
+/// Toggles for the output safety lints. All lints are enabled by default.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutputLintOptions {
+    pub script_output_without_datum: bool,
+    pub datum_hash_not_published: bool,
+    pub tokens_to_unusual_address: bool,
+    pub output_repeats_input: bool,
+    /// Inline datums bigger than this size (in bytes) produce a warning, `None` disables the lint
+    pub max_inline_datum_size: Option<u32>,
+}
+
+impl Default for OutputLintOptions {
+    fn default() -> Self {
+        Self {
+            script_output_without_datum: true,
+            datum_hash_not_published: true,
+            tokens_to_unusual_address: true,
+            output_repeats_input: true,
+            max_inline_datum_size: Some(4096),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidationInputContext {
@@ -86,6 +137,20 @@ pub struct ValidationInputContext {
     pub potential_committee_members: Vec<CommitteeInputContext>,
     pub treasury_value: u64,
     pub network_type: NetworkType,
+    /// VRF key hashes already used by registered pools
+    #[serde(default)]
+    pub vrf_key_hash_contexts: Vec<VrfKeyHashInputContext>,
+    #[serde(default)]
+    pub output_lint_options: OutputLintOptions,
+    /// History of the deposit parameters, used to infer deregistration refunds
+    #[serde(default)]
+    pub deposit_history: Vec<DepositParametersInputContext>,
+    /// Era to validate against, inferred from the protocol version when not set
+    #[serde(default)]
+    pub era: Option<TransactionEra>,
+    /// Adds a cost breakdown to each redeemer result. Scripts are evaluated a second time.
+    #[serde(default)]
+    pub profile_scripts: bool,
 }
 
 impl ValidationInputContext {
@@ -102,6 +167,7 @@ impl ValidationInputContext {
         network_type: NetworkType,
         current_committee_members: Vec<CommitteeInputContext>,
         potential_committee_members: Vec<CommitteeInputContext>,
+        vrf_key_hash_contexts: Vec<VrfKeyHashInputContext>,
     ) -> Self {
         Self {
             utxo_set,
@@ -116,9 +182,26 @@ impl ValidationInputContext {
             network_type,
             current_committee_members,
             potential_committee_members,
+            vrf_key_hash_contexts,
+            output_lint_options: OutputLintOptions::default(),
+            deposit_history: Vec::new(),
+            era: None,
+            profile_scripts: false,
         }
     }
 
+    /// Era selected by the caller, or the one matching the protocol version
+    pub fn transaction_era(&self) -> TransactionEra {
+        self.era.unwrap_or_else(|| {
+            TransactionEra::from_protocol_version(self.protocol_parameters.protocol_major_version())
+        })
+    }
+
+    /// Epoch of the context slot on its network
+    pub fn current_epoch(&self) -> u64 {
+        slot_to_epoch(self.slot, &self.network_type)
+    }
+
     pub fn find_utxo(&self, tx_hash: String, tx_index: u32) -> Option<&UtxoInputContext> {
         self.utxo_set.iter().find(|utxo| {
             utxo.utxo.input.tx_hash == tx_hash && utxo.utxo.input.output_index == tx_index
@@ -143,6 +226,23 @@ impl ValidationInputContext {
             .find(|pool| &pool.pool_id == pool_id)
     }
 
+    pub fn find_vrf_key_hash_context(
+        &self,
+        vrf_key_hash: &String,
+    ) -> Option<&VrfKeyHashInputContext> {
+        self.vrf_key_hash_contexts
+            .iter()
+            .find(|context| &context.vrf_key_hash == vrf_key_hash)
+    }
+
+    /// Deposit parameters that were in effect in the given epoch
+    pub fn find_deposit_parameters(&self, epoch: u64) -> Option<&DepositParametersInputContext> {
+        self.deposit_history
+            .iter()
+            .filter(|parameters| parameters.epoch <= epoch)
+            .max_by_key(|parameters| parameters.epoch)
+    }
+
     pub fn find_gov_action_context(
         &self,
         action_id: GovernanceActionId,
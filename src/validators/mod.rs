@@ -7,6 +7,15 @@ pub mod value;
 pub mod protocol_params;
 pub mod validation_result;
 pub mod validator;
+pub mod anchor_documents;
+pub mod block_validator;
+pub mod context_update;
+pub mod transaction_chain;
+pub mod emulator;
+pub mod ratification;
+pub mod node_errors;
+pub mod phase_one_consistency;
+pub mod script_contexts;
 
 #[cfg(test)]
 pub mod tests;
\ No newline at end of file
@@ -0,0 +1,1678 @@
+//! Translation of the errors returned by a node that rejects a transaction. The Conway ledger
+//! predicate failures are mapped to the errors `validate_transaction` reports, so node errors
+//! come with the same messages and hints.
+
+use crate::bingen::wasm_bindgen;
+use crate::common::TxInput;
+use crate::js_error::JsError;
+use crate::validators::common::{
+    FeeDecomposition, GovernanceActionId, LocalCredential, NetworkType, ProtocolVersion, Value,
+    Voter,
+};
+use crate::validators::helpers::credential_to_bech32_reward_address;
+use crate::validators::phase_1::errors::{Phase1Error, ValidationPhase1Error};
+use crate::validators::phase_2::errors::{Phase2Error, ValidationPhase2Error};
+use cardano_serialization_lib as csl;
+use pallas_codec::minicbor::{self, data::Type, Decoder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+/// Eras in the order of the hard fork combinator, which prefixes era specific errors with the index
+const ERA_NAMES: [&str; 7] = [
+    "Byron", "Shelley", "Allegra", "Mary", "Alonzo", "Babbage", "Conway",
+];
+const CONWAY_ERA_INDEX: u64 = 6;
+
+/// A node failure without a matching cquisitor error
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct UntranslatedNodeFailure {
+    /// Ledger rule that reported the failure, e.g. `LEDGER/UTXOW/UTXO`
+    pub rule: String,
+    /// Constructor name of the predicate failure, when known
+    pub name: Option<String>,
+    /// The failure as decoded CBOR, Haskell text or the original JSON
+    pub failure: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+pub struct NodeErrorTranslation {
+    /// Era of the failures, when the error carries the hard fork combinator wrapper
+    pub era: Option<String>,
+    pub errors: Vec<ValidationPhase1Error>,
+    pub phase2_errors: Vec<ValidationPhase2Error>,
+    pub untranslated_failures: Vec<UntranslatedNodeFailure>,
+}
+
+#[wasm_bindgen]
+pub fn translate_node_error_js(error: &str, network_type: &str) -> Result<String, JsError> {
+    let network_type: NetworkType =
+        serde_json::from_value(serde_json::Value::String(network_type.to_string()))
+            .map_err(|e| JsError::new(&e.to_string()))?;
+    let translation = translate_node_error(error, &network_type).map_err(|e| JsError::new(&e))?;
+    serde_json::to_string(&translation).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Translates a transaction submission error: the CBOR hex of `MsgRejectTx`, of the hard fork
+/// `ApplyTxErr` or of the list of ledger failures, the JSON of cardano-cli, cardano-submit-api
+/// or Ogmios, or the Haskell text of the failures.
+/// The network is used to render the credentials of failures as reward addresses.
+pub fn translate_node_error(
+    error: &str,
+    network_type: &NetworkType,
+) -> Result<NodeErrorTranslation, String> {
+    let error = error.trim();
+    let mut translator = Translator::new(network_type);
+    if let Ok(bytes) = hex::decode(error) {
+        translator.translate_cbor(&bytes)?;
+    } else if let Ok(json) = serde_json::from_str::<serde_json::Value>(error) {
+        translator.translate_json(&json);
+    } else {
+        translator.translate_text(error);
+    }
+    Ok(translator.result)
+}
+
+/// Ledger rules of the Conway era with the constructor names of their failures, indexed by
+/// the CBOR tag
+#[derive(Clone, Copy)]
+enum Rule {
+    Ledger,
+    Utxow,
+    Utxo,
+    Utxos,
+    Certs,
+    Cert,
+    Deleg,
+    Pool,
+    GovCert,
+    Gov,
+}
+
+impl Rule {
+    const ALL: [Rule; 10] = [
+        Rule::Ledger,
+        Rule::Utxow,
+        Rule::Utxo,
+        Rule::Utxos,
+        Rule::Certs,
+        Rule::Cert,
+        Rule::Deleg,
+        Rule::Pool,
+        Rule::GovCert,
+        Rule::Gov,
+    ];
+
+    fn path(self) -> &'static str {
+        match self {
+            Rule::Ledger => "LEDGER",
+            Rule::Utxow => "LEDGER/UTXOW",
+            Rule::Utxo => "LEDGER/UTXOW/UTXO",
+            Rule::Utxos => "LEDGER/UTXOW/UTXO/UTXOS",
+            Rule::Certs => "LEDGER/CERTS",
+            Rule::Cert => "LEDGER/CERTS/CERT",
+            Rule::Deleg => "LEDGER/CERTS/CERT/DELEG",
+            Rule::Pool => "LEDGER/CERTS/CERT/POOL",
+            Rule::GovCert => "LEDGER/CERTS/CERT/GOVCERT",
+            Rule::Gov => "LEDGER/GOV",
+        }
+    }
+
+    fn failure_names(self) -> &'static [&'static str] {
+        match self {
+            Rule::Ledger => &[
+                "",
+                "ConwayUtxowFailure",
+                "ConwayCertsFailure",
+                "ConwayGovFailure",
+                "ConwayWdrlNotDelegatedToDRep",
+                "ConwayTreasuryValueMismatch",
+                "ConwayTxRefScriptsSizeTooBig",
+                "ConwayMempoolFailure",
+                "ConwayWithdrawalsMissingAccounts",
+                "ConwayIncompleteWithdrawals",
+            ],
+            Rule::Utxow => &[
+                "UtxoFailure",
+                "InvalidWitnessesUTXOW",
+                "MissingVKeyWitnessesUTXOW",
+                "MissingScriptWitnessesUTXOW",
+                "ScriptWitnessNotValidatingUTXOW",
+                "MissingTxBodyMetadataHash",
+                "MissingTxMetadata",
+                "ConflictingMetadataHash",
+                "InvalidMetadata",
+                "ExtraneousScriptWitnessesUTXOW",
+                "MissingRedeemers",
+                "MissingRequiredDatums",
+                "NotAllowedSupplementalDatums",
+                "PPViewHashesDontMatch",
+                "UnspendableUTxONoDatumHash",
+                "ExtraRedeemers",
+                "MalformedScriptWitnesses",
+                "MalformedReferenceScripts",
+            ],
+            Rule::Utxo => &[
+                "UtxosFailure",
+                "BadInputsUTxO",
+                "OutsideValidityIntervalUTxO",
+                "MaxTxSizeUTxO",
+                "InputSetEmptyUTxO",
+                "FeeTooSmallUTxO",
+                "ValueNotConservedUTxO",
+                "WrongNetwork",
+                "WrongNetworkWithdrawal",
+                "OutputTooSmallUTxO",
+                "OutputBootAddrAttrsTooBig",
+                "OutputTooBigUTxO",
+                "InsufficientCollateral",
+                "ScriptsNotPaidUTxO",
+                "ExUnitsTooBigUTxO",
+                "CollateralContainsNonADA",
+                "WrongNetworkInTxBody",
+                "OutsideForecast",
+                "TooManyCollateralInputs",
+                "NoCollateralInputs",
+                "IncorrectTotalCollateralField",
+                "BabbageOutputTooSmallUTxO",
+                "BabbageNonDisjointRefInputs",
+            ],
+            Rule::Utxos => &["ValidationTagMismatch", "CollectErrors"],
+            Rule::Certs => &["WithdrawalsNotInRewardsCERTS", "CertFailure"],
+            Rule::Cert => &["", "DelegFailure", "PoolFailure", "GovCertFailure"],
+            Rule::Deleg => &[
+                "",
+                "IncorrectDepositDELEG",
+                "StakeKeyRegisteredDELEG",
+                "StakeKeyNotRegisteredDELEG",
+                "StakeKeyHasNonZeroRewardAccountBalanceDELEG",
+                "DelegateeDRepNotRegisteredDELEG",
+                "DelegateeStakePoolNotRegisteredDELEG",
+                "DepositIncorrectDELEG",
+                "RefundIncorrectDELEG",
+            ],
+            Rule::Pool => &[
+                "StakePoolNotRegisteredOnKeyPOOL",
+                "StakePoolRetirementWrongEpochPOOL",
+                "",
+                "StakePoolCostTooLowPOOL",
+                "WrongNetworkPOOL",
+                "PoolMedataHashTooBig",
+                "VRFKeyHashAlreadyRegistered",
+            ],
+            Rule::GovCert => &[
+                "ConwayDRepAlreadyRegistered",
+                "ConwayDRepNotRegistered",
+                "ConwayDRepIncorrectDeposit",
+                "ConwayCommitteeHasPreviouslyResigned",
+                "ConwayDRepIncorrectRefund",
+                "ConwayCommitteeIsUnknown",
+            ],
+            Rule::Gov => &[
+                "GovActionsDoNotExist",
+                "MalformedProposal",
+                "ProposalProcedureNetworkIdMismatch",
+                "TreasuryWithdrawalsNetworkIdMismatch",
+                "ProposalDepositIncorrect",
+                "DisallowedVoters",
+                "ConflictingCommitteeUpdate",
+                "ExpirationEpochTooSmall",
+                "InvalidPrevGovActionId",
+                "VotingOnExpiredGovAction",
+                "ProposalCantFollow",
+                "InvalidPolicyHash",
+                "DisallowedProposalDuringBootstrap",
+                "DisallowedVotesDuringBootstrap",
+                "VotersDoNotExist",
+                "ZeroTreasuryWithdrawals",
+                "ProposalReturnAccountDoesNotExist",
+                "TreasuryWithdrawalReturnAccountsDoNotExist",
+            ],
+        }
+    }
+
+    fn failure_name(self, tag: u64) -> Option<&'static str> {
+        self.failure_names()
+            .get(tag as usize)
+            .filter(|name| !name.is_empty())
+            .copied()
+    }
+}
+
+/// Generic CBOR tree, the failures are matched on its shape
+#[derive(Debug, Clone)]
+enum Cbor {
+    Int(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Cbor>),
+    Map(Vec<(Cbor, Cbor)>),
+    Tag(u64, Box<Cbor>),
+    Bool(bool),
+    Float(f64),
+    Null,
+}
+
+impl<'b, C> minicbor::Decode<'b, C> for Cbor {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut C) -> Result<Self, minicbor::decode::Error> {
+        match d.datatype()? {
+            Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::I8
+            | Type::I16
+            | Type::I32
+            | Type::I64
+            | Type::Int => Ok(Cbor::Int(i128::from(d.int()?))),
+            Type::Bytes | Type::BytesIndef => {
+                let mut bytes = Vec::new();
+                for chunk in d.bytes_iter()? {
+                    bytes.extend_from_slice(chunk?);
+                }
+                Ok(Cbor::Bytes(bytes))
+            }
+            Type::String | Type::StringIndef => {
+                let mut text = String::new();
+                for chunk in d.str_iter()? {
+                    text.push_str(chunk?);
+                }
+                Ok(Cbor::Text(text))
+            }
+            Type::Array | Type::ArrayIndef => Ok(Cbor::Array(
+                d.array_iter::<Cbor>()?.collect::<Result<_, _>>()?,
+            )),
+            Type::Map | Type::MapIndef => Ok(Cbor::Map(
+                d.map_iter::<Cbor, Cbor>()?.collect::<Result<_, _>>()?,
+            )),
+            Type::Tag => {
+                let tag = d.tag()?.as_u64();
+                Ok(Cbor::Tag(tag, Box::new(d.decode()?)))
+            }
+            Type::Bool => Ok(Cbor::Bool(d.bool()?)),
+            Type::F16 => Ok(Cbor::Float(d.f16()? as f64)),
+            Type::F32 => Ok(Cbor::Float(d.f32()? as f64)),
+            Type::F64 => Ok(Cbor::Float(d.f64()?)),
+            Type::Null | Type::Undefined | Type::Simple => {
+                d.skip()?;
+                Ok(Cbor::Null)
+            }
+            other => Err(minicbor::decode::Error::type_mismatch(other)),
+        }
+    }
+}
+
+impl Cbor {
+    /// Elements of an array, sets tagged with 258 included
+    fn items(&self) -> &[Cbor] {
+        match self {
+            Cbor::Array(items) => items,
+            Cbor::Tag(258, inner) => inner.items(),
+            _ => &[],
+        }
+    }
+
+    fn is_array(&self) -> bool {
+        matches!(self, Cbor::Array(_) | Cbor::Tag(258, _))
+    }
+
+    fn int(&self) -> Option<i128> {
+        match self {
+            Cbor::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn u64(&self) -> Option<u64> {
+        self.int().and_then(|value| u64::try_from(value).ok())
+    }
+
+    fn bytes(&self) -> Option<&[u8]> {
+        match self {
+            Cbor::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    fn hex(&self) -> Option<String> {
+        self.bytes().map(hex::encode)
+    }
+
+    /// `StrictMaybe` is encoded as an empty or a one element array
+    fn strict_maybe(&self) -> Option<&Cbor> {
+        self.items().first()
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Cbor::Int(value) => match i64::try_from(*value) {
+                Ok(value) => serde_json::Value::from(value),
+                Err(_) => serde_json::Value::String(value.to_string()),
+            },
+            Cbor::Bytes(bytes) => serde_json::Value::String(hex::encode(bytes)),
+            Cbor::Text(text) => serde_json::Value::String(text.clone()),
+            Cbor::Array(items) => items.iter().map(Cbor::to_json).collect(),
+            Cbor::Map(entries) => entries
+                .iter()
+                .map(|(key, value)| serde_json::json!([key.to_json(), value.to_json()]))
+                .collect(),
+            Cbor::Tag(tag, inner) => serde_json::json!({ "tag": tag, "value": inner.to_json() }),
+            Cbor::Bool(value) => serde_json::Value::Bool(*value),
+            Cbor::Float(value) => serde_json::json!(value),
+            Cbor::Null => serde_json::Value::Null,
+        }
+    }
+}
+
+/// Peels `MsgRejectTx` (`[2, reason]`), the `[error]` wrapper of an error from the current
+/// era and the hard fork wrapper (`[era, failures]`) off the error, returning the era index
+/// and the list of ledger failures
+fn unwrap_failures(error: &Cbor) -> Result<(Option<u64>, &[Cbor]), String> {
+    let mut node = error;
+    let mut era = None;
+    loop {
+        let items = node.items();
+        if !items.is_empty() && items.iter().all(Cbor::is_array) {
+            // A single failure has a tag field, the hard fork wrapper holds a list of failures
+            match items {
+                [wrapped] if is_era_wrapper(wrapped) => node = wrapped,
+                _ => return Ok((era, items)),
+            }
+            continue;
+        }
+        match items {
+            [Cbor::Int(index), inner] if inner.is_array() => {
+                era = u64::try_from(*index).ok();
+                node = inner;
+            }
+            _ => return Err("The CBOR is not a transaction submission error".to_string()),
+        }
+    }
+}
+
+fn is_era_wrapper(cbor: &Cbor) -> bool {
+    match cbor.items() {
+        [Cbor::Int(_), failures] => {
+            !failures.items().is_empty() && failures.items().iter().all(Cbor::is_array)
+        }
+        _ => false,
+    }
+}
+
+fn tx_input(cbor: &Cbor) -> Option<TxInput> {
+    match cbor.items() {
+        [tx_hash, index] => Some(TxInput {
+            tx_hash: tx_hash.hex()?,
+            output_index: u32::try_from(index.u64()?).ok()?,
+        }),
+        _ => None,
+    }
+}
+
+fn local_credential(cbor: &Cbor) -> Option<LocalCredential> {
+    match cbor.items() {
+        [kind, hash] => match kind.u64()? {
+            0 => Some(LocalCredential::KeyHash(hash.bytes()?.to_vec())),
+            1 => Some(LocalCredential::ScriptHash(hash.bytes()?.to_vec())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn address(bytes: &[u8]) -> String {
+    match csl::Address::from_bytes(bytes.to_vec()) {
+        Ok(address) => match csl::ByronAddress::from_address(&address) {
+            Some(byron_address) => byron_address.to_base58(),
+            None => address
+                .to_bech32(None)
+                .unwrap_or_else(|_| hex::encode(bytes)),
+        },
+        Err(_) => hex::encode(bytes),
+    }
+}
+
+fn gov_action_id(cbor: &Cbor) -> Option<GovernanceActionId> {
+    match cbor.items() {
+        [tx_hash, index] => Some(GovernanceActionId {
+            tx_hash: tx_hash.bytes()?.to_vec(),
+            index: u32::try_from(index.u64()?).ok()?,
+        }),
+        _ => None,
+    }
+}
+
+fn voter(cbor: &Cbor) -> Option<Voter> {
+    match cbor.items() {
+        [kind, hash] => {
+            let hash = hash.bytes()?.to_vec();
+            match kind.u64()? {
+                0 => Some(Voter::ConstitutionalCommitteeHotKeyHash(hash)),
+                1 => Some(Voter::ConstitutionalCommitteeHotScriptHash(hash)),
+                2 => Some(Voter::DRepKeyHash(hash)),
+                3 => Some(Voter::DRepScriptHash(hash)),
+                4 => Some(Voter::StakingPoolKeyHash(hash)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn protocol_version(cbor: &Cbor) -> Option<ProtocolVersion> {
+    match cbor.items() {
+        [major, minor] => Some(ProtocolVersion {
+            major: major.u64()?,
+            minor: minor.u64()?,
+        }),
+        _ => None,
+    }
+}
+
+fn value(cbor: &Cbor) -> Option<Value> {
+    let (coin, multiasset) = match cbor {
+        Cbor::Int(coin) => return Some(Value::new_from_coins(*coin)),
+        _ => match cbor.items() {
+            [coin, Cbor::Map(multiasset)] => (coin.u64()?, multiasset),
+            _ => return None,
+        },
+    };
+    let mut csl_multiasset = csl::MultiAsset::new();
+    for (policy_id, assets) in multiasset {
+        let policy_id = csl::ScriptHash::from_bytes(policy_id.bytes()?.to_vec()).ok()?;
+        let mut csl_assets = csl::Assets::new();
+        if let Cbor::Map(assets) = assets {
+            for (name, quantity) in assets {
+                let name = csl::AssetName::new(name.bytes()?.to_vec()).ok()?;
+                csl_assets.insert(&name, &csl::BigNum::from(quantity.u64()?));
+            }
+        }
+        csl_multiasset.insert(&policy_id, &csl_assets);
+    }
+    let csl_value = csl::Value::new_with_assets(&csl::BigNum::from(coin), &csl_multiasset);
+    Some(Value::new_from_csl_value(&csl_value))
+}
+
+/// Coin of an output in the legacy array or the post-Alonzo map format
+fn output_coin(cbor: &Cbor) -> Option<i128> {
+    let amount = match cbor {
+        Cbor::Map(fields) => &fields.iter().find(|(key, _)| key.u64() == Some(1))?.1,
+        _ => cbor.items().get(1)?,
+    };
+    match amount {
+        Cbor::Int(coin) => Some(*coin),
+        _ => amount.items().first()?.int(),
+    }
+}
+
+/// `(actual, limit)` of a `Mismatch` failing a "less than or equal" relation. The actual
+/// value is the bigger one whatever the order of the two fields.
+fn exceeded(first: &Cbor, second: &Cbor) -> Option<(u64, u64)> {
+    let (first, second) = (first.u64()?, second.u64()?);
+    Some((first.max(second), first.min(second)))
+}
+
+/// Node errors only carry the minimum fee, not its parts
+fn unknown_fee_decomposition() -> FeeDecomposition {
+    FeeDecomposition {
+        tx_size_fee: 0,
+        reference_scripts_fee: 0,
+        execution_units_fee: 0,
+    }
+}
+
+fn plutus_language(cbor: &Cbor) -> Option<String> {
+    match cbor.u64()? {
+        0 => Some("PlutusV1".to_string()),
+        1 => Some("PlutusV2".to_string()),
+        2 => Some("PlutusV3".to_string()),
+        _ => None,
+    }
+}
+
+struct Translator<'a> {
+    network_type: &'a NetworkType,
+    result: NodeErrorTranslation,
+}
+
+impl<'a> Translator<'a> {
+    fn new(network_type: &'a NetworkType) -> Self {
+        Self {
+            network_type,
+            result: NodeErrorTranslation::default(),
+        }
+    }
+
+    fn phase1(&mut self, rule: Rule, error: Phase1Error) {
+        self.result
+            .errors
+            .push(ValidationPhase1Error::new(error, rule.path().to_string()));
+    }
+
+    fn phase2(&mut self, rule: Rule, error: Phase2Error) {
+        self.result
+            .phase2_errors
+            .push(ValidationPhase2Error::new(error, rule.path().to_string()));
+    }
+
+    fn untranslated(&mut self, rule: &str, name: Option<&str>, failure: serde_json::Value) {
+        self.result
+            .untranslated_failures
+            .push(UntranslatedNodeFailure {
+                rule: rule.to_string(),
+                name: name.map(str::to_string),
+                failure,
+            });
+    }
+
+    fn reward_address(&self, credential: &LocalCredential) -> Option<String> {
+        let credential = match credential {
+            LocalCredential::KeyHash(hash) => {
+                csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(hash.clone()).ok()?)
+            }
+            LocalCredential::ScriptHash(hash) => {
+                csl::Credential::from_scripthash(&csl::ScriptHash::from_bytes(hash.clone()).ok()?)
+            }
+        };
+        Some(credential_to_bech32_reward_address(
+            &credential,
+            self.network_type,
+        ))
+    }
+
+    fn pool_id(bytes: &[u8]) -> Option<String> {
+        csl::Ed25519KeyHash::from_bytes(bytes.to_vec())
+            .ok()?
+            .to_bech32("pool")
+            .ok()
+    }
+
+    fn translate_cbor(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let error: Cbor =
+            minicbor::decode(bytes).map_err(|e| format!("Failed to decode CBOR: {}", e))?;
+        let (era, failures) = unwrap_failures(&error)?;
+        self.result.era = era
+            .and_then(|index| ERA_NAMES.get(index as usize))
+            .map(|name| name.to_string());
+        for failure in failures {
+            if era.is_none_or(|index| index == CONWAY_ERA_INDEX) {
+                self.translate(Rule::Ledger, failure);
+            } else {
+                self.untranslated(Rule::Ledger.path(), None, failure.to_json());
+            }
+        }
+        Ok(())
+    }
+
+    /// Translates a failure of the rule, keeping it untranslated when its shape isn't known
+    fn translate(&mut self, rule: Rule, failure: &Cbor) {
+        let fields = failure.items();
+        let tag = fields.first().and_then(Cbor::u64);
+        let counts = (
+            self.result.errors.len(),
+            self.result.phase2_errors.len(),
+            self.result.untranslated_failures.len(),
+        );
+        let translated = tag.and_then(|tag| match rule {
+            Rule::Ledger => self.ledger(tag, &fields[1..]),
+            Rule::Utxow => self.utxow(tag, &fields[1..]),
+            Rule::Utxo => self.utxo(tag, &fields[1..]),
+            Rule::Utxos => self.utxos(tag, &fields[1..]),
+            Rule::Certs => self.certs(tag, &fields[1..]),
+            Rule::Cert => self.cert(tag, &fields[1..]),
+            Rule::Deleg => self.deleg(tag, &fields[1..]),
+            Rule::Pool => self.pool(tag, &fields[1..]),
+            Rule::GovCert => self.gov_cert(tag, &fields[1..]),
+            Rule::Gov => self.gov(tag, &fields[1..]),
+        });
+        if translated.is_none() {
+            self.result.errors.truncate(counts.0);
+            self.result.phase2_errors.truncate(counts.1);
+            self.result.untranslated_failures.truncate(counts.2);
+            let name = tag.and_then(|tag| rule.failure_name(tag));
+            self.untranslated(rule.path(), name, failure.to_json());
+        }
+    }
+
+    fn ledger(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::Ledger;
+        match (tag, fields) {
+            (1, [failure]) => self.translate(Rule::Utxow, failure),
+            (2, [failure]) => self.translate(Rule::Certs, failure),
+            (3, [failure]) => self.translate(Rule::Gov, failure),
+            (4, [key_hashes]) => {
+                for key_hash in key_hashes.items() {
+                    let credential = LocalCredential::KeyHash(key_hash.bytes()?.to_vec());
+                    let reward_address = self.reward_address(&credential)?;
+                    self.phase1(
+                        rule,
+                        Phase1Error::WithdrawalNotAllowedBecauseNotDelegatedToDRep {
+                            reward_address,
+                        },
+                    );
+                }
+            }
+            (5, [declared, actual]) => self.phase1(
+                rule,
+                Phase1Error::TreasuryValueMismatch {
+                    declared_value: declared.u64()?,
+                    actual_value: actual.u64()?,
+                },
+            ),
+            (6, [first, second]) => {
+                let (actual_size, max_size) = exceeded(first, second)?;
+                self.phase1(
+                    rule,
+                    Phase1Error::RefScriptsSizeTooBig {
+                        actual_size,
+                        max_size,
+                    },
+                )
+            }
+            (7, [Cbor::Text(message)]) => self.phase1(
+                rule,
+                Phase1Error::UnknownError {
+                    message: message.clone(),
+                },
+            ),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn utxow(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::Utxow;
+        match (tag, fields) {
+            (0, [failure]) => self.translate(Rule::Utxo, failure),
+            (1, [vkeys]) => {
+                for vkey in vkeys.items() {
+                    let invalid_signature = match vkey.items() {
+                        [vkey, ..] => vkey.hex()?,
+                        [] => vkey.hex()?,
+                    };
+                    self.phase1(rule, Phase1Error::InvalidSignature { invalid_signature });
+                }
+            }
+            (2, [key_hashes]) => {
+                for key_hash in key_hashes.items() {
+                    let missing_key_hash = key_hash.hex()?;
+                    self.phase1(rule, Phase1Error::MissingVKeyWitnesses { missing_key_hash });
+                }
+            }
+            (3, [script_hashes]) => {
+                for script_hash in script_hashes.items() {
+                    let missing_script_hash = script_hash.hex()?;
+                    self.phase1(
+                        rule,
+                        Phase1Error::MissingScriptWitnesses {
+                            missing_script_hash,
+                        },
+                    );
+                }
+            }
+            (4, [script_hashes]) => {
+                for script_hash in script_hashes.items() {
+                    let native_script_hash = script_hash.hex()?;
+                    self.phase1(
+                        rule,
+                        Phase1Error::NativeScriptIsUnsuccessful { native_script_hash },
+                    );
+                }
+            }
+            (5, [_]) => self.phase1(rule, Phase1Error::MissingTxBodyMetadataHash),
+            (6, [_]) => self.phase1(rule, Phase1Error::MissingTxMetadata),
+            (7, [supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::ConflictingMetadataHash {
+                    expected_hash: expected.hex()?,
+                    actual_hash: supplied.hex()?,
+                },
+            ),
+            (8, []) => self.phase1(
+                rule,
+                Phase1Error::InvalidMetadata {
+                    message: "Metadata is not well formed".to_string(),
+                },
+            ),
+            (9, [script_hashes]) => {
+                for script_hash in script_hashes.items() {
+                    let extraneous_script = script_hash.hex()?;
+                    self.phase1(
+                        rule,
+                        Phase1Error::ExtraneousScriptWitnesses { extraneous_script },
+                    );
+                }
+            }
+            (11, [missing, _received]) => {
+                for datum_hash in missing.items() {
+                    let datum_hash = datum_hash.hex()?;
+                    self.phase1(rule, Phase1Error::MissingDatum { datum_hash });
+                }
+            }
+            (12, [not_allowed, _acceptable]) => {
+                for datum_hash in not_allowed.items() {
+                    let datum_hash = datum_hash.hex()?;
+                    self.phase1(rule, Phase1Error::ExtraneousDatumWitnesses { datum_hash });
+                }
+            }
+            (13, [provided, expected]) => self.phase1(
+                rule,
+                Phase1Error::ScriptDataHashMismatch {
+                    expected_hash: expected.strict_maybe().and_then(Cbor::hex),
+                    provided_hash: provided.strict_maybe().and_then(Cbor::hex),
+                },
+            ),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn utxo(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::Utxo;
+        match (tag, fields) {
+            (0, [failure]) => self.translate(Rule::Utxos, failure),
+            (1, [inputs]) => {
+                for input in inputs.items() {
+                    let invalid_input = tx_input(input)?;
+                    self.phase1(rule, Phase1Error::BadInputsUTxO { invalid_input });
+                }
+            }
+            (2, [interval, slot]) => {
+                let (start, end) = match interval.items() {
+                    [start, end] => (start.strict_maybe(), end.strict_maybe()),
+                    _ => return None,
+                };
+                self.phase1(
+                    rule,
+                    Phase1Error::OutsideValidityIntervalUTxO {
+                        current_slot: slot.u64()?,
+                        interval_start: start.and_then(Cbor::u64).unwrap_or(0),
+                        interval_end: end.and_then(Cbor::u64).unwrap_or(u64::MAX),
+                    },
+                )
+            }
+            (3, [first, second]) => {
+                let (actual_size, max_size) = exceeded(first, second)?;
+                self.phase1(
+                    rule,
+                    Phase1Error::MaxTxSizeUTxO {
+                        actual_size,
+                        max_size,
+                    },
+                )
+            }
+            (4, []) => self.phase1(rule, Phase1Error::InputSetEmptyUTxO),
+            (5, [first, second]) => {
+                // The fee is too small: the supplied fee is the smaller value
+                let (min_fee, actual_fee) = exceeded(first, second)?;
+                self.phase1(
+                    rule,
+                    Phase1Error::FeeTooSmallUTxO {
+                        actual_fee,
+                        min_fee,
+                        fee_decomposition: unknown_fee_decomposition(),
+                    },
+                )
+            }
+            (6, [consumed, produced]) => {
+                let input_sum = value(consumed)?;
+                let output_sum = value(produced)?;
+                let mut difference = input_sum.clone();
+                difference.subtract(&output_sum);
+                self.phase1(
+                    rule,
+                    Phase1Error::ValueNotConservedUTxO {
+                        input_sum,
+                        output_sum,
+                        difference,
+                    },
+                )
+            }
+            (7, [_network, addresses]) => {
+                let wrong_addresses = addresses
+                    .items()
+                    .iter()
+                    .map(|address_bytes| address_bytes.bytes().map(address))
+                    .collect::<Option<HashSet<String>>>()?;
+                self.phase1(rule, Phase1Error::WrongNetwork { wrong_addresses })
+            }
+            (8, [_network, reward_accounts]) => {
+                let wrong_addresses = reward_accounts
+                    .items()
+                    .iter()
+                    .map(|account| account.bytes().map(address))
+                    .collect::<Option<HashSet<String>>>()?;
+                self.phase1(
+                    rule,
+                    Phase1Error::WrongNetworkWithdrawal { wrong_addresses },
+                )
+            }
+            (11, [outputs]) => {
+                for output in outputs.items() {
+                    match output.items() {
+                        [actual_size, max_size, _output] => self.phase1(
+                            rule,
+                            Phase1Error::OutputsValueTooBig {
+                                actual_size: u32::try_from(actual_size.u64()?).ok()?,
+                                max_size: u32::try_from(max_size.u64()?).ok()?,
+                            },
+                        ),
+                        _ => return None,
+                    }
+                }
+            }
+            (12, [balance, required]) => self.phase1(
+                rule,
+                Phase1Error::InsufficientCollateral {
+                    total_collateral: balance.int()?,
+                    required_collateral: required.int()?,
+                },
+            ),
+            (13, [Cbor::Map(utxos)]) => {
+                for (input, _output) in utxos {
+                    let input = tx_input(input)?;
+                    self.phase1(
+                        rule,
+                        Phase1Error::CollateralIsLockedByScript {
+                            invalid_collateral: format!("{}#{}", input.tx_hash, input.output_index),
+                        },
+                    );
+                }
+            }
+            (14, [supplied, limit]) => match (supplied.items(), limit.items()) {
+                ([memory, steps], [max_memory, max_steps]) => self.phase1(
+                    rule,
+                    Phase1Error::ExUnitsTooBigUTxO {
+                        actual_memory_units: memory.u64()?,
+                        actual_steps_units: steps.u64()?,
+                        max_memory_units: max_memory.u64()?,
+                        max_steps_units: max_steps.u64()?,
+                    },
+                ),
+                _ => return None,
+            },
+            (15, [_value]) => {
+                self.phase1(rule, Phase1Error::CalculatedCollateralContainsNonAdaAssets)
+            }
+            (16, [supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::WrongNetworkInTxBody {
+                    actual_network: u8::try_from(supplied.u64()?).ok()?,
+                    expected_network: u8::try_from(expected.u64()?).ok()?,
+                },
+            ),
+            (18, [first, second]) => {
+                let (actual_count, max_count) = exceeded(first, second)?;
+                self.phase1(
+                    rule,
+                    Phase1Error::TooManyCollateralInputs {
+                        actual_count: u32::try_from(actual_count).ok()?,
+                        max_count: u32::try_from(max_count).ok()?,
+                    },
+                )
+            }
+            (19, []) => self.phase1(rule, Phase1Error::NoCollateralInputs),
+            (20, [balance, declared]) => self.phase1(
+                rule,
+                Phase1Error::IncorrectTotalCollateralField {
+                    declared_total: declared.int()?,
+                    actual_sum: balance.int()?,
+                },
+            ),
+            (21, [outputs]) => {
+                for output in outputs.items() {
+                    match output.items() {
+                        [output, min_amount] => self.phase1(
+                            rule,
+                            Phase1Error::OutputTooSmallUTxO {
+                                output_amount: output_coin(output)?,
+                                min_amount: min_amount.int()?,
+                            },
+                        ),
+                        _ => return None,
+                    }
+                }
+            }
+            (22, [inputs]) => {
+                for input in inputs.items() {
+                    let input = tx_input(input)?;
+                    self.phase1(rule, Phase1Error::ReferenceInputOverlapsWithInput { input });
+                }
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn utxos(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::Utxos;
+        match (tag, fields) {
+            // Scripts failed while the transaction claimed they would succeed
+            (0, [Cbor::Bool(true), description]) => match description.items() {
+                [failed_unexpectedly, failures] if failed_unexpectedly.u64() == Some(1) => {
+                    for failure in failures.items() {
+                        match failure.items() {
+                            [_, Cbor::Text(error), ..] => self.phase2(
+                                rule,
+                                Phase2Error::MachineError {
+                                    error: error.clone(),
+                                },
+                            ),
+                            _ => return None,
+                        }
+                    }
+                }
+                _ => return None,
+            },
+            (1, [errors]) => {
+                for error in errors.items() {
+                    match error.items() {
+                        [kind, purpose] if kind.u64() == Some(1) => self.phase2(
+                            rule,
+                            Phase2Error::MissingScriptForRedeemer {
+                                error: purpose.to_json().to_string(),
+                            },
+                        ),
+                        [kind, language] if kind.u64() == Some(2) => self.phase2(
+                            rule,
+                            Phase2Error::CostModelNotFound {
+                                language: plutus_language(language)?,
+                            },
+                        ),
+                        [kind, error] if kind.u64() == Some(3) => self.phase2(
+                            rule,
+                            Phase2Error::BuildTxContextError {
+                                error: error.to_json().to_string(),
+                            },
+                        ),
+                        _ => return None,
+                    }
+                }
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn certs(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        match (tag, fields) {
+            (1, [failure]) => self.translate(Rule::Cert, failure),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn cert(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        match (tag, fields) {
+            (1, [failure]) => self.translate(Rule::Deleg, failure),
+            (2, [failure]) => self.translate(Rule::Pool, failure),
+            (3, [failure]) => self.translate(Rule::GovCert, failure),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn deleg(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::Deleg;
+        match (tag, fields) {
+            (2, [credential]) => {
+                let reward_address = self.reward_address(&local_credential(credential)?)?;
+                self.phase1(rule, Phase1Error::StakeAlreadyRegistered { reward_address })
+            }
+            (3, [credential]) => {
+                let reward_address = self.reward_address(&local_credential(credential)?)?;
+                self.phase1(rule, Phase1Error::StakeNotRegistered { reward_address })
+            }
+            (6, [pool_key_hash]) => self.phase1(
+                rule,
+                Phase1Error::StakePoolNotRegistered {
+                    pool_id: Self::pool_id(pool_key_hash.bytes()?)?,
+                },
+            ),
+            (7, [supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::StakeRegistrationWrongDeposit {
+                    supplied_deposit: supplied.int()?,
+                    required_deposit: expected.int()?,
+                },
+            ),
+            (8, [supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::StakeDeregistrationWrongRefund {
+                    supplied_refund: supplied.int()?,
+                    required_refund: expected.int()?,
+                },
+            ),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn pool(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::Pool;
+        match (tag, fields) {
+            (0, [pool_key_hash]) => self.phase1(
+                rule,
+                Phase1Error::StakePoolNotRegistered {
+                    pool_id: Self::pool_id(pool_key_hash.bytes()?)?,
+                },
+            ),
+            (1, [current_epoch, specified_epoch, max_epoch]) => {
+                let current_epoch = current_epoch.u64()?;
+                self.phase1(
+                    rule,
+                    Phase1Error::WrongRetirementEpoch {
+                        specified_epoch: specified_epoch.u64()?,
+                        current_epoch,
+                        min_epoch: current_epoch + 1,
+                        max_epoch: max_epoch.u64()?,
+                    },
+                )
+            }
+            // Two mismatches: the epoch against the current one and against the maximum
+            (1, [specified_epoch, current_epoch, _, max_epoch]) => {
+                let current_epoch = current_epoch.u64()?;
+                self.phase1(
+                    rule,
+                    Phase1Error::WrongRetirementEpoch {
+                        specified_epoch: specified_epoch.u64()?,
+                        current_epoch,
+                        min_epoch: current_epoch + 1,
+                        max_epoch: max_epoch.u64()?,
+                    },
+                )
+            }
+            (3, [first, second]) => {
+                // The cost is too low: the specified cost is the smaller value
+                let (min_cost, specified_cost) = exceeded(first, second)?;
+                self.phase1(
+                    rule,
+                    Phase1Error::StakePoolCostTooLow {
+                        specified_cost,
+                        min_cost,
+                    },
+                )
+            }
+            (5, [pool_key_hash, hash_size]) => self.phase1(
+                rule,
+                Phase1Error::PoolMetadataHashTooBig {
+                    pool_id: Self::pool_id(pool_key_hash.bytes()?)?,
+                    hash_size: u32::try_from(hash_size.u64()?).ok()?,
+                    max_size: 32,
+                },
+            ),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn gov_cert(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::GovCert;
+        match (tag, fields) {
+            (2, [supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::DRepIncorrectDeposit {
+                    supplied_deposit: supplied.int()?,
+                    required_deposit: expected.int()?,
+                },
+            ),
+            (3, [credential]) => self.phase1(
+                rule,
+                Phase1Error::CommitteeHasPreviouslyResigned {
+                    committee_credential: local_credential(credential)?,
+                },
+            ),
+            (4, [supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::DRepDeregistrationWrongRefund {
+                    supplied_refund: supplied.int()?,
+                    required_refund: expected.int()?,
+                },
+            ),
+            (5, [credential]) => self.phase1(
+                rule,
+                Phase1Error::CommitteeIsUnknown {
+                    committee_key_hash: local_credential(credential)?,
+                },
+            ),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn gov(&mut self, tag: u64, fields: &[Cbor]) -> Option<()> {
+        let rule = Rule::Gov;
+        match (tag, fields) {
+            (0, [action_ids]) => {
+                let invalid_action_ids = action_ids
+                    .items()
+                    .iter()
+                    .map(gov_action_id)
+                    .collect::<Option<Vec<_>>>()?;
+                self.phase1(
+                    rule,
+                    Phase1Error::GovActionsDoNotExist { invalid_action_ids },
+                )
+            }
+            (2, [reward_account, network]) => self.phase1(
+                rule,
+                Phase1Error::ProposalProcedureNetworkIdMismatch {
+                    reward_account: address(reward_account.bytes()?),
+                    expected_network: u8::try_from(network.u64()?).ok()?,
+                },
+            ),
+            (3, [reward_accounts, network]) => {
+                let expected_network = u8::try_from(network.u64()?).ok()?;
+                for reward_account in reward_accounts.items() {
+                    let mismatched_account = address(reward_account.bytes()?);
+                    self.phase1(
+                        rule,
+                        Phase1Error::TreasuryWithdrawalsNetworkIdMismatch {
+                            mismatched_account,
+                            expected_network,
+                        },
+                    );
+                }
+            }
+            (5, [pairs]) => {
+                let disallowed_pairs = pairs
+                    .items()
+                    .iter()
+                    .map(|pair| match pair.items() {
+                        [voter_cbor, action_id] => {
+                            Some((voter(voter_cbor)?, gov_action_id(action_id)?))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                self.phase1(rule, Phase1Error::DisallowedVoters { disallowed_pairs })
+            }
+            (6, [credentials]) => {
+                for credential in credentials.items() {
+                    let conflicting_credentials = local_credential(credential)?;
+                    self.phase1(
+                        rule,
+                        Phase1Error::ConflictingCommitteeUpdate {
+                            conflicting_credentials,
+                        },
+                    );
+                }
+            }
+            (7, [Cbor::Map(expirations)]) => {
+                let invalid_expirations = expirations
+                    .iter()
+                    .map(|(credential, epoch)| Some((local_credential(credential)?, epoch.u64()?)))
+                    .collect::<Option<HashMap<_, _>>>()?;
+                self.phase1(
+                    rule,
+                    Phase1Error::ExpirationEpochTooSmall {
+                        invalid_expirations,
+                    },
+                )
+            }
+            (8, [proposal]) => self.phase1(
+                rule,
+                Phase1Error::InvalidPrevGovActionId {
+                    proposal: proposal.to_json(),
+                },
+            ),
+            (9, [pairs]) => {
+                for pair in pairs.items() {
+                    let expired_gov_action = match pair.items() {
+                        [_voter, action_id] => gov_action_id(action_id)?,
+                        _ => return None,
+                    };
+                    self.phase1(
+                        rule,
+                        Phase1Error::VotingOnExpiredGovAction { expired_gov_action },
+                    );
+                }
+            }
+            (10, [prev_action_id, supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::ProposalCantFollow {
+                    prev_gov_action_id: prev_action_id.strict_maybe().and_then(gov_action_id),
+                    supplied_version: protocol_version(supplied)?,
+                    expected_versions: vec![protocol_version(expected)?],
+                },
+            ),
+            (11, [supplied, expected]) => self.phase1(
+                rule,
+                Phase1Error::InvalidConstitutionPolicyHash {
+                    supplied_hash: supplied.strict_maybe().and_then(Cbor::hex),
+                    expected_hash: expected.strict_maybe().and_then(Cbor::hex),
+                },
+            ),
+            (14, [voters]) => {
+                for voter_cbor in voters.items() {
+                    let missing_voter = serde_json::to_value(voter(voter_cbor)?).ok()?;
+                    self.phase1(rule, Phase1Error::VoterDoNotExist { missing_voter });
+                }
+            }
+            (16, [reward_account]) => self.phase1(
+                rule,
+                Phase1Error::ProposalReturnAccountDoesNotExist {
+                    return_account: address(reward_account.bytes()?),
+                },
+            ),
+            (17, [reward_accounts]) => {
+                for reward_account in reward_accounts.items() {
+                    let missing_account = address(reward_account.bytes()?);
+                    self.phase1(
+                        rule,
+                        Phase1Error::TreasuryWithdrawalReturnAccountsDoNotExist { missing_account },
+                    );
+                }
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    /// Ogmios reports one failure with a code and a `data` object, cardano-cli and
+    /// cardano-submit-api embed the Haskell text of the failures in strings
+    fn translate_json(&mut self, json: &serde_json::Value) {
+        let ogmios_error = json.get("error").unwrap_or(json);
+        if let (Some(code), Some(message)) = (ogmios_error.get("code"), ogmios_error.get("message"))
+        {
+            let data = ogmios_error.get("data").unwrap_or(&serde_json::Value::Null);
+            if !self.ogmios(data) {
+                self.untranslated(
+                    &format!("ogmios/{}", code),
+                    message.as_str(),
+                    ogmios_error.clone(),
+                );
+            }
+            return;
+        }
+
+        let mut texts = Vec::new();
+        collect_strings(json, &mut texts);
+        let before = self.result.errors.len() + self.result.untranslated_failures.len();
+        for text in texts {
+            self.translate_text_failures(text);
+        }
+        if self.result.errors.len() + self.result.untranslated_failures.len() == before {
+            self.untranslated("", None, json.clone());
+        }
+    }
+
+    fn ogmios(&mut self, data: &serde_json::Value) -> bool {
+        let lovelace = |value: &serde_json::Value| {
+            value
+                .pointer("/ada/lovelace")
+                .or_else(|| value.get("lovelace"))
+                .and_then(serde_json::Value::as_u64)
+        };
+        if let Some(references) = data
+            .get("unknownOutputReferences")
+            .and_then(|references| references.as_array())
+        {
+            for reference in references {
+                let tx_hash = reference
+                    .pointer("/transaction/id")
+                    .and_then(|id| id.as_str());
+                let index = reference.get("index").and_then(|index| index.as_u64());
+                if let (Some(tx_hash), Some(index)) = (tx_hash, index) {
+                    self.phase1(
+                        Rule::Utxo,
+                        Phase1Error::BadInputsUTxO {
+                            invalid_input: TxInput {
+                                tx_hash: tx_hash.to_string(),
+                                output_index: index as u32,
+                            },
+                        },
+                    );
+                }
+            }
+            return true;
+        }
+        if let Some(signatories) = data
+            .get("missingSignatories")
+            .and_then(|signatories| signatories.as_array())
+        {
+            for signatory in signatories.iter().filter_map(|s| s.as_str()) {
+                self.phase1(
+                    Rule::Utxow,
+                    Phase1Error::MissingVKeyWitnesses {
+                        missing_key_hash: signatory.to_string(),
+                    },
+                );
+            }
+            return true;
+        }
+        if let (Some(min_fee), Some(actual_fee)) = (
+            data.get("minimumRequiredFee").and_then(lovelace),
+            data.get("providedFee").and_then(lovelace),
+        ) {
+            self.phase1(
+                Rule::Utxo,
+                Phase1Error::FeeTooSmallUTxO {
+                    actual_fee,
+                    min_fee,
+                    fee_decomposition: unknown_fee_decomposition(),
+                },
+            );
+            return true;
+        }
+        if let (Some(consumed), Some(produced)) = (
+            data.get("valueConsumed").and_then(lovelace),
+            data.get("valueProduced").and_then(lovelace),
+        ) {
+            let input_sum = Value::new_from_coins(consumed as i128);
+            let output_sum = Value::new_from_coins(produced as i128);
+            let difference = Value::new_from_coins(consumed as i128 - produced as i128);
+            self.phase1(
+                Rule::Utxo,
+                Phase1Error::ValueNotConservedUTxO {
+                    input_sum,
+                    output_sum,
+                    difference,
+                },
+            );
+            return true;
+        }
+        if let (Some(actual_size), Some(max_size)) = (
+            data.pointer("/measuredTransactionSize/bytes")
+                .or_else(|| data.get("measuredTransactionSize"))
+                .and_then(|size| size.as_u64()),
+            data.pointer("/maximumTransactionSize/bytes")
+                .or_else(|| data.get("maximumTransactionSize"))
+                .and_then(|size| size.as_u64()),
+        ) {
+            self.phase1(
+                Rule::Utxo,
+                Phase1Error::MaxTxSizeUTxO {
+                    actual_size,
+                    max_size,
+                },
+            );
+            return true;
+        }
+        if let (Some(interval), Some(current_slot)) = (
+            data.get("validityInterval"),
+            data.get("currentSlot").and_then(|slot| slot.as_u64()),
+        ) {
+            self.phase1(
+                Rule::Utxo,
+                Phase1Error::OutsideValidityIntervalUTxO {
+                    current_slot,
+                    interval_start: interval
+                        .get("invalidBefore")
+                        .and_then(|slot| slot.as_u64())
+                        .unwrap_or(0),
+                    interval_end: interval
+                        .get("invalidAfter")
+                        .and_then(|slot| slot.as_u64())
+                        .unwrap_or(u64::MAX),
+                },
+            );
+            return true;
+        }
+        false
+    }
+
+    fn translate_text(&mut self, text: &str) {
+        if !self.translate_text_failures(text) {
+            self.untranslated("", None, serde_json::Value::String(text.to_string()));
+        }
+    }
+
+    /// Finds the predicate failures named in the Haskell text of an `ApplyTxError`. Returns
+    /// whether any was found.
+    fn translate_text_failures(&mut self, text: &str) -> bool {
+        let mut found = false;
+        for (position, rule, name) in failure_names_in_text(text) {
+            found = true;
+            let arguments = text_arguments(&text[position + name.len()..]);
+            if self.text_failure(rule, name, arguments).is_none() {
+                self.untranslated(
+                    rule.path(),
+                    Some(name),
+                    serde_json::Value::String(format!("{}{}", name, arguments)),
+                );
+            }
+        }
+        found
+    }
+
+    fn text_failure(&mut self, rule: Rule, name: &str, arguments: &str) -> Option<()> {
+        let numbers = || text_numbers(arguments);
+        let hashes = |length: usize| {
+            text_quoted(arguments)
+                .into_iter()
+                .filter(move |quoted| quoted.len() == length && hex::decode(quoted).is_ok())
+        };
+        match name {
+            "BadInputsUTxO" | "BabbageNonDisjointRefInputs" => {
+                let indexes = text_values_after(arguments, "unTxIx = ");
+                let inputs: Vec<TxInput> = hashes(64)
+                    .zip(indexes)
+                    .map(|(tx_hash, index)| TxInput {
+                        tx_hash: tx_hash.to_string(),
+                        output_index: index as u32,
+                    })
+                    .collect();
+                if inputs.is_empty() {
+                    return None;
+                }
+                for input in inputs {
+                    let error = if name == "BadInputsUTxO" {
+                        Phase1Error::BadInputsUTxO {
+                            invalid_input: input,
+                        }
+                    } else {
+                        Phase1Error::ReferenceInputOverlapsWithInput { input }
+                    };
+                    self.phase1(rule, error);
+                }
+            }
+            "MissingVKeyWitnessesUTXOW" => {
+                for key_hash in hashes(56) {
+                    self.phase1(
+                        rule,
+                        Phase1Error::MissingVKeyWitnesses {
+                            missing_key_hash: key_hash.to_string(),
+                        },
+                    );
+                }
+            }
+            "MissingScriptWitnessesUTXOW" => {
+                for script_hash in hashes(56) {
+                    self.phase1(
+                        rule,
+                        Phase1Error::MissingScriptWitnesses {
+                            missing_script_hash: script_hash.to_string(),
+                        },
+                    );
+                }
+            }
+            "ExtraneousScriptWitnessesUTXOW" => {
+                for script_hash in hashes(56) {
+                    self.phase1(
+                        rule,
+                        Phase1Error::ExtraneousScriptWitnesses {
+                            extraneous_script: script_hash.to_string(),
+                        },
+                    );
+                }
+            }
+            "ScriptWitnessNotValidatingUTXOW" => {
+                for script_hash in hashes(56) {
+                    self.phase1(
+                        rule,
+                        Phase1Error::NativeScriptIsUnsuccessful {
+                            native_script_hash: script_hash.to_string(),
+                        },
+                    );
+                }
+            }
+            "InputSetEmptyUTxO" => self.phase1(rule, Phase1Error::InputSetEmptyUTxO),
+            "NoCollateralInputs" => self.phase1(rule, Phase1Error::NoCollateralInputs),
+            "MissingTxBodyMetadataHash" => {
+                self.phase1(rule, Phase1Error::MissingTxBodyMetadataHash)
+            }
+            "MissingTxMetadata" => self.phase1(rule, Phase1Error::MissingTxMetadata),
+            "CollateralContainsNonADA" => {
+                self.phase1(rule, Phase1Error::CalculatedCollateralContainsNonAdaAssets)
+            }
+            "FeeTooSmallUTxO" | "MaxTxSizeUTxO" | "TooManyCollateralInputs" => {
+                let numbers = numbers();
+                let (first, second) = (*numbers.first()?, *numbers.get(1)?);
+                let (bigger, smaller) = (first.max(second) as u64, first.min(second) as u64);
+                let error = match name {
+                    "FeeTooSmallUTxO" => Phase1Error::FeeTooSmallUTxO {
+                        actual_fee: smaller,
+                        min_fee: bigger,
+                        fee_decomposition: unknown_fee_decomposition(),
+                    },
+                    "MaxTxSizeUTxO" => Phase1Error::MaxTxSizeUTxO {
+                        actual_size: bigger,
+                        max_size: smaller,
+                    },
+                    _ => Phase1Error::TooManyCollateralInputs {
+                        actual_count: bigger as u32,
+                        max_count: smaller as u32,
+                    },
+                };
+                self.phase1(rule, error)
+            }
+            "ValueNotConservedUTxO" => {
+                let coins = text_values_after(arguments, "Coin ");
+                let (consumed, produced) = (*coins.first()?, *coins.get(1)?);
+                self.phase1(
+                    rule,
+                    Phase1Error::ValueNotConservedUTxO {
+                        input_sum: Value::new_from_coins(consumed),
+                        output_sum: Value::new_from_coins(produced),
+                        difference: Value::new_from_coins(consumed - produced),
+                    },
+                )
+            }
+            "InsufficientCollateral" => {
+                let balance = *text_values_after(arguments, "DeltaCoin ").first()?;
+                let required = *text_values_after(arguments, "(Coin ").first()?;
+                self.phase1(
+                    rule,
+                    Phase1Error::InsufficientCollateral {
+                        total_collateral: balance,
+                        required_collateral: required,
+                    },
+                )
+            }
+            "StakeKeyRegisteredDELEG" | "StakeKeyNotRegisteredDELEG" => {
+                let hash = hex::decode(hashes(56).next()?).ok()?;
+                let credential = if arguments.contains("ScriptHashObj") {
+                    LocalCredential::ScriptHash(hash)
+                } else {
+                    LocalCredential::KeyHash(hash)
+                };
+                let reward_address = self.reward_address(&credential)?;
+                let error = if name == "StakeKeyRegisteredDELEG" {
+                    Phase1Error::StakeAlreadyRegistered { reward_address }
+                } else {
+                    Phase1Error::StakeNotRegistered { reward_address }
+                };
+                self.phase1(rule, error)
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+fn collect_strings<'j>(json: &'j serde_json::Value, texts: &mut Vec<&'j str>) {
+    match json {
+        serde_json::Value::String(text) => texts.push(text),
+        serde_json::Value::Array(items) => {
+            items.iter().for_each(|item| collect_strings(item, texts))
+        }
+        serde_json::Value::Object(fields) => fields
+            .values()
+            .for_each(|value| collect_strings(value, texts)),
+        _ => {}
+    }
+}
+
+/// Leaf failures named in the text, in order. Wrappers like `UtxoFailure` are skipped since
+/// the failure they wrap is named after them.
+fn failure_names_in_text(text: &str) -> Vec<(usize, Rule, &'static str)> {
+    let mut found: Vec<(usize, Rule, &'static str)> = Vec::new();
+    for rule in Rule::ALL.iter() {
+        for (tag, name) in rule.failure_names().iter().enumerate() {
+            if name.is_empty() || is_wrapper(*rule, tag as u64) {
+                continue;
+            }
+            for (position, _) in text.match_indices(name) {
+                let end = position + name.len();
+                let is_word = !text[end..].starts_with(|c: char| c.is_alphanumeric())
+                    && !text[..position].ends_with(|c: char| c.is_alphanumeric());
+                if is_word {
+                    found.push((position, *rule, name));
+                }
+            }
+        }
+    }
+    found.sort_by_key(|(position, _, _)| *position);
+    found
+}
+
+fn is_wrapper(rule: Rule, tag: u64) -> bool {
+    matches!(
+        (rule, tag),
+        (Rule::Ledger, 1..=3)
+            | (Rule::Utxow, 0)
+            | (Rule::Utxo, 0)
+            | (Rule::Certs, 1)
+            | (Rule::Cert, _)
+    )
+}
+
+/// Arguments of a constructor: the text up to the parenthesis closing the enclosing group
+fn text_arguments(text: &str) -> &str {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut previous = ' ';
+    for (index, c) in text.char_indices() {
+        match c {
+            '"' if previous != '\\' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => {
+                depth -= 1;
+                if depth < 0 {
+                    return &text[..index];
+                }
+            }
+            ',' if !in_string && depth == 0 => return &text[..index],
+            _ => {}
+        }
+        previous = c;
+    }
+    text
+}
+
+fn text_quoted(text: &str) -> Vec<&str> {
+    text.split('"').skip(1).step_by(2).collect()
+}
+
+fn text_numbers(text: &str) -> Vec<i128> {
+    let unquoted: String = text.split('"').step_by(2).collect::<Vec<_>>().join(" ");
+    unquoted
+        .split(|c: char| !(c.is_ascii_digit() || c == '-'))
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+/// Numbers following each occurrence of the prefix
+fn text_values_after(text: &str, prefix: &str) -> Vec<i128> {
+    text.match_indices(prefix)
+        .filter_map(|(position, _)| {
+            let rest = text[position + prefix.len()..].trim_start_matches('(');
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '-'))
+                .unwrap_or(rest.len());
+            rest[..end].parse().ok()
+        })
+        .collect()
+}
@@ -7,7 +7,7 @@ use std::collections::HashSet;
 use crate::common::TxInput;
 use crate::validators::common::ProtocolVersion;
 
-use crate::validators::common::{FeeDecomposition, LocalCredential as Credential, GovernanceActionId, Voter};
+use crate::validators::common::{FeeDecomposition, LocalCredential as Credential, GovernanceActionId, TransactionEra, Voter};
 use crate::validators::phase_1::hints::get_error_hint;
 use crate::validators::phase_1::hints::get_warning_hint;
 use crate::validators::value::Value;
@@ -18,6 +18,10 @@ pub struct ValidationPhase1Error {
     pub error_message: String,
     pub locations: Vec<String>,
     pub hint: Option<String>,
+    /// Protocol major version from which the rule that produced the error applies,
+    /// `None` for rules that apply in every protocol version or are gated by era
+    #[serde(default)]
+    pub rule_version: Option<u32>,
 }
 
 impl ValidationPhase1Error {
@@ -29,6 +33,7 @@ impl ValidationPhase1Error {
             error_message,
             locations: vec![location],
             hint,
+            rule_version: None,
         }
     }
 
@@ -40,8 +45,14 @@ impl ValidationPhase1Error {
             error_message,
             locations: locations.to_vec(),
             hint,
+            rule_version: None,
         }
     }
+
+    pub fn with_rule_version(mut self, rule_version: u32) -> Self {
+        self.rule_version = Some(rule_version);
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
@@ -49,6 +60,9 @@ pub struct ValidationPhase1Warning {
     pub warning: Phase1Warning,
     pub locations: Vec<String>,
     pub hint: Option<String>,
+    /// Same as `ValidationPhase1Error::rule_version`
+    #[serde(default)]
+    pub rule_version: Option<u32>,
 }
 
 impl ValidationPhase1Warning {
@@ -58,8 +72,14 @@ impl ValidationPhase1Warning {
             warning,
             locations: vec![location],
             hint,
+            rule_version: None,
         }
     }
+
+    pub fn with_rule_version(mut self, rule_version: u32) -> Self {
+        self.rule_version = Some(rule_version);
+        self
+    }
 }
 
 /// Phase 1 validation errors
@@ -76,6 +96,12 @@ pub enum Phase1Error {
         interval_start: u64,
         interval_end: u64,
     },
+    /// A validity interval bound of a transaction with Plutus scripts lies beyond the slot-to-time forecast horizon
+    OutsideForecast {
+        slot: u64,
+        current_slot: u64,
+        forecast_horizon: u64,
+    },
     /// The transaction's size in bytes exceeds the protocol's maximum allowed size
     MaxTxSizeUTxO { actual_size: u64, max_size: u64 },
     /// The transaction has an empty input set
@@ -198,6 +224,12 @@ pub enum Phase1Error {
         reward_address: String,
         remaining_balance: u64,
     },
+    /// Off-chain document doesn't match the anchor or pool metadata hash
+    AnchorDocumentHashMismatch {
+        url: String,
+        expected_hash: String,
+        actual_hash: String,
+    },
     /// A withdrawal attempted from a non-existent reward account
     RewardAccountNotExisting { reward_address: String },
     /// Wrong requested withdrawal amount
@@ -217,6 +249,53 @@ pub enum Phase1Error {
     },
     /// A stake pool's cost parameter is below the minimum fixed fee
     StakePoolCostTooLow { specified_cost: u64, min_cost: u64 },
+    /// A stake pool's margin is not a valid unit interval
+    PoolMarginOutOfRange {
+        pool_id: String,
+        numerator: u64,
+        denominator: u64,
+    },
+    /// A stake pool's pledge or cost exceeds the maximum lovelace supply
+    PoolAmountOutOfBounds {
+        pool_id: String,
+        field: String,
+        amount: u64,
+        max_amount: u64,
+    },
+    /// A stake pool's reward account belongs to a different network
+    WrongNetworkPool {
+        pool_id: String,
+        reward_account: String,
+        expected_network: u8,
+    },
+    /// A stake pool relay DNS name exceeds the maximum length
+    PoolRelayDnsNameTooLong {
+        pool_id: String,
+        relay_index: u32,
+        dns_name: String,
+        length: u32,
+        max_length: u32,
+    },
+    /// A stake pool metadata URL exceeds the maximum length
+    PoolMetadataUrlTooLong {
+        pool_id: String,
+        url: String,
+        length: u32,
+        max_length: u32,
+    },
+    /// A stake pool metadata hash is bigger than allowed. Only translated from node errors,
+    /// such a hash can't be decoded with CSL.
+    PoolMetadataHashTooBig {
+        pool_id: String,
+        hash_size: u32,
+        max_size: u32,
+    },
+    /// The VRF key hash of a stake pool registration is already used by another pool
+    VrfKeyHashAlreadyRegistered {
+        pool_id: String,
+        vrf_key_hash: String,
+        registered_pool_id: String,
+    },
     /// An MIR certificate attempted to withdraw more than available
     InsufficientFundsForMir {
         requested_amount: u64,
@@ -391,6 +470,49 @@ pub enum Phase1Error {
     ReferenceInputOverlapsWithInput {
         input: TxInput,
     },
+    /// A set contains the same element more than once
+    DuplicateSetElement {
+        field: String,
+        /// Byte offset of the duplicate element in the transaction CBOR
+        offset: u64,
+    },
+    /// A field that must be a non-empty set or map is empty
+    EmptyNonEmptySet {
+        field: String,
+        /// Byte offset of the empty set in the transaction CBOR
+        offset: u64,
+    },
+    /// A multi-asset entry (mint or output value) has a zero quantity
+    ZeroQuantityAsset {
+        field: String,
+        policy_id: String,
+        asset_name: String,
+        /// Byte offset of the quantity in the transaction CBOR
+        offset: u64,
+    },
+    /// A map contains the same key more than once
+    DuplicateMapKey {
+        field: String,
+        /// Byte offset of the duplicate key in the transaction CBOR
+        offset: u64,
+    },
+    /// A set is wrapped in a tag other than 258
+    InvalidSetTag {
+        field: String,
+        tag: String,
+        /// Byte offset of the tag in the transaction CBOR
+        offset: u64,
+    },
+    /// The transaction uses a feature that does not exist in the validation era
+    FeatureNotSupportedInEra {
+        feature: String,
+        era: TransactionEra,
+    },
+    /// The transaction CBOR cannot be decoded with the codec of the validation era
+    TransactionNotDecodableInEra {
+        era: TransactionEra,
+        message: String,
+    },
 }
 
 impl Phase1Error {
@@ -412,6 +534,16 @@ impl Phase1Error {
                             interval_start, interval_end, current_slot
                         )
                     }
+            Self::OutsideForecast {
+                        slot,
+                        current_slot,
+                        forecast_horizon,
+                    } => {
+                        format!(
+                            "Validity interval bound at slot {} cannot be converted to POSIX time: it is beyond the forecast horizon (slot {}, current slot {})",
+                            slot, forecast_horizon, current_slot
+                        )
+                    }
             Self::MaxTxSizeUTxO {
                         actual_size,
                         max_size,
@@ -569,6 +701,9 @@ impl Phase1Error {
             Self::InvalidMetadata { message } => {
                         format!("Invalid metadata: {}", message)
                     }
+            Self::AnchorDocumentHashMismatch { url, expected_hash, actual_hash } => {
+                        format!("Document hash mismatch for {}: expected {}, got {}", url, expected_hash, actual_hash)
+                    }
             Self::ExtraneousScriptWitnesses { extraneous_script } => {
                         format!(
                             "Unnecessary script witness provided: {}",
@@ -614,6 +749,80 @@ impl Phase1Error {
                             specified_cost, min_cost
                         )
                     }
+            Self::PoolMarginOutOfRange {
+                        pool_id,
+                        numerator,
+                        denominator,
+                    } => {
+                        format!(
+                            "Stake pool {} margin {}/{} is not in the range [0, 1]",
+                            pool_id, numerator, denominator
+                        )
+                    }
+            Self::PoolAmountOutOfBounds {
+                        pool_id,
+                        field,
+                        amount,
+                        max_amount,
+                    } => {
+                        format!(
+                            "Stake pool {} {} {} exceeds the maximum {}",
+                            pool_id, field, amount, max_amount
+                        )
+                    }
+            Self::WrongNetworkPool {
+                        pool_id,
+                        reward_account,
+                        expected_network,
+                    } => {
+                        format!(
+                            "Stake pool {} reward account {} is not on the expected network {}",
+                            pool_id, reward_account, expected_network
+                        )
+                    }
+            Self::PoolRelayDnsNameTooLong {
+                        pool_id,
+                        relay_index,
+                        dns_name,
+                        length,
+                        max_length,
+                    } => {
+                        format!(
+                            "Stake pool {} relay {} DNS name {:?} is {} bytes long, maximum is {}",
+                            pool_id, relay_index, dns_name, length, max_length
+                        )
+                    }
+            Self::PoolMetadataUrlTooLong {
+                        pool_id,
+                        url,
+                        length,
+                        max_length,
+                    } => {
+                        format!(
+                            "Stake pool {} metadata URL {:?} is {} bytes long, maximum is {}",
+                            pool_id, url, length, max_length
+                        )
+                    }
+            Self::PoolMetadataHashTooBig {
+                        pool_id,
+                        hash_size,
+                        max_size,
+                    } => {
+                        format!(
+                            "Stake pool {} metadata hash is {} bytes, maximum is {}",
+                            pool_id, hash_size, max_size
+                        )
+                    }
+            Self::VrfKeyHashAlreadyRegistered {
+                        pool_id,
+                        vrf_key_hash,
+                        registered_pool_id,
+                    } => {
+                        format!(
+                            "VRF key hash {} of stake pool {} is already used by stake pool {}",
+                            vrf_key_hash, pool_id, registered_pool_id
+                        )
+                    }
             Self::InsufficientFundsForMir {
                         requested_amount,
                         available_amount,
@@ -841,6 +1050,27 @@ impl Phase1Error {
             Self::ReferenceInputOverlapsWithInput { input } => {
                 format!("Reference input overlaps with input: {:?}", input)
             },
+            Self::DuplicateSetElement { field, offset } => {
+                format!("Duplicate element in set {} at byte offset {}", field, offset)
+            },
+            Self::EmptyNonEmptySet { field, offset } => {
+                format!("Field {} must not be empty (byte offset {})", field, offset)
+            },
+            Self::ZeroQuantityAsset { field, policy_id, asset_name, offset } => {
+                format!("Zero quantity for asset {}.{} in {} at byte offset {}", policy_id, asset_name, field, offset)
+            },
+            Self::DuplicateMapKey { field, offset } => {
+                format!("Duplicate key in map {} at byte offset {}", field, offset)
+            },
+            Self::InvalidSetTag { field, tag, offset } => {
+                format!("Set {} is tagged with {} instead of 258 at byte offset {}", field, tag, offset)
+            },
+            Self::FeatureNotSupportedInEra { feature, era } => {
+                format!("{} is not supported in the {} era", feature, era)
+            },
+            Self::TransactionNotDecodableInEra { era, message } => {
+                format!("Transaction cannot be decoded as a {} era transaction: {}", era, message)
+            },
         }
     }
 }
@@ -871,6 +1101,97 @@ pub enum Phase1Warning {
     PoolAlreadyRegistered {
         pool_id: String,
     },
+    /// Pool owner stake key is not registered, so the owner's stake will not count towards the pledge
+    PoolOwnerStakeKeyNotRegistered {
+        pool_id: String,
+        owner_reward_address: String,
+    },
+    /// Delegation to a pool that has a pending retirement
+    DelegationToRetiringPool {
+        pool_id: String,
+        retirement_epoch: u64,
+    },
+    /// Delegation to a DRep that is expired (inactive)
+    DelegationToInactiveDRep {
+        drep_id: String,
+        expiry_epoch: u64,
+        current_epoch: u64,
+    },
+    /// Delegation to the pool or DRep the account is already delegated to
+    RedundantDelegation {
+        reward_address: String,
+        delegation_target: String,
+    },
+    /// Withdrawal from an account whose DRep delegation will be dropped
+    WithdrawalDRepDelegationWillBeDropped {
+        reward_address: String,
+        drep_id: String,
+    },
+    /// Output to a Plutus V1/V2 script address without a datum can't be spent
+    ScriptOutputWithoutDatum {
+        address: String,
+        script_hash: String,
+        language: String,
+    },
+    /// Output to a script address with a datum hash whose datum isn't published in the witness set
+    DatumHashNotPublished {
+        address: String,
+        datum_hash: String,
+    },
+    /// Tokens sent to a malformed or pointer address
+    TokensSentToUnusualAddress {
+        address: String,
+        address_kind: String,
+    },
+    /// Output repeats the exact address and value of a spent input
+    OutputRepeatsInput {
+        input: String,
+    },
+    /// Inline datum is larger than the configured limit
+    LargeInlineDatum {
+        datum_size: u32,
+        max_size: u32,
+    },
+    /// Minted tokens don't match their CIP-25 (label 721) metadata
+    Cip25MetadataMismatch {
+        policy_id: String,
+        asset_name: String,
+        message: String,
+    },
+    /// Minted CIP-68 tokens don't form a valid reference/user token pair
+    Cip68TokenMismatch {
+        policy_id: String,
+        asset_name: String,
+        message: String,
+    },
+    /// CIP-27 (label 777) royalties metadata is invalid
+    Cip27RoyaltiesInvalid {
+        message: String,
+    },
+    /// Catalyst voting registration (labels 61284/61285) is malformed
+    InvalidVotingRegistration {
+        standard: String,
+        message: String,
+    },
+    /// Catalyst voting registration signature doesn't verify against the stake key
+    VotingRegistrationInvalidSignature {
+        stake_public_key: String,
+    },
+    /// Catalyst voting registration payment address is on a different network
+    VotingRegistrationWrongNetwork {
+        payment_address: String,
+        expected_network: u8,
+    },
+    /// No document was provided for an anchor or pool metadata URL
+    AnchorDocumentMissing {
+        url: String,
+    },
+    /// Off-chain document doesn't follow its metadata standard
+    AnchorDocumentInvalid {
+        url: String,
+        standard: String,
+        message: String,
+    },
     /// DRep already registered
     DRepAlreadyRegistered {
         drep_id: String,
@@ -923,6 +1244,60 @@ impl Phase1Warning {
             Self::PoolAlreadyRegistered { pool_id } => {
                         format!("Pool already registered: {}", pool_id)
                     }
+            Self::PoolOwnerStakeKeyNotRegistered { pool_id, owner_reward_address } => {
+                        format!("Owner stake key {} of pool {} is not registered", owner_reward_address, pool_id)
+                    }
+            Self::DelegationToRetiringPool { pool_id, retirement_epoch } => {
+                        format!("Delegation to pool {} which retires at epoch {}", pool_id, retirement_epoch)
+                    }
+            Self::DelegationToInactiveDRep { drep_id, expiry_epoch, current_epoch } => {
+                        format!("Delegation to DRep {} which expired at epoch {} (current epoch: {})", drep_id, expiry_epoch, current_epoch)
+                    }
+            Self::RedundantDelegation { reward_address, delegation_target } => {
+                        format!("Account {} is already delegated to {}", reward_address, delegation_target)
+                    }
+            Self::WithdrawalDRepDelegationWillBeDropped { reward_address, drep_id } => {
+                        format!("Withdrawal from {} whose delegation to DRep {} will be dropped", reward_address, drep_id)
+                    }
+            Self::ScriptOutputWithoutDatum { address, script_hash, language } => {
+                        format!("Output to {} script address {} (script hash {}) has no datum and can't be spent", language, address, script_hash)
+                    }
+            Self::DatumHashNotPublished { address, datum_hash } => {
+                        format!("Output to script address {} has datum hash {} but the datum is not included in the witness set", address, datum_hash)
+                    }
+            Self::TokensSentToUnusualAddress { address, address_kind } => {
+                        format!("Tokens are sent to {} address {}", address_kind, address)
+                    }
+            Self::OutputRepeatsInput { input } => {
+                        format!("Output repeats the exact address and value of input {}", input)
+                    }
+            Self::LargeInlineDatum { datum_size, max_size } => {
+                        format!("Inline datum is {} bytes, larger than {} bytes", datum_size, max_size)
+                    }
+            Self::Cip25MetadataMismatch { policy_id, asset_name, message } => {
+                        format!("CIP-25 metadata for {}.{}: {}", policy_id, asset_name, message)
+                    }
+            Self::Cip68TokenMismatch { policy_id, asset_name, message } => {
+                        format!("CIP-68 token {}.{}: {}", policy_id, asset_name, message)
+                    }
+            Self::Cip27RoyaltiesInvalid { message } => {
+                        format!("CIP-27 royalties metadata: {}", message)
+                    }
+            Self::InvalidVotingRegistration { standard, message } => {
+                        format!("Invalid {} voting registration: {}", standard, message)
+                    }
+            Self::VotingRegistrationInvalidSignature { stake_public_key } => {
+                        format!("Voting registration signature is invalid for stake key {}", stake_public_key)
+                    }
+            Self::VotingRegistrationWrongNetwork { payment_address, expected_network } => {
+                        format!("Voting registration payment address {} is not on network {}", payment_address, expected_network)
+                    }
+            Self::AnchorDocumentMissing { url } => {
+                        format!("No document provided for {}", url)
+                    }
+            Self::AnchorDocumentInvalid { url, standard, message } => {
+                        format!("Document {} doesn't follow {}: {}", url, standard, message)
+                    }
             Self::DRepAlreadyRegistered { drep_id } => {
                         format!("DRep already registered: {}", drep_id)
                     }
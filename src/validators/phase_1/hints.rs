@@ -9,6 +9,9 @@ pub fn get_error_hint(error: &Phase1Error) -> Option<String> {
         Phase1Error::OutsideValidityIntervalUTxO { .. } => Some(
             "Adjust the transaction's validity interval to include the current slot. Use 'ttl' and 'validity_start_interval' fields to set appropriate bounds.".to_string()
         ),
+        Phase1Error::OutsideForecast { .. } => Some(
+            "Transactions that execute Plutus scripts must have validity bounds that can be translated to POSIX time. Move 'ttl' (and 'validity_start_interval') closer to the current slot, within the network's stability window (3k/f slots).".to_string()
+        ),
         Phase1Error::MaxTxSizeUTxO { .. } => Some(
             "Reduce transaction size by: 1) Combining multiple outputs to the same address, 2) Removing unnecessary metadata or auxiliary data, 3) Using more efficient scripts, 4) Use refenced scripts and datums instead of providing them in the transaction witness set, 5) Splitting into multiple transactions.".to_string()
         ),
@@ -97,7 +100,10 @@ pub fn get_error_hint(error: &Phase1Error) -> Option<String> {
             "Ensure the metadata hash in the transaction body matches the actual hash of the provided metadata. Recalculate the hash if necessary.".to_string()
         ),
         Phase1Error::InvalidMetadata { .. } => Some(
-            "Fix the metadata format to comply with the CBOR specification and Cardano metadata standards. Check for invalid characters or structure.".to_string()
+            "Fix the metadatum at the reported location. Text and bytes must be at most 64 bytes (split longer values into a list of chunks), integers must fit in [-2^64, 2^64 - 1], labels must be unsigned 64-bit integers, and scripts in the auxiliary data must be well-formed.".to_string()
+        ),
+        Phase1Error::AnchorDocumentHashMismatch { .. } => Some(
+            "The on-chain hash must be the blake2b-256 of the exact bytes served at the URL. Re-hash the published document (without reformatting it) or publish the document that was hashed.".to_string()
         ),
         Phase1Error::ExtraneousScriptWitnesses { .. } => Some(
             "Remove unnecessary script witnesses from the transaction witness set. Only include scripts that are actually referenced by the transaction.".to_string()
@@ -126,6 +132,27 @@ pub fn get_error_hint(error: &Phase1Error) -> Option<String> {
         Phase1Error::StakePoolCostTooLow { .. } => Some(
             "Increase the stake pool's fixed cost to meet the minimum required amount set by protocol parameters.".to_string()
         ),
+        Phase1Error::PoolMarginOutOfRange { .. } => Some(
+            "Set the pool margin to a fraction between 0 and 1 with a non-zero denominator (e.g. 1/100 for 1%).".to_string()
+        ),
+        Phase1Error::PoolAmountOutOfBounds { .. } => Some(
+            "Pool pledge and cost cannot exceed the total lovelace supply (45,000,000,000,000,000). Check the amounts are given in lovelace, not ADA with extra digits.".to_string()
+        ),
+        Phase1Error::WrongNetworkPool { .. } => Some(
+            "Use a reward account for the network the transaction is submitted to (stake1... for mainnet, stake_test1... for testnets).".to_string()
+        ),
+        Phase1Error::PoolRelayDnsNameTooLong { .. } => Some(
+            "The ledger limits relay DNS names to 64 bytes. Use a shorter host name or an IP address relay.".to_string()
+        ),
+        Phase1Error::PoolMetadataUrlTooLong { .. } => Some(
+            "The ledger limits the pool metadata URL to 64 bytes. Host the metadata under a shorter URL (e.g. using a URL shortener or a shorter path).".to_string()
+        ),
+        Phase1Error::PoolMetadataHashTooBig { .. } => Some(
+            "The pool metadata hash must be the 32-byte blake2b-256 hash of the metadata file.".to_string()
+        ),
+        Phase1Error::VrfKeyHashAlreadyRegistered { .. } => Some(
+            "Since protocol version 10 each stake pool must use a unique VRF key. Generate a new VRF key pair for this pool.".to_string()
+        ),
         Phase1Error::InsufficientFundsForMir { .. } => Some(
             "Reduce the MIR (Move Instantaneous Rewards) amount to stay within available treasury or reserve funds.".to_string()
         ),
@@ -240,6 +267,27 @@ pub fn get_error_hint(error: &Phase1Error) -> Option<String> {
         Phase1Error::ReferenceInputOverlapsWithInput { .. } => Some(
             "Remove the reference input that overlaps with the input. Reference inputs are not allowed to overlap with inputs.".to_string()
         ),
+        Phase1Error::DuplicateSetElement { .. } => Some(
+            "Remove the repeated element. Since protocol version 9 the node fails to deserialize sets with duplicate elements, even though some serialization libraries accept them.".to_string()
+        ),
+        Phase1Error::EmptyNonEmptySet { .. } => Some(
+            "Omit the field entirely instead of encoding it as an empty set or map. Certificates, withdrawals, collateral, required signers and similar fields must be non-empty when present.".to_string()
+        ),
+        Phase1Error::ZeroQuantityAsset { .. } => Some(
+            "Remove the asset entry with zero quantity. Mint entries and output values cannot contain zero quantities.".to_string()
+        ),
+        Phase1Error::DuplicateMapKey { .. } => Some(
+            "Remove the duplicated key. The node rejects maps with duplicate keys since protocol version 9.".to_string()
+        ),
+        Phase1Error::InvalidSetTag { .. } => Some(
+            "Encode sets either as plain arrays or wrapped in CBOR tag 258. Any other tag is rejected by the node.".to_string()
+        ),
+        Phase1Error::FeatureNotSupportedInEra { .. } => Some(
+            "Remove the field or certificate, or validate against a later era. Leave the era unset to infer it from the protocol version.".to_string()
+        ),
+        Phase1Error::TransactionNotDecodableInEra { .. } => Some(
+            "The transaction uses an encoding introduced in a later era. Build it with the serialization rules of the target era.".to_string()
+        ),
     }
 }
 
@@ -265,14 +313,68 @@ pub fn get_warning_hint(warning: &Phase1Warning) -> Option<String> {
             "Use a regular payment address (base address or enterprise address) instead of a reward address.".to_string()
         ),
         Phase1Warning::CannotCheckStakeDeregistrationRefund { .. } => Some(
-            "The stake deregistration refund amount cannot be verified. Ensure you're provided required information into validation context for cquisitor.".to_string()
+            "The stake deregistration refund amount cannot be verified. Provide payedDeposit for the account, or its registrationEpoch together with depositHistory, in the validation context.".to_string()
         ),
         Phase1Warning::CannotCheckDRepDeregistrationRefund { .. } => Some(
-            "The DRep deregistration refund amount cannot be verified. Ensure you're provided required information into validation context for cquisitor.".to_string()
+            "The DRep deregistration refund amount cannot be verified. Provide payedDeposit for the DRep, or its registrationEpoch together with depositHistory, in the validation context.".to_string()
         ),
         Phase1Warning::PoolAlreadyRegistered { .. } => Some(
             "The stake pool is already registered. It's ok, it just means pool parameters will be updated and you don't need to pay a deposit.".to_string()
         ),
+        Phase1Warning::PoolOwnerStakeKeyNotRegistered { .. } => Some(
+            "Register the owner's stake key and delegate it to the pool, otherwise the owner's stake does not count towards the declared pledge.".to_string()
+        ),
+        Phase1Warning::DelegationToRetiringPool { .. } => Some(
+            "The pool will stop producing blocks after its retirement epoch and the delegation will stop earning rewards. Consider delegating to another pool.".to_string()
+        ),
+        Phase1Warning::DelegationToInactiveDRep { .. } => Some(
+            "The DRep is inactive and its voting power is not counted until it votes or sends an update certificate. Consider delegating to an active DRep.".to_string()
+        ),
+        Phase1Warning::RedundantDelegation { .. } => Some(
+            "The account is already delegated to this target. The certificate has no effect and can be removed to save fees.".to_string()
+        ),
+        Phase1Warning::WithdrawalDRepDelegationWillBeDropped { .. } => Some(
+            "The account's DRep is not registered or deregisters in this transaction, so the delegation will be dropped and further withdrawals will fail. Delegate the account to another DRep.".to_string()
+        ),
+        Phase1Warning::ScriptOutputWithoutDatum { .. } => Some(
+            "Plutus V1 and V2 validators require a datum to spend an output. Attach an inline datum or a datum hash, otherwise the funds are locked forever.".to_string()
+        ),
+        Phase1Warning::DatumHashNotPublished { .. } => Some(
+            "The spender must supply the datum matching this hash. Include the datum in the witness set or use an inline datum so it can be recovered from the chain.".to_string()
+        ),
+        Phase1Warning::TokensSentToUnusualAddress { .. } => Some(
+            "Malformed and pointer addresses are rarely controlled by wallets. Double-check the destination address before sending tokens to it.".to_string()
+        ),
+        Phase1Warning::OutputRepeatsInput { .. } => Some(
+            "The output recreates one of the inputs without changes. Remove both the input and the output unless the input is spent on purpose (e.g. to trigger a script).".to_string()
+        ),
+        Phase1Warning::LargeInlineDatum { .. } => Some(
+            "Large inline datums increase min-ADA and fees for this and every spending transaction. Consider storing a datum hash or reducing the datum size.".to_string()
+        ),
+        Phase1Warning::Cip25MetadataMismatch { .. } => Some(
+            "Wallets and explorers may not display the token correctly. Use text keys (hex policy id, UTF-8 asset name) for CIP-25 version 1 or raw bytes with \"version\": 2, describe every minted asset, and split URIs longer than 64 bytes into a list of strings.".to_string()
+        ),
+        Phase1Warning::Cip68TokenMismatch { .. } => Some(
+            "CIP-68 tokens are minted as a reference token (100) plus a user token (222/333/444) with the same name suffix. Send the reference token to an output with an inline datum of the form Constr 0 [metadata, version, extra].".to_string()
+        ),
+        Phase1Warning::Cip27RoyaltiesInvalid { .. } => Some(
+            "Marketplaces may ignore the royalties. Label 777 metadata needs a \"rate\" between 0 and 1 as a decimal string and a valid \"addr\" (split into 64-byte chunks if needed), minted together with the royalty token that has an empty asset name.".to_string()
+        ),
+        Phase1Warning::InvalidVotingRegistration { .. } => Some(
+            "The transaction is valid on-chain, but Catalyst will ignore the registration. Follow the CIP-36 layout: key 1 is a list of [32-byte voting key, weight], key 2 the 32-byte stake public key, key 3 a Shelley payment address, key 4 a nonce and optional key 5 the voting purpose.".to_string()
+        ),
+        Phase1Warning::VotingRegistrationInvalidSignature { .. } => Some(
+            "Sign the blake2b-256 hash of the CBOR map { 61284: registration } with the stake key from key 2 and put the signature under key 1 of label 61285.".to_string()
+        ),
+        Phase1Warning::VotingRegistrationWrongNetwork { .. } => Some(
+            "Voting rewards are paid to the payment address, so it must belong to the network the transaction is submitted to.".to_string()
+        ),
+        Phase1Warning::AnchorDocumentMissing { .. } => Some(
+            "Fetch the document from the URL and pass its bytes in the documents map to verify its hash.".to_string()
+        ),
+        Phase1Warning::AnchorDocumentInvalid { .. } => Some(
+            "Wallets and explorers may not display the document. Governance metadata should follow CIP-100 (with CIP-108 for proposals and CIP-119 for DReps), pool metadata should follow the CIP-6 limits.".to_string()
+        ),
         Phase1Warning::DRepAlreadyRegistered { .. } => Some(
             "The DRep is already registered. Consider using a DRep update certificate instead of registration if you need to modify DRep information.".to_string()
         ),
@@ -1,34 +1,128 @@
-use crate::validators::{
-    phase_1::errors::{Phase1Error, ValidationPhase1Error},
-    validation_result::ValidationResult,
+use crate::{
+    cbor::cbor_decoder::{get_tokenizer, get_value},
+    validators::{
+        phase_1::{
+            errors::{Phase1Error, Phase1Warning, ValidationPhase1Error, ValidationPhase1Warning},
+            validation::{
+                strict_decoding::{array_values, get_type, map_entries, scalar_value, uint_value},
+                token_metadata::collect_token_metadata_warnings,
+            },
+        },
+        validation_result::ValidationResult,
+    },
 };
 use cardano_serialization_lib as csl;
+use serde_json::Value;
+use uplc::ast::{FakeNamedDeBruijn, Program};
+
+/// Maximum size of a text or bytes metadatum (or of one chunk of it)
+const MAX_METADATUM_LENGTH: usize = 64;
+const AUXILIARY_DATA_LOCATION: &str = "transaction.auxiliary_data";
 
 pub struct AuxiliaryDataValidator {
     pub auxiliary_data: Option<csl::AuxiliaryData>,
     pub expected_auxiliary_data_hash: Option<csl::AuxiliaryDataHash>,
     pub actual_auxiliary_data_hash: Option<csl::AuxiliaryDataHash>,
+    /// Ill-formed metadata and scripts found in the raw auxiliary data: (message, location)
+    pub metadata_violations: Vec<(String, String)>,
+    /// CIP-25/CIP-68/CIP-27 findings for minted tokens: (warning, location)
+    pub token_metadata_warnings: Vec<(Phase1Warning, String)>,
 }
 
 impl AuxiliaryDataValidator {
-    pub fn new(tx_body: &csl::TransactionBody, auxiliary_data: Option<csl::AuxiliaryData>) -> Self {
+    pub fn new(
+        tx_body: &csl::TransactionBody,
+        auxiliary_data: Option<csl::AuxiliaryData>,
+        tx_bytes: &[u8],
+    ) -> Self {
         let actual_auxiliary_data_hash = tx_body.auxiliary_data_hash();
         let expected_auxiliary_data_hash = if let Some(auxiliary_data) = &auxiliary_data {
             Some(csl::hash_auxiliary_data(&auxiliary_data))
         } else {
             None
         };
+        let token_metadata_warnings =
+            collect_token_metadata_warnings(tx_body, auxiliary_data.as_ref());
 
         Self {
             auxiliary_data,
             expected_auxiliary_data_hash,
             actual_auxiliary_data_hash,
+            metadata_violations: Self::collect_raw_violations(tx_bytes),
+            token_metadata_warnings,
         }
     }
 
+    /// Checks the raw auxiliary data against the ledger's metadata and script rules.
+    /// CSL refuses to decode some of these, so this works on the raw transaction CBOR.
+    pub fn collect_raw_violations(tx_bytes: &[u8]) -> Vec<(String, String)> {
+        let mut violations = Vec::new();
+        let root = match get_value(get_tokenizer(tx_bytes)) {
+            Ok(root) => root,
+            Err(_) => return violations,
+        };
+        let tx_items = match root
+            .as_array()
+            .and_then(|items| items.first())
+            .and_then(array_values)
+        {
+            Some(tx_items) => tx_items,
+            None => return violations,
+        };
+        // Since Alonzo the auxiliary data follows the is_valid flag
+        let auxiliary_data = match tx_items.len() {
+            4 => &tx_items[3],
+            3 => &tx_items[2],
+            _ => return violations,
+        };
+
+        match get_type(auxiliary_data) {
+            // Shelley: metadata only
+            Some("Map") => check_metadata(auxiliary_data, &mut violations),
+            // Allegra / Mary: [metadata, native_scripts]
+            Some("Array") => {
+                let items = array_values(auxiliary_data).cloned().unwrap_or_default();
+                if let Some(metadata) = items.first() {
+                    check_metadata(metadata, &mut violations);
+                }
+                if let Some(native_scripts) = items.get(1) {
+                    check_native_scripts(tx_bytes, native_scripts, &mut violations);
+                }
+            }
+            // Alonzo and later: #6.259({ 0: metadata, 1: native, 2: v1, 3: v2, 4: v3 })
+            Some("Tag") => {
+                let fields = auxiliary_data.get("value").cloned().unwrap_or_default();
+                for (key, value) in map_entries(&fields) {
+                    match uint_value(key) {
+                        Some(0) => check_metadata(value, &mut violations),
+                        Some(1) => check_native_scripts(tx_bytes, value, &mut violations),
+                        Some(version @ 2..=4) => check_plutus_scripts(
+                            value,
+                            &format!("plutus_scripts_v{}", version - 1),
+                            &mut violations,
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        violations
+    }
+
     pub fn validate(&self) -> ValidationResult {
         let mut errors = Vec::new();
 
+        for (message, location) in &self.metadata_violations {
+            errors.push(ValidationPhase1Error::new(
+                Phase1Error::InvalidMetadata {
+                    message: message.clone(),
+                },
+                location.clone(),
+            ));
+        }
+
         if let Some(_) = &self.auxiliary_data {
             if let Some(expected_hash) = &self.expected_auxiliary_data_hash {
                 match self.actual_auxiliary_data_hash.as_ref() {
@@ -62,6 +156,180 @@ impl AuxiliaryDataValidator {
                 }
             }
         }
-        ValidationResult::new_phase_1(errors, Vec::new())
+
+        let warnings = self
+            .token_metadata_warnings
+            .iter()
+            .map(|(warning, location)| ValidationPhase1Warning::new(warning.clone(), location.clone()))
+            .collect();
+
+        ValidationResult::new_phase_1(errors, warnings)
+    }
+}
+
+fn check_metadata(metadata: &Value, violations: &mut Vec<(String, String)>) {
+    let location = format!("{}.metadata", AUXILIARY_DATA_LOCATION);
+    for (label, metadatum) in map_entries(metadata) {
+        let label_str = scalar_value(label).unwrap_or_default();
+        let label_location = format!("{}.{}", location, label_str);
+        if !is_uint(label) {
+            violations.push((
+                format!(
+                    "Metadata label {} is outside the range [0, 2^64 - 1]",
+                    label_str
+                ),
+                label_location.clone(),
+            ));
+        }
+        check_metadatum(metadatum, &label_location, violations);
+    }
+}
+
+fn check_metadatum(value: &Value, location: &str, violations: &mut Vec<(String, String)>) {
+    match get_type(value).unwrap_or_default() {
+        "String" | "Bytes" => {
+            let (kind, length) = metadatum_chunk_length(value);
+            check_metadatum_length(kind, length, location, violations);
+        }
+        "IndefiniteLengthString" | "IndefiniteLengthBytes" => {
+            // The limit applies to the concatenated chunks
+            let kind = if get_type(value) == Some("IndefiniteLengthBytes") {
+                "Bytes"
+            } else {
+                "Text"
+            };
+            let length = value
+                .get("chunks")
+                .and_then(Value::as_array)
+                .map(|chunks| {
+                    chunks
+                        .iter()
+                        .map(|chunk| metadatum_chunk_length(chunk).1)
+                        .sum()
+                })
+                .unwrap_or(0);
+            check_metadatum_length(kind, length, location, violations);
+        }
+        "U8" | "U16" | "U32" | "U64" | "I8" | "I16" | "I32" | "I64" | "Int" => {}
+        "Array" => {
+            for (index, item) in array_values(value).unwrap_or(&vec![]).iter().enumerate() {
+                check_metadatum(item, &format!("{}.{}", location, index), violations);
+            }
+        }
+        "Map" => {
+            for (index, (key, item)) in map_entries(value).into_iter().enumerate() {
+                let key_segment = match get_type(key) {
+                    Some("Array") | Some("Map") | Some("Tag") => index.to_string(),
+                    _ => scalar_value(key).unwrap_or_else(|| index.to_string()),
+                };
+                let entry_location = format!("{}.{}", location, key_segment);
+                check_metadatum(key, &entry_location, violations);
+                check_metadatum(item, &entry_location, violations);
+            }
+        }
+        "Tag" => {
+            let tag = value.get("tag").and_then(Value::as_str).unwrap_or_default();
+            let message = if tag == "PosBignum" || tag == "NegBignum" {
+                "Integer metadatum is outside the range [-2^64, 2^64 - 1]".to_string()
+            } else {
+                format!("Tagged value {} is not allowed in metadata", tag)
+            };
+            violations.push((message, location.to_string()));
+        }
+        other => {
+            violations.push((
+                format!("Value of type {} is not allowed in metadata", other),
+                location.to_string(),
+            ));
+        }
+    }
+}
+
+fn metadatum_chunk_length(value: &Value) -> (&'static str, usize) {
+    let content = value.get("value").and_then(Value::as_str).unwrap_or_default();
+    match get_type(value) {
+        Some("Bytes") => ("Bytes", content.len() / 2),
+        _ => ("Text", content.len()),
+    }
+}
+
+fn check_metadatum_length(
+    kind: &str,
+    length: usize,
+    location: &str,
+    violations: &mut Vec<(String, String)>,
+) {
+    if length > MAX_METADATUM_LENGTH {
+        violations.push((
+            format!(
+                "{} metadatum is {} bytes long, maximum is {}",
+                kind, length, MAX_METADATUM_LENGTH
+            ),
+            location.to_string(),
+        ));
+    }
+}
+
+fn check_native_scripts(tx_bytes: &[u8], value: &Value, violations: &mut Vec<(String, String)>) {
+    for (index, script) in set_values(value).iter().enumerate() {
+        let location = format!("{}.native_scripts.{}", AUXILIARY_DATA_LOCATION, index);
+        let decoded = item_bytes(tx_bytes, script)
+            .ok_or_else(|| "Native script is not a CBOR array".to_string())
+            .and_then(|bytes| {
+                csl::NativeScript::from_bytes(bytes.to_vec()).map_err(|e| format!("{:?}", e))
+            });
+        if let Err(e) = decoded {
+            violations.push((format!("Malformed native script: {}", e), location));
+        }
+    }
+}
+
+fn check_plutus_scripts(value: &Value, field: &str, violations: &mut Vec<(String, String)>) {
+    for (index, script) in set_values(value).iter().enumerate() {
+        let location = format!("{}.{}.{}", AUXILIARY_DATA_LOCATION, field, index);
+        let script_bytes = match get_type(script) {
+            Some("Bytes") => script
+                .get("value")
+                .and_then(Value::as_str)
+                .and_then(|script_hex| hex::decode(script_hex).ok()),
+            _ => None,
+        };
+        let decoded = script_bytes
+            .ok_or_else(|| "script is not a byte string".to_string())
+            .and_then(|bytes| {
+                let mut buffer = Vec::new();
+                Program::<FakeNamedDeBruijn>::from_cbor(&bytes, &mut buffer)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = decoded {
+            violations.push((format!("Malformed Plutus script: {}", e), location));
+        }
+    }
+}
+
+/// Elements of a plain array or of a set tagged with 258
+fn set_values(value: &Value) -> Vec<Value> {
+    let elements = if get_type(value) == Some("Tag") {
+        value.get("value").cloned().unwrap_or_default()
+    } else {
+        value.clone()
+    };
+    array_values(&elements).cloned().unwrap_or_default()
+}
+
+/// Raw CBOR bytes of a decoded collection item
+fn item_bytes<'a>(tx_bytes: &'a [u8], value: &Value) -> Option<&'a [u8]> {
+    let position = value.get("struct_position_info")?;
+    let start = position.get("offset")?.as_u64()? as usize;
+    let length = position.get("length")?.as_u64()? as usize;
+    tx_bytes.get(start..start + length)
+}
+
+fn is_uint(value: &Value) -> bool {
+    match get_type(value) {
+        Some("U8") | Some("U16") | Some("U32") | Some("U64") => true,
+        Some("Int") => value.get("value").and_then(Value::as_u64).is_some(),
+        _ => false,
     }
 }
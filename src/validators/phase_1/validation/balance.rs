@@ -1,15 +1,19 @@
 use crate::validators::common::Value;
-use crate::validators::helpers::credential_to_bech32_reward_address;
-use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::helpers::{credential_to_bech32_reward_address, normalize_drep_id};
+use crate::validators::input_contexts::{DepositParametersInputContext, ValidationInputContext};
+use crate::validators::protocol_params::{CONWAY_PROTOCOL_VERSION, POST_BOOTSTRAP_PROTOCOL_VERSION};
 use crate::validators::phase_1::errors::{
     Phase1Error, Phase1Warning, ValidationPhase1Error, ValidationPhase1Warning,
 };
 use crate::validators::validation_result::ValidationResult;
 use crate::validators::value::MultiAsset;
 use cardano_serialization_lib as csl;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum DepositType {
     VotingProposal { amount: i128, index: u32 },
     StakeRegistration { amount: i128, index: u32 },
@@ -17,17 +21,20 @@ pub enum DepositType {
     PoolRegistration { amount: i128, index: u32 },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum RefundType {
     StakeDeregistration {
         amount: i128,
         index: u32,
         reward_address: String,
+        /// Refund inferred from the ledger state, `None` if it can't be determined
+        expected_amount: Option<i128>,
     },
     DrepDeregistration {
         amount: i128,
         index: u32,
         drep_bech32: String,
+        expected_amount: Option<i128>,
     },
 }
 
@@ -45,7 +52,7 @@ pub struct InputsDecomposition {
     pub mints: MultiAsset,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct Withdrawal {
     pub amount: u64,
     pub index: u32,
@@ -203,7 +210,7 @@ impl<'a> BalanceValidator<'a> {
 
     fn validate_withdrawals_balance(&self) -> ValidationResult {
         let mut errors = Vec::new();
-        let warnings = Vec::new();
+        let mut warnings = Vec::new();
 
         for withdrawal in self.inputs.withdrawals.iter() {
             let reward_address = withdrawal.reward_address.clone();
@@ -224,13 +231,39 @@ impl<'a> BalanceValidator<'a> {
                             ));
                         }
                     }
-                    if account_context.delegated_to_drep.is_none() {
-                        errors.push(ValidationPhase1Error::new(
-                            Phase1Error::WithdrawalNotAllowedBecauseNotDelegatedToDRep {
-                                reward_address: reward_address.clone(),
-                            },
-                            format!("transaction.body.withdrawals.{}", withdrawal.index),
-                        ));
+                    // Starting with protocol version 10, withdrawals from key hash credentials
+                    // require a DRep delegation
+                    let requires_drep_delegation = self
+                        .validation_input_context
+                        .protocol_parameters
+                        .protocol_major_version()
+                        >= POST_BOOTSTRAP_PROTOCOL_VERSION
+                        && is_key_hash_reward_address(&reward_address);
+                    if let Some(delegated_to_drep) = &account_context.delegated_to_drep {
+                        if requires_drep_delegation
+                            && self.is_drep_delegation_dropped(delegated_to_drep)
+                        {
+                            warnings.push(
+                                ValidationPhase1Warning::new(
+                                    Phase1Warning::WithdrawalDRepDelegationWillBeDropped {
+                                        reward_address: reward_address.clone(),
+                                        drep_id: delegated_to_drep.clone(),
+                                    },
+                                    format!("transaction.body.withdrawals.{}", withdrawal.index),
+                                )
+                                .with_rule_version(POST_BOOTSTRAP_PROTOCOL_VERSION),
+                            );
+                        }
+                    } else if requires_drep_delegation {
+                        errors.push(
+                            ValidationPhase1Error::new(
+                                Phase1Error::WithdrawalNotAllowedBecauseNotDelegatedToDRep {
+                                    reward_address: reward_address.clone(),
+                                },
+                                format!("transaction.body.withdrawals.{}", withdrawal.index),
+                            )
+                            .with_rule_version(POST_BOOTSTRAP_PROTOCOL_VERSION),
+                        );
                     }
                 } else {
                     errors.push(ValidationPhase1Error::new(
@@ -252,6 +285,26 @@ impl<'a> BalanceValidator<'a> {
         ValidationResult::new_phase_1(errors, warnings)
     }
 
+    /// Returns true if the DRep is deregistered in this tx or isn't registered at all,
+    /// in which case delegations to it are dropped by the ledger.
+    fn is_drep_delegation_dropped(&self, drep_id: &str) -> bool {
+        let drep_id = normalize_drep_id(drep_id);
+        let deregistered_in_tx = self.inputs.refunds.iter().any(|refund| match refund {
+            RefundType::DrepDeregistration { drep_bech32, .. } => {
+                normalize_drep_id(drep_bech32) == drep_id
+            }
+            _ => false,
+        });
+        let not_registered = self
+            .validation_input_context
+            .drep_contexts
+            .iter()
+            .find(|drep| normalize_drep_id(&drep.bech32_drep) == drep_id)
+            .map(|drep| !drep.is_registered)
+            .unwrap_or(false);
+        deregistered_in_tx || not_registered
+    }
+
     fn validate_deposits(&self) -> ValidationResult {
         let mut errors = Vec::new();
         let warnings = Vec::new();
@@ -333,67 +386,54 @@ impl<'a> BalanceValidator<'a> {
                 RefundType::StakeDeregistration {
                     amount,
                     index,
-                    reward_address,
-                } => {
-                    if let Some(account_context) = self
-                        .validation_input_context
-                        .find_account_context(&reward_address)
-                    {
-                        if let Some(payed_deposit) = account_context.payed_deposit {
-                            if *amount != payed_deposit as i128 {
-                                errors.push(ValidationPhase1Error::new(
-                                    Phase1Error::StakeDeregistrationWrongRefund {
-                                        supplied_refund: *amount,
-                                        required_refund: payed_deposit as i128,
-                                    },
-                                    format!("transaction.body.certs.{}", index),
-                                ));
-                            }
-                        } else {
-                            warnings.push(ValidationPhase1Warning::new(
-                                Phase1Warning::CannotCheckStakeDeregistrationRefund {},
+                    expected_amount,
+                    ..
+                } => match expected_amount {
+                    Some(expected_amount) => {
+                        if amount != expected_amount {
+                            errors.push(ValidationPhase1Error::new(
+                                Phase1Error::StakeDeregistrationWrongRefund {
+                                    supplied_refund: *amount,
+                                    required_refund: *expected_amount,
+                                },
                                 format!("transaction.body.certs.{}", index),
                             ));
                         }
-                    } else {
+                    }
+                    None => {
                         warnings.push(ValidationPhase1Warning::new(
                             Phase1Warning::CannotCheckStakeDeregistrationRefund {},
                             format!("transaction.body.certs.{}", index),
                         ));
                     }
-                }
+                },
                 RefundType::DrepDeregistration {
                     amount,
                     index,
-                    drep_bech32,
-                } => {
-                    let drep_context = self
-                        .validation_input_context
-                        .find_drep_context(&drep_bech32);
-                    if let Some(drep_context) = drep_context {
-                        if let Some(payed_deposit) = drep_context.payed_deposit {
-                            if *amount != payed_deposit as i128 {
-                                errors.push(ValidationPhase1Error::new(
+                    expected_amount,
+                    ..
+                } => match expected_amount {
+                    Some(expected_amount) => {
+                        if amount != expected_amount {
+                            errors.push(
+                                ValidationPhase1Error::new(
                                     Phase1Error::DRepDeregistrationWrongRefund {
                                         supplied_refund: *amount,
-                                        required_refund: payed_deposit as i128,
+                                        required_refund: *expected_amount,
                                     },
                                     format!("transaction.body.certs.{}", index),
-                                ));
-                            }
-                        } else {
-                            warnings.push(ValidationPhase1Warning::new(
-                                Phase1Warning::CannotCheckDRepDeregistrationRefund {},
-                                format!("transaction.body.certs.{}", index),
-                            ));
+                                )
+                                .with_rule_version(CONWAY_PROTOCOL_VERSION),
+                            );
                         }
-                    } else {
+                    }
+                    None => {
                         warnings.push(ValidationPhase1Warning::new(
                             Phase1Warning::CannotCheckDRepDeregistrationRefund {},
                             format!("transaction.body.certs.{}", index),
                         ));
                     }
-                }
+                },
             }
         }
         ValidationResult::new_phase_1(errors, warnings)
@@ -425,6 +465,9 @@ fn calculate_deposits_and_refunds(
 ) -> (Vec<RefundType>, Vec<DepositType>) {
     let mut refunds = Vec::new();
     let mut deposits = Vec::new();
+    // Deposits of registrations earlier in this tx, refunded by a later deregistration
+    let mut stake_registrations_in_tx: HashMap<String, i128> = HashMap::new();
+    let mut drep_registrations_in_tx: HashMap<String, i128> = HashMap::new();
 
     let certificates = tx_body.certs();
     if let Some(certificates) = certificates {
@@ -435,56 +478,56 @@ fn calculate_deposits_and_refunds(
             match cert_type {
                 csl::CertificateKind::StakeRegistration => {
                     let reg_cert = cert.as_stake_registration().unwrap();
-                    let explicit_deposit = reg_cert.coin();
-                    if let Some(explicit_deposit) = explicit_deposit {
-                        deposits.push(DepositType::StakeRegistration {
-                            amount: explicit_deposit.to_str().parse::<i128>().unwrap_or(0),
-                            index: i as u32,
-                        });
-                    } else {
-                        deposits.push(DepositType::StakeRegistration {
-                            amount: validation_input_context
-                                .protocol_parameters
-                                .stake_key_deposit as i128,
-                            index: i as u32,
-                        });
-                    }
+                    let amount = match reg_cert.coin() {
+                        Some(explicit_deposit) => {
+                            explicit_deposit.to_str().parse::<i128>().unwrap_or(0)
+                        }
+                        None => {
+                            validation_input_context.protocol_parameters.stake_key_deposit as i128
+                        }
+                    };
+                    stake_registrations_in_tx.insert(
+                        credential_to_bech32_reward_address(
+                            &reg_cert.stake_credential(),
+                            &validation_input_context.network_type,
+                        ),
+                        amount,
+                    );
+                    deposits.push(DepositType::StakeRegistration {
+                        amount,
+                        index: i as u32,
+                    });
                 }
                 csl::CertificateKind::StakeDeregistration => {
                     let dereg_cert = cert.as_stake_deregistration().unwrap();
-                    let explitict_refund = dereg_cert.coin();
                     let payedout_address = credential_to_bech32_reward_address(
                         &dereg_cert.stake_credential(),
                         &validation_input_context.network_type,
                     );
-                    let account_context =
-                        validation_input_context.find_account_context(&payedout_address);
-                    if let Some(explicit_refund) = explitict_refund {
-                        refunds.push(RefundType::StakeDeregistration {
-                            amount: explicit_refund.to_str().parse::<i128>().unwrap_or(0),
-                            index: i as u32,
-                            reward_address: payedout_address.clone(),
-                        });
-                    } else {
-                        if let Some(account_context) = account_context {
-                            if let Some(payed_deposit) = account_context.payed_deposit {
-                                refunds.push(RefundType::StakeDeregistration {
-                                    amount: payed_deposit as i128,
-                                    index: i as u32,
-                                    reward_address: payedout_address.clone(),
-                                });
-                            }
-                        } else {
-                            refunds.push(RefundType::StakeDeregistration {
-                                amount: validation_input_context
-                                    .protocol_parameters
-                                    .stake_key_deposit
-                                    as i128,
-                                index: i as u32,
-                                reward_address: payedout_address.clone(),
-                            });
+                    let is_legacy_cert = dereg_cert.coin().is_none();
+                    let expected_amount = infer_stake_deposit(
+                        &payedout_address,
+                        is_legacy_cert,
+                        &stake_registrations_in_tx,
+                        validation_input_context,
+                    );
+                    // Legacy certificates refund whatever the ledger expects, Conway ones state it
+                    let amount = match dereg_cert.coin() {
+                        Some(explicit_refund) => {
+                            explicit_refund.to_str().parse::<i128>().unwrap_or(0)
                         }
-                    }
+                        None => expected_amount.unwrap_or(
+                            validation_input_context.protocol_parameters.stake_key_deposit
+                                as i128,
+                        ),
+                    };
+                    stake_registrations_in_tx.remove(&payedout_address);
+                    refunds.push(RefundType::StakeDeregistration {
+                        amount,
+                        index: i as u32,
+                        reward_address: payedout_address,
+                        expected_amount,
+                    });
                 }
                 csl::CertificateKind::StakeDelegation => {}
                 csl::CertificateKind::PoolRegistration => {
@@ -514,17 +557,29 @@ fn calculate_deposits_and_refunds(
                     let drep_credential = dereg_cert.voting_credential();
                     let drep = csl::DRep::new_from_credential(&drep_credential);
                     let drep_bech32 = drep.to_bech32(true).unwrap_or_else(|_| "".to_string());
+                    let expected_amount = infer_drep_deposit(
+                        &drep_bech32,
+                        &drep_registrations_in_tx,
+                        validation_input_context,
+                    );
+                    drep_registrations_in_tx.remove(&drep_bech32);
                     refunds.push(RefundType::DrepDeregistration {
                         amount: explicit_refund.to_str().parse::<i128>().unwrap_or(0),
                         index: i as u32,
                         drep_bech32,
+                        expected_amount,
                     });
                 }
                 csl::CertificateKind::DRepRegistration => {
                     let reg_cert = cert.as_drep_registration().unwrap();
-                    let explicit_deposit = reg_cert.coin();
+                    let explicit_deposit = reg_cert.coin().to_str().parse::<i128>().unwrap_or(0);
+                    let drep = csl::DRep::new_from_credential(&reg_cert.voting_credential());
+                    drep_registrations_in_tx.insert(
+                        drep.to_bech32(true).unwrap_or_else(|_| "".to_string()),
+                        explicit_deposit,
+                    );
                     deposits.push(DepositType::DrepRegistration {
-                        amount: explicit_deposit.to_str().parse::<i128>().unwrap_or(0),
+                        amount: explicit_deposit,
                         index: i as u32,
                     });
                 }
@@ -532,26 +587,47 @@ fn calculate_deposits_and_refunds(
                 csl::CertificateKind::StakeAndVoteDelegation => {}
                 csl::CertificateKind::StakeRegistrationAndDelegation => {
                     let reg_cert = cert.as_stake_registration_and_delegation().unwrap();
-                    let explicit_deposit = reg_cert.coin();
+                    let explicit_deposit = reg_cert.coin().to_str().parse::<i128>().unwrap_or(0);
+                    stake_registrations_in_tx.insert(
+                        credential_to_bech32_reward_address(
+                            &reg_cert.stake_credential(),
+                            &validation_input_context.network_type,
+                        ),
+                        explicit_deposit,
+                    );
                     deposits.push(DepositType::StakeRegistration {
-                        amount: explicit_deposit.to_str().parse::<i128>().unwrap_or(0),
+                        amount: explicit_deposit,
                         index: i as u32,
                     });
                 }
                 csl::CertificateKind::StakeVoteRegistrationAndDelegation => {
                     let reg_cert = cert.as_stake_vote_registration_and_delegation().unwrap();
-                    let explicit_deposit = reg_cert.coin();
+                    let explicit_deposit = reg_cert.coin().to_str().parse::<i128>().unwrap_or(0);
+                    stake_registrations_in_tx.insert(
+                        credential_to_bech32_reward_address(
+                            &reg_cert.stake_credential(),
+                            &validation_input_context.network_type,
+                        ),
+                        explicit_deposit,
+                    );
                     deposits.push(DepositType::StakeRegistration {
-                        amount: explicit_deposit.to_str().parse::<i128>().unwrap_or(0),
+                        amount: explicit_deposit,
                         index: i as u32,
                     });
                 }
                 csl::CertificateKind::VoteDelegation => {}
                 csl::CertificateKind::VoteRegistrationAndDelegation => {
                     let reg_cert = cert.as_vote_registration_and_delegation().unwrap();
-                    let explicit_deposit = reg_cert.coin();
+                    let explicit_deposit = reg_cert.coin().to_str().parse::<i128>().unwrap_or(0);
+                    stake_registrations_in_tx.insert(
+                        credential_to_bech32_reward_address(
+                            &reg_cert.stake_credential(),
+                            &validation_input_context.network_type,
+                        ),
+                        explicit_deposit,
+                    );
                     deposits.push(DepositType::StakeRegistration {
-                        amount: explicit_deposit.to_str().parse::<i128>().unwrap_or(0),
+                        amount: explicit_deposit,
                         index: i as u32,
                     });
                 }
@@ -561,6 +637,92 @@ fn calculate_deposits_and_refunds(
     (refunds, deposits)
 }
 
+/// Script credentials are exempt from the DRep delegation requirement for withdrawals
+fn is_key_hash_reward_address(reward_address: &str) -> bool {
+    csl::Address::from_bech32(reward_address)
+        .ok()
+        .and_then(|address| csl::RewardAddress::from_address(&address))
+        .map(|address| address.payment_cred().kind() == csl::CredKind::Key)
+        .unwrap_or(true)
+}
+
+/// Deposit that a stake deregistration must refund, in order of preference: a registration
+/// earlier in this tx, the recorded deposit, the deposit parameter in effect at the registration
+/// epoch, or the current parameter if it never changed. Before Conway, legacy certificates refund
+/// the current parameter.
+fn infer_stake_deposit(
+    reward_address: &String,
+    is_legacy_cert: bool,
+    registrations_in_tx: &HashMap<String, i128>,
+    validation_input_context: &ValidationInputContext,
+) -> Option<i128> {
+    if let Some(deposit) = registrations_in_tx.get(reward_address) {
+        return Some(*deposit);
+    }
+    let current_deposit = validation_input_context.protocol_parameters.stake_key_deposit;
+    if is_legacy_cert
+        && validation_input_context
+            .protocol_parameters
+            .protocol_major_version()
+            < CONWAY_PROTOCOL_VERSION
+    {
+        return Some(current_deposit as i128);
+    }
+    let account_context = validation_input_context.find_account_context(reward_address);
+    infer_recorded_deposit(
+        account_context.and_then(|account| account.payed_deposit),
+        account_context.and_then(|account| account.registration_epoch),
+        current_deposit,
+        |parameters| parameters.stake_key_deposit,
+        validation_input_context,
+    )
+}
+
+/// Same as `infer_stake_deposit` for DRep deregistrations
+fn infer_drep_deposit(
+    drep_bech32: &String,
+    registrations_in_tx: &HashMap<String, i128>,
+    validation_input_context: &ValidationInputContext,
+) -> Option<i128> {
+    if let Some(deposit) = registrations_in_tx.get(drep_bech32) {
+        return Some(*deposit);
+    }
+    let drep_context = validation_input_context.find_drep_context(drep_bech32);
+    infer_recorded_deposit(
+        drep_context.and_then(|drep| drep.payed_deposit),
+        drep_context.and_then(|drep| drep.registration_epoch),
+        validation_input_context.protocol_parameters.drep_deposit,
+        |parameters| parameters.drep_deposit,
+        validation_input_context,
+    )
+}
+
+fn infer_recorded_deposit(
+    payed_deposit: Option<u64>,
+    registration_epoch: Option<u64>,
+    current_deposit: u64,
+    deposit_parameter: fn(&DepositParametersInputContext) -> u64,
+    validation_input_context: &ValidationInputContext,
+) -> Option<i128> {
+    if let Some(payed_deposit) = payed_deposit {
+        return Some(payed_deposit as i128);
+    }
+    if let Some(parameters) = registration_epoch
+        .and_then(|epoch| validation_input_context.find_deposit_parameters(epoch))
+    {
+        return Some(deposit_parameter(parameters) as i128);
+    }
+    let history = &validation_input_context.deposit_history;
+    if !history.is_empty()
+        && history
+            .iter()
+            .all(|parameters| deposit_parameter(parameters) == current_deposit)
+    {
+        return Some(current_deposit as i128);
+    }
+    None
+}
+
 fn get_withdrawals(tx_body: &csl::TransactionBody) -> Vec<Withdrawal> {
     let withdrawals = tx_body.withdrawals();
     if let Some(withdrawals) = withdrawals {
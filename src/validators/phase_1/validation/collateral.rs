@@ -1,4 +1,5 @@
 use crate::validators::{
+    common::TransactionEra,
     helpers::{csl_tx_input_to_string, string_to_csl_address},
     input_contexts::ValidationInputContext,
     phase_1::errors::{Phase1Error, Phase1Warning, ValidationPhase1Error, ValidationPhase1Warning},
@@ -25,6 +26,8 @@ pub struct CollateralValidator {
     pub max_number_of_inputs: u32,
     pub need_collateral: bool,
     pub min_ada_for_collateral_return: Option<i128>,
+    /// Collateral return (and with it multi-asset collateral) exists since Babbage
+    pub allows_collateral_return: bool,
 }
 
 impl CollateralValidator {
@@ -64,6 +67,8 @@ impl CollateralValidator {
             .max_collateral_inputs;
         let min_ada_for_collateral_return =
             calculate_min_ada_for_collateral_return(tx_body, validation_input_context);
+        let allows_collateral_return =
+            validation_input_context.transaction_era() >= TransactionEra::Babbage;
         Self {
             invalid_inputs,
             total_input,
@@ -75,6 +80,7 @@ impl CollateralValidator {
             max_number_of_inputs,
             need_collateral,
             min_ada_for_collateral_return,
+            allows_collateral_return,
         }
     }
 
@@ -151,7 +157,7 @@ impl CollateralValidator {
             }
         }
 
-        if self.collateral_return.is_some() {
+        if self.collateral_return.is_some() && self.allows_collateral_return {
             if self
                 .actual_collateral
                 .as_ref()
@@ -0,0 +1,306 @@
+use crate::validators::{
+    common::TransactionEra,
+    input_contexts::ValidationInputContext,
+    phase_1::errors::{Phase1Error, ValidationPhase1Error},
+    validation_result::ValidationResult,
+};
+use cardano_serialization_lib as csl;
+use pallas_traverse::MultiEraTx;
+
+pub struct EraValidator {
+    pub era: TransactionEra,
+    pub unsupported_features: Vec<(String, String)>, // (feature, location)
+    pub decoding_error: Option<String>,
+}
+
+impl EraValidator {
+    pub fn new(
+        tx_bytes: &[u8],
+        tx_body: &csl::TransactionBody,
+        tx_witness_set: &csl::TransactionWitnessSet,
+        validation_input_context: &ValidationInputContext,
+    ) -> Self {
+        let era = validation_input_context.transaction_era();
+        let mut unsupported_features = Vec::new();
+        let mut unsupported = |feature: &str, location: String, introduced_in: TransactionEra| {
+            if era < introduced_in {
+                unsupported_features.push((feature.to_string(), location));
+            }
+        };
+
+        // Allegra: validity start and timelock scripts
+        if tx_body.validity_start_interval_bignum().is_some() {
+            unsupported(
+                "Validity interval start",
+                "transaction.body.validity_start_interval".to_string(),
+                TransactionEra::Allegra,
+            );
+        }
+        if let Some(native_scripts) = tx_witness_set.native_scripts() {
+            for i in 0..native_scripts.len() {
+                if has_timelock(&native_scripts.get(i)) {
+                    unsupported(
+                        "Timelock native script",
+                        format!("transaction.witness_set.native_scripts.{}", i),
+                        TransactionEra::Allegra,
+                    );
+                }
+            }
+        }
+
+        // Mary: multi-assets
+        if tx_body.mint().is_some() {
+            unsupported(
+                "Minting",
+                "transaction.body.mint".to_string(),
+                TransactionEra::Mary,
+            );
+        }
+
+        // Alonzo: Plutus scripts, collateral and the script integrity hash
+        if tx_body.collateral().is_some() {
+            unsupported(
+                "Collateral inputs",
+                "transaction.body.collateral".to_string(),
+                TransactionEra::Alonzo,
+            );
+        }
+        if tx_body.script_data_hash().is_some() {
+            unsupported(
+                "Script data hash",
+                "transaction.body.script_data_hash".to_string(),
+                TransactionEra::Alonzo,
+            );
+        }
+        if tx_body.required_signers().is_some() {
+            unsupported(
+                "Required signers",
+                "transaction.body.required_signers".to_string(),
+                TransactionEra::Alonzo,
+            );
+        }
+        if tx_body.network_id().is_some() {
+            unsupported(
+                "Network id",
+                "transaction.body.network_id".to_string(),
+                TransactionEra::Alonzo,
+            );
+        }
+        if tx_witness_set.redeemers().is_some() {
+            unsupported(
+                "Redeemers",
+                "transaction.witness_set.redeemers".to_string(),
+                TransactionEra::Alonzo,
+            );
+        }
+        if tx_witness_set.plutus_data().is_some() {
+            unsupported(
+                "Plutus data",
+                "transaction.witness_set.plutus_data".to_string(),
+                TransactionEra::Alonzo,
+            );
+        }
+        if let Some(plutus_scripts) = tx_witness_set.plutus_scripts() {
+            for i in 0..plutus_scripts.len() {
+                let location = format!("transaction.witness_set.plutus_scripts.{}", i);
+                match plutus_scripts.get(i).language_version().kind() {
+                    csl::LanguageKind::PlutusV1 => {
+                        unsupported("Plutus V1 script", location, TransactionEra::Alonzo)
+                    }
+                    csl::LanguageKind::PlutusV2 => {
+                        unsupported("Plutus V2 script", location, TransactionEra::Babbage)
+                    }
+                    csl::LanguageKind::PlutusV3 => {
+                        unsupported("Plutus V3 script", location, TransactionEra::Conway)
+                    }
+                }
+            }
+        }
+
+        let outputs = tx_body.outputs();
+        for i in 0..outputs.len() {
+            let output = outputs.get(i);
+            let location = format!("transaction.body.outputs.{}", i);
+            if output.amount().multiasset().is_some() {
+                unsupported("Multi-asset output", location.clone(), TransactionEra::Mary);
+            }
+            if output.has_data_hash() {
+                unsupported(
+                    "Output datum hash",
+                    location.clone(),
+                    TransactionEra::Alonzo,
+                );
+            }
+            if output.has_plutus_data() {
+                unsupported("Inline datum", location.clone(), TransactionEra::Babbage);
+            }
+            if output.has_script_ref() {
+                unsupported("Reference script", location, TransactionEra::Babbage);
+            }
+        }
+
+        // Babbage: reference inputs and collateral return
+        if tx_body.reference_inputs().is_some() {
+            unsupported(
+                "Reference inputs",
+                "transaction.body.reference_inputs".to_string(),
+                TransactionEra::Babbage,
+            );
+        }
+        if tx_body.collateral_return().is_some() {
+            unsupported(
+                "Collateral return",
+                "transaction.body.collateral_return".to_string(),
+                TransactionEra::Babbage,
+            );
+        }
+        if tx_body.total_collateral().is_some() {
+            unsupported(
+                "Total collateral",
+                "transaction.body.total_collateral".to_string(),
+                TransactionEra::Babbage,
+            );
+        }
+
+        // Conway: governance
+        if tx_body.voting_procedures().is_some() {
+            unsupported(
+                "Voting procedures",
+                "transaction.body.voting_procedures".to_string(),
+                TransactionEra::Conway,
+            );
+        }
+        if tx_body.voting_proposals().is_some() {
+            unsupported(
+                "Proposal procedures",
+                "transaction.body.voting_proposals".to_string(),
+                TransactionEra::Conway,
+            );
+        }
+        if tx_body.current_treasury_value().is_some() {
+            unsupported(
+                "Current treasury value",
+                "transaction.body.current_treasury_value".to_string(),
+                TransactionEra::Conway,
+            );
+        }
+        if tx_body.donation().is_some() {
+            unsupported(
+                "Treasury donation",
+                "transaction.body.donation".to_string(),
+                TransactionEra::Conway,
+            );
+        }
+        if let Some(certs) = tx_body.certs() {
+            for i in 0..certs.len() {
+                let location = format!("transaction.body.certs.{}", i);
+                if let Some(feature) = conway_certificate_name(&certs.get(i)) {
+                    unsupported(feature, location, TransactionEra::Conway);
+                }
+            }
+            if era >= TransactionEra::Conway {
+                for i in 0..certs.len() {
+                    if let Some(feature) = removed_certificate_name(&certs.get(i)) {
+                        unsupported_features
+                            .push((feature.to_string(), format!("transaction.body.certs.{}", i)));
+                    }
+                }
+            }
+        }
+
+        // Features that need a codec change are reported above with a precise location,
+        // the decoding check only catches what they don't cover.
+        let decoding_error = if unsupported_features.is_empty() {
+            MultiEraTx::decode_for_era(era.to_pallas_era(), tx_bytes)
+                .err()
+                .map(|e| e.to_string())
+        } else {
+            None
+        };
+
+        Self {
+            era,
+            unsupported_features,
+            decoding_error,
+        }
+    }
+
+    pub fn validate(&self) -> ValidationResult {
+        let mut errors = Vec::new();
+
+        for (feature, location) in &self.unsupported_features {
+            errors.push(ValidationPhase1Error::new(
+                Phase1Error::FeatureNotSupportedInEra {
+                    feature: feature.clone(),
+                    era: self.era,
+                },
+                location.clone(),
+            ));
+        }
+
+        if let Some(message) = &self.decoding_error {
+            errors.push(ValidationPhase1Error::new(
+                Phase1Error::TransactionNotDecodableInEra {
+                    era: self.era,
+                    message: message.clone(),
+                },
+                "transaction".to_string(),
+            ));
+        }
+
+        ValidationResult::new_phase_1(errors, Vec::new())
+    }
+}
+
+fn has_timelock(script: &csl::NativeScript) -> bool {
+    let children = match script.kind() {
+        csl::NativeScriptKind::TimelockStart | csl::NativeScriptKind::TimelockExpiry => {
+            return true
+        }
+        csl::NativeScriptKind::ScriptPubkey => return false,
+        csl::NativeScriptKind::ScriptAll => script.as_script_all().map(|s| s.native_scripts()),
+        csl::NativeScriptKind::ScriptAny => script.as_script_any().map(|s| s.native_scripts()),
+        csl::NativeScriptKind::ScriptNOfK => script.as_script_n_of_k().map(|s| s.native_scripts()),
+    };
+    children.is_some_and(|scripts| (0..scripts.len()).any(|i| has_timelock(&scripts.get(i))))
+}
+
+/// Certificates introduced by Conway, including the deposit carrying registrations
+fn conway_certificate_name(cert: &csl::Certificate) -> Option<&'static str> {
+    match cert.kind() {
+        csl::CertificateKind::StakeRegistration => cert
+            .as_stake_registration()
+            .and_then(|c| c.coin())
+            .map(|_| "Registration certificate with deposit"),
+        csl::CertificateKind::StakeDeregistration => cert
+            .as_stake_deregistration()
+            .and_then(|c| c.coin())
+            .map(|_| "Deregistration certificate with refund"),
+        csl::CertificateKind::CommitteeHotAuth => Some("Committee hot key authorization"),
+        csl::CertificateKind::CommitteeColdResign => Some("Committee resignation"),
+        csl::CertificateKind::DRepRegistration => Some("DRep registration"),
+        csl::CertificateKind::DRepDeregistration => Some("DRep deregistration"),
+        csl::CertificateKind::DRepUpdate => Some("DRep update"),
+        csl::CertificateKind::VoteDelegation => Some("Vote delegation"),
+        csl::CertificateKind::StakeAndVoteDelegation => Some("Stake and vote delegation"),
+        csl::CertificateKind::StakeRegistrationAndDelegation => {
+            Some("Stake registration and delegation")
+        }
+        csl::CertificateKind::VoteRegistrationAndDelegation => {
+            Some("Vote registration and delegation")
+        }
+        csl::CertificateKind::StakeVoteRegistrationAndDelegation => {
+            Some("Stake and vote registration and delegation")
+        }
+        _ => None,
+    }
+}
+
+/// Certificates removed in Conway
+fn removed_certificate_name(cert: &csl::Certificate) -> Option<&'static str> {
+    match cert.kind() {
+        csl::CertificateKind::GenesisKeyDelegation => Some("Genesis key delegation"),
+        csl::CertificateKind::MoveInstantaneousRewardsCert => Some("Move instantaneous rewards"),
+        _ => None,
+    }
+}
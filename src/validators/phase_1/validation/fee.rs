@@ -1,7 +1,7 @@
 use crate::{
     js_error::JsError,
     validators::{
-        common::FeeDecomposition,
+        common::{FeeDecomposition, TransactionEra},
         input_contexts::{UtxoInputContext, ValidationInputContext},
         phase_1::errors::{
             Phase1Error, Phase1Warning, ValidationPhase1Error, ValidationPhase1Warning,
@@ -26,17 +26,23 @@ impl FeeValidator {
     ) -> Result<Self, JsError> {
         let utxos = collect_utxos(tx_body, validation_input_context);
         let redeemers = tx_witness_set.redeemers().unwrap_or(csl::Redeemers::new());
-        let total_reference_scripts_size = utxos
-            .iter()
-            .filter(|utxo| utxo.utxo.output.script_ref.is_some())
-            .map(|utxo| {
-                if let Some(script_ref) = &utxo.utxo.output.script_ref {
-                    script_ref.len() / 2
-                } else {
-                    0
-                }
-            })
-            .sum();
+        // Reference scripts are paid for from Conway on
+        let total_reference_scripts_size =
+            if validation_input_context.transaction_era() >= TransactionEra::Conway {
+                utxos
+                    .iter()
+                    .filter(|utxo| utxo.utxo.output.script_ref.is_some())
+                    .map(|utxo| {
+                        if let Some(script_ref) = &utxo.utxo.output.script_ref {
+                            script_ref.len() / 2
+                        } else {
+                            0
+                        }
+                    })
+                    .sum()
+            } else {
+                0
+            };
         let ref_script_coins_per_byte_csl = validation_input_context
             .protocol_parameters
             .reference_script_cost_per_byte
@@ -135,14 +141,18 @@ fn collect_utxos<'a>(
     let inputs = tx_body.inputs();
     let mut input_utxos: Vec<&'a UtxoInputContext> = inputs
         .into_iter()
-        .map(|input| validation_input_context.find_utxo(input.to_hex(), input.index()))
+        .map(|input| {
+            validation_input_context.find_utxo(input.transaction_id().to_hex(), input.index())
+        })
         .filter_map(|utxo| utxo)
         .collect();
     let ref_utoxs = tx_body.reference_inputs();
     let ref_utoxs: Vec<&'a UtxoInputContext> = ref_utoxs
         .unwrap_or(csl::TransactionInputs::new())
         .into_iter()
-        .map(|input| validation_input_context.find_utxo(input.to_hex(), input.index()))
+        .map(|input| {
+            validation_input_context.find_utxo(input.transaction_id().to_hex(), input.index())
+        })
         .filter_map(|utxo| utxo)
         .collect();
 
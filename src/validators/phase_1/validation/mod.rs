@@ -1,18 +1,27 @@
 pub mod auxiliary_data;
 pub mod balance;
 pub mod collateral;
+pub mod era;
 pub mod fee;
 pub mod native_script_executor;
 pub mod output;
+pub mod output_lint;
 pub mod registration;
+pub mod strict_decoding;
+pub mod token_metadata;
 pub mod transaction_limits;
+pub mod voting_registration;
 pub mod witness;
 
 pub use auxiliary_data::AuxiliaryDataValidator;
 pub use balance::BalanceValidator;
 pub use collateral::CollateralValidator;
+pub use era::EraValidator;
 pub use native_script_executor::NativeScriptExecutor;
 pub use output::OutputValidator;
+pub use output_lint::OutputLintValidator;
 pub use registration::RegistrationValidator;
+pub use strict_decoding::StrictDecodingValidator;
 pub use transaction_limits::TransactionLimitsValidator;
+pub use voting_registration::VotingRegistrationValidator;
 pub use witness::WitnessValidator;
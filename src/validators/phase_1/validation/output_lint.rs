@@ -0,0 +1,233 @@
+use crate::validators::{
+    helpers::{csl_tx_input_to_string, normalize_script_ref, string_to_csl_address},
+    input_contexts::{OutputLintOptions, ValidationInputContext},
+    phase_1::errors::{Phase1Warning, ValidationPhase1Warning},
+    validation_result::ValidationResult,
+    value::Value,
+};
+use cardano_serialization_lib as csl;
+use std::collections::{HashMap, HashSet};
+
+/// Non-fatal checks for outputs that are likely to lock funds or waste fees.
+pub struct OutputLintValidator {
+    pub warnings: Vec<(Phase1Warning, usize)>, // (warning, output_index)
+}
+
+impl OutputLintValidator {
+    pub fn new(
+        tx_body: &csl::TransactionBody,
+        tx_witness_set: &csl::TransactionWitnessSet,
+        validation_input_context: &ValidationInputContext,
+    ) -> Self {
+        let options = &validation_input_context.output_lint_options;
+        let script_versions =
+            collect_plutus_script_versions(tx_body, tx_witness_set, validation_input_context);
+        let published_datums = collect_published_datums(tx_witness_set);
+        let input_outputs = collect_input_outputs(tx_body, validation_input_context);
+
+        let mut warnings = Vec::new();
+        let outputs = tx_body.outputs();
+        for i in 0..outputs.len() {
+            let output = outputs.get(i);
+            let output_warnings = lint_output(
+                &output,
+                options,
+                &script_versions,
+                &published_datums,
+                &input_outputs,
+            );
+            warnings.extend(output_warnings.into_iter().map(|warning| (warning, i)));
+        }
+
+        Self { warnings }
+    }
+
+    pub fn validate(&self) -> ValidationResult {
+        let warnings = self
+            .warnings
+            .iter()
+            .map(|(warning, index)| {
+                ValidationPhase1Warning::new(
+                    warning.clone(),
+                    format!("transaction.body.outputs.{}", index),
+                )
+            })
+            .collect();
+
+        ValidationResult::new_phase_1(Vec::new(), warnings)
+    }
+}
+
+fn lint_output(
+    output: &csl::TransactionOutput,
+    options: &OutputLintOptions,
+    script_versions: &HashMap<csl::ScriptHash, csl::LanguageKind>,
+    published_datums: &HashSet<csl::DataHash>,
+    input_outputs: &[(String, Vec<u8>, Value)],
+) -> Vec<Phase1Warning> {
+    let mut warnings = Vec::new();
+    let address = output.address();
+    let address_str = address.to_bech32(None).unwrap_or_else(|_| address.to_hex());
+    let script_hash = address
+        .payment_cred()
+        .and_then(|credential| credential.to_scripthash());
+
+    if let Some(script_hash) = &script_hash {
+        // Plutus V1/V2 scripts can't spend outputs without a datum
+        if options.script_output_without_datum
+            && !output.has_plutus_data()
+            && !output.has_data_hash()
+        {
+            if let Some(language) = script_versions.get(script_hash) {
+                if *language != csl::LanguageKind::PlutusV3 {
+                    warnings.push(Phase1Warning::ScriptOutputWithoutDatum {
+                        address: address_str.clone(),
+                        script_hash: script_hash.to_hex(),
+                        language: format!("{:?}", language),
+                    });
+                }
+            }
+        }
+
+        // The datum behind a datum hash must be published somewhere to spend the output
+        if options.datum_hash_not_published {
+            if let Some(datum_hash) = output.data_hash() {
+                if !published_datums.contains(&datum_hash) {
+                    warnings.push(Phase1Warning::DatumHashNotPublished {
+                        address: address_str.clone(),
+                        datum_hash: datum_hash.to_hex(),
+                    });
+                }
+            }
+        }
+    }
+
+    // Tokens sent to malformed or pointer addresses
+    if options.tokens_to_unusual_address {
+        let has_tokens = output
+            .amount()
+            .multiasset()
+            .map(|multiasset| multiasset.len() > 0)
+            .unwrap_or(false);
+        let address_kind = address.kind();
+        if has_tokens
+            && (address_kind == csl::AddressKind::Malformed
+                || address_kind == csl::AddressKind::Pointer)
+        {
+            warnings.push(Phase1Warning::TokensSentToUnusualAddress {
+                address: address_str.clone(),
+                address_kind: format!("{:?}", address_kind),
+            });
+        }
+    }
+
+    // Output that just recreates one of the inputs
+    if options.output_repeats_input {
+        let address_bytes = address.to_bytes();
+        let value = Value::new_from_csl_value(&output.amount());
+        if let Some((input, _, _)) = input_outputs
+            .iter()
+            .find(|(_, input_address, input_value)| {
+                *input_address == address_bytes && *input_value == value
+            })
+        {
+            warnings.push(Phase1Warning::OutputRepeatsInput {
+                input: input.clone(),
+            });
+        }
+    }
+
+    // Very large inline datums
+    if let (Some(max_size), Some(datum)) = (options.max_inline_datum_size, output.plutus_data()) {
+        let datum_size = datum.to_bytes().len() as u32;
+        if datum_size > max_size {
+            warnings.push(Phase1Warning::LargeInlineDatum {
+                datum_size,
+                max_size,
+            });
+        }
+    }
+
+    warnings
+}
+
+fn collect_plutus_script_versions(
+    tx_body: &csl::TransactionBody,
+    tx_witness_set: &csl::TransactionWitnessSet,
+    validation_input_context: &ValidationInputContext,
+) -> HashMap<csl::ScriptHash, csl::LanguageKind> {
+    let mut script_versions = HashMap::new();
+
+    if let Some(plutus_scripts) = tx_witness_set.plutus_scripts() {
+        for i in 0..plutus_scripts.len() {
+            let script = plutus_scripts.get(i);
+            script_versions.insert(script.hash(), script.language_version().kind());
+        }
+    }
+
+    let mut script_refs = Vec::new();
+    let inputs = tx_body.inputs();
+    let ref_inputs = tx_body
+        .reference_inputs()
+        .unwrap_or(csl::TransactionInputs::new());
+    for input in inputs.into_iter().chain(&ref_inputs) {
+        if let Some(utxo) = validation_input_context
+            .find_utxo(input.transaction_id().to_hex(), input.index())
+        {
+            if let Some(script_ref_hex) = &utxo.utxo.output.script_ref {
+                if let Ok(script_ref) = normalize_script_ref(script_ref_hex) {
+                    script_refs.push(script_ref);
+                }
+            }
+        }
+    }
+    let outputs = tx_body.outputs();
+    for i in 0..outputs.len() {
+        if let Some(script_ref) = outputs.get(i).script_ref() {
+            script_refs.push(script_ref);
+        }
+    }
+
+    for script_ref in script_refs {
+        if let Some(plutus_script) = script_ref.plutus_script() {
+            script_versions.insert(
+                plutus_script.hash(),
+                plutus_script.language_version().kind(),
+            );
+        }
+    }
+
+    script_versions
+}
+
+fn collect_published_datums(tx_witness_set: &csl::TransactionWitnessSet) -> HashSet<csl::DataHash> {
+    let mut published_datums = HashSet::new();
+    if let Some(plutus_data) = tx_witness_set.plutus_data() {
+        for i in 0..plutus_data.len() {
+            published_datums.insert(csl::hash_plutus_data(&plutus_data.get(i)));
+        }
+    }
+    published_datums
+}
+
+/// Returns (input, address bytes, value) for every spent input found in the context
+fn collect_input_outputs(
+    tx_body: &csl::TransactionBody,
+    validation_input_context: &ValidationInputContext,
+) -> Vec<(String, Vec<u8>, Value)> {
+    let mut input_outputs = Vec::new();
+    for input in tx_body.inputs().into_iter() {
+        if let Some(utxo) = validation_input_context
+            .find_utxo(input.transaction_id().to_hex(), input.index())
+        {
+            if let Ok(address) = string_to_csl_address(&utxo.utxo.output.address) {
+                input_outputs.push((
+                    csl_tx_input_to_string(input),
+                    address.to_bytes(),
+                    Value::new_from_common_assets(&utxo.utxo.output.amount),
+                ));
+            }
+        }
+    }
+    input_outputs
+}
@@ -3,14 +3,23 @@ use std::collections::{HashMap, HashSet};
 
 use crate::validators::{
     common::LocalCredential,
-    helpers::{credential_to_bech32_reward_address, csl_credential_to_local_credential},
+    helpers::{
+        credential_to_bech32_reward_address, csl_credential_to_local_credential,
+        network_type_to_network_id, normalize_drep_id, normalize_pool_id,
+    },
     input_contexts::ValidationInputContext,
     phase_1::errors::{
         Phase1Error, Phase1Warning, ValidationPhase1Error, ValidationPhase1Warning,
     },
+    protocol_params::POST_BOOTSTRAP_PROTOCOL_VERSION,
     validation_result::ValidationResult,
 };
 
+/// Total lovelace supply, the upper bound for pool pledge and cost
+const MAX_LOVELACE_SUPPLY: u64 = 45_000_000_000_000_000;
+/// Maximum length of a relay DNS name and of a pool metadata URL
+const MAX_POOL_TEXT_LENGTH: usize = 64;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum RegistrableEntity {
     Account(String),
@@ -40,6 +49,7 @@ enum CertificateType {
     PoolRegistration {
         pool_id: String,
         pool_cost: u64,
+        pool_params: PoolParamsInfo,
     },
     PoolRetirement {
         pool_id: String,
@@ -87,6 +97,21 @@ enum CertificateType {
     MoveInstantaneousRewardsCert,
 }
 
+#[derive(Debug, Clone)]
+struct PoolParamsInfo {
+    vrf_key_hash: String,
+    pledge: u64,
+    margin_numerator: u64,
+    margin_denominator: u64,
+    reward_account: String,
+    reward_account_network: u8,
+    /// Owner stake keys as reward addresses
+    owners: Vec<String>,
+    /// (relay_index, dns_name) for relays that carry a DNS name
+    relay_dns_names: Vec<(u32, String)>,
+    metadata_url: Option<String>,
+}
+
 #[derive(Debug)]
 struct RegistrationState {
     /// Tracks which entities are registered at the start of tx processing
@@ -98,6 +123,8 @@ struct RegistrationState {
     pool_retirements_in_tx: HashMap<String, u64>, // pool_id -> retirement_epoch
     /// Committee resignations in this tx
     committee_resignations_in_tx: HashSet<LocalCredential>,
+    /// First pool registering each VRF key hash in this tx
+    pool_vrf_key_hashes_in_tx: HashMap<String, String>, // vrf_key_hash -> pool_id
 }
 
 pub struct RegistrationValidator<'a> {
@@ -118,6 +145,7 @@ impl<'a> RegistrationValidator<'a> {
             deregistrations_in_tx: HashSet::new(),
             pool_retirements_in_tx: HashMap::new(),
             committee_resignations_in_tx: HashSet::new(),
+            pool_vrf_key_hashes_in_tx: HashMap::new(),
         };
 
         // Load initial registration state from validation context
@@ -224,7 +252,12 @@ impl<'a> RegistrationValidator<'a> {
                     let pool_params = pool_reg_cert.pool_params();
                     let pool_id = pool_params.operator().to_hex();
                     let pool_cost = pool_params.cost().into();
-                    Some(CertificateType::PoolRegistration { pool_id, pool_cost })
+                    let pool_params = Self::process_pool_params(&pool_params, context);
+                    Some(CertificateType::PoolRegistration {
+                        pool_id,
+                        pool_cost,
+                        pool_params,
+                    })
                 } else {
                     None
                 }
@@ -409,6 +442,62 @@ impl<'a> RegistrationValidator<'a> {
         })
     }
 
+    fn process_pool_params(
+        pool_params: &csl::PoolParams,
+        context: &ValidationInputContext,
+    ) -> PoolParamsInfo {
+        let margin = pool_params.margin();
+        let reward_address = pool_params.reward_account().to_address();
+        let reward_account = reward_address
+            .to_bech32(None)
+            .unwrap_or_else(|_| reward_address.to_hex());
+        let reward_account_network = reward_address.network_id().unwrap_or(0);
+
+        let owners_keyhashes = pool_params.pool_owners();
+        let owners = (0..owners_keyhashes.len())
+            .map(|i| {
+                credential_to_bech32_reward_address(
+                    &csl::Credential::from_keyhash(&owners_keyhashes.get(i)),
+                    &context.network_type,
+                )
+            })
+            .collect();
+
+        let relays = pool_params.relays();
+        let mut relay_dns_names = Vec::new();
+        for i in 0..relays.len() {
+            let relay = relays.get(i);
+            let dns_name = match relay.kind() {
+                csl::RelayKind::SingleHostName => relay
+                    .as_single_host_name()
+                    .map(|relay| relay.dns_name().record()),
+                csl::RelayKind::MultiHostName => relay
+                    .as_multi_host_name()
+                    .map(|relay| relay.dns_name().record()),
+                csl::RelayKind::SingleHostAddr => None,
+            };
+            if let Some(dns_name) = dns_name {
+                relay_dns_names.push((i as u32, dns_name));
+            }
+        }
+
+        // CSL only decodes 32 byte metadata hashes, a bigger one fails the decoding of the
+        // whole transaction, so only the URL is left to check
+        let metadata_url = pool_params.pool_metadata().map(|metadata| metadata.url().url());
+
+        PoolParamsInfo {
+            vrf_key_hash: pool_params.vrf_keyhash().to_hex(),
+            pledge: pool_params.pledge().into(),
+            margin_numerator: margin.numerator().into(),
+            margin_denominator: margin.denominator().into(),
+            reward_account,
+            reward_account_network,
+            owners,
+            relay_dns_names,
+            metadata_url,
+        }
+    }
+
     fn update_state(state: &mut RegistrationState, cert_type: &CertificateType) {
         match cert_type {
             CertificateType::StakeRegistration { reward_address, .. }
@@ -430,11 +519,19 @@ impl<'a> RegistrationValidator<'a> {
                     .registrations_in_tx
                     .remove(&RegistrableEntity::Account(reward_address.clone()));
             }
-            CertificateType::PoolRegistration { pool_id, .. } => {
+            CertificateType::PoolRegistration {
+                pool_id,
+                pool_params,
+                ..
+            } => {
                 state
                     .registrations_in_tx
                     .insert(RegistrableEntity::Pool(pool_id.clone()));
                 state.pool_retirements_in_tx.remove(pool_id);
+                state
+                    .pool_vrf_key_hashes_in_tx
+                    .entry(pool_params.vrf_key_hash.clone())
+                    .or_insert_with(|| pool_id.clone());
             }
             CertificateType::PoolRetirement {
                 pool_id,
@@ -487,9 +584,7 @@ impl<'a> RegistrationValidator<'a> {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
 
-        // Calculate epoch parameters
-        let slots_per_epoch = 432000u64; // Standard epoch length in slots (5 days)
-        let current_epoch = self.validation_input_context.slot / slots_per_epoch;
+        let current_epoch = self.validation_input_context.current_epoch();
 
         for cert_info in &self.certificates {
             self.validate_certificate(&cert_info, current_epoch, &mut errors, &mut warnings);
@@ -636,8 +731,19 @@ impl<'a> RegistrationValidator<'a> {
                         format!("transaction.body.certs.{}", cert_info.cert_index),
                     ));
                 }
+
+                self.check_pool_delegation_health(
+                    reward_address,
+                    pool_id,
+                    cert_info.cert_index,
+                    warnings,
+                );
             }
-            CertificateType::PoolRegistration { pool_id, pool_cost } => {
+            CertificateType::PoolRegistration {
+                pool_id,
+                pool_cost,
+                pool_params,
+            } => {
                 let entity = RegistrableEntity::Pool(pool_id.clone());
 
                 // Check for duplicate registration in the same transaction
@@ -671,6 +777,15 @@ impl<'a> RegistrationValidator<'a> {
                     ));
                 }
 
+                self.validate_pool_params(
+                    pool_id,
+                    *pool_cost,
+                    pool_params,
+                    cert_info.cert_index,
+                    errors,
+                    warnings,
+                );
+
                 // Check if pool is already registered (warning only)
                 if self
                     .registration_state
@@ -860,6 +975,13 @@ impl<'a> RegistrationValidator<'a> {
                         format!("transaction.body.certs.{}", cert_info.cert_index),
                     ));
                 }
+
+                self.check_pool_delegation_health(
+                    reward_address,
+                    pool_id,
+                    cert_info.cert_index,
+                    warnings,
+                );
             }
             CertificateType::DRepUpdate { drep_id } => {
                 // Check if DRep is registered
@@ -964,6 +1086,21 @@ impl<'a> RegistrationValidator<'a> {
                         ));
                     }
                 }
+
+                self.check_pool_delegation_health(
+                    reward_address,
+                    pool_id,
+                    cert_info.cert_index,
+                    warnings,
+                );
+
+                self.check_drep_delegation_health(
+                    reward_address,
+                    drep,
+                    current_epoch,
+                    cert_info.cert_index,
+                    warnings,
+                );
             }
             CertificateType::StakeVoteRegistrationAndDelegation {
                 reward_address,
@@ -1055,6 +1192,21 @@ impl<'a> RegistrationValidator<'a> {
                         ));
                     }
                 }
+
+                self.check_pool_delegation_health(
+                    reward_address,
+                    pool_id,
+                    cert_info.cert_index,
+                    warnings,
+                );
+
+                self.check_drep_delegation_health(
+                    reward_address,
+                    drep,
+                    current_epoch,
+                    cert_info.cert_index,
+                    warnings,
+                );
             }
             CertificateType::VoteDelegation {
                 reward_address,
@@ -1109,6 +1261,14 @@ impl<'a> RegistrationValidator<'a> {
                         ));
                     }
                 }
+
+                self.check_drep_delegation_health(
+                    reward_address,
+                    drep,
+                    current_epoch,
+                    cert_info.cert_index,
+                    warnings,
+                );
             }
             CertificateType::VoteRegistrationAndDelegation {
                 reward_address,
@@ -1175,6 +1335,14 @@ impl<'a> RegistrationValidator<'a> {
                         ));
                     }
                 }
+
+                self.check_drep_delegation_health(
+                    reward_address,
+                    drep,
+                    current_epoch,
+                    cert_info.cert_index,
+                    warnings,
+                );
             }
             CertificateType::GenesisKeyDelegation => {
                 errors.push(ValidationPhase1Error::new(
@@ -1334,4 +1502,238 @@ impl<'a> RegistrationValidator<'a> {
             }
         }
     }
+
+    fn validate_pool_params(
+        &self,
+        pool_id: &String,
+        pool_cost: u64,
+        pool_params: &PoolParamsInfo,
+        cert_index: u32,
+        errors: &mut Vec<ValidationPhase1Error>,
+        warnings: &mut Vec<ValidationPhase1Warning>,
+    ) {
+        let location = format!("transaction.body.certs.{}", cert_index);
+
+        // Margin must be a unit interval
+        if pool_params.margin_denominator == 0
+            || pool_params.margin_numerator > pool_params.margin_denominator
+        {
+            errors.push(ValidationPhase1Error::new(
+                Phase1Error::PoolMarginOutOfRange {
+                    pool_id: pool_id.clone(),
+                    numerator: pool_params.margin_numerator,
+                    denominator: pool_params.margin_denominator,
+                },
+                location.clone(),
+            ));
+        }
+
+        // Pledge and cost can't exceed the lovelace supply
+        for (field, amount) in [("pledge", pool_params.pledge), ("cost", pool_cost)].iter() {
+            if *amount > MAX_LOVELACE_SUPPLY {
+                errors.push(ValidationPhase1Error::new(
+                    Phase1Error::PoolAmountOutOfBounds {
+                        pool_id: pool_id.clone(),
+                        field: field.to_string(),
+                        amount: *amount,
+                        max_amount: MAX_LOVELACE_SUPPLY,
+                    },
+                    location.clone(),
+                ));
+            }
+        }
+
+        // Reward account must be on the current network
+        let expected_network =
+            network_type_to_network_id(&self.validation_input_context.network_type);
+        if pool_params.reward_account_network != expected_network {
+            errors.push(ValidationPhase1Error::new(
+                Phase1Error::WrongNetworkPool {
+                    pool_id: pool_id.clone(),
+                    reward_account: pool_params.reward_account.clone(),
+                    expected_network,
+                },
+                location.clone(),
+            ));
+        }
+
+        // Owner stake keys should be registered for the pledge to count
+        for owner in &pool_params.owners {
+            if let Some(account_context) = self.validation_input_context.find_account_context(owner)
+            {
+                if !account_context.is_registered {
+                    warnings.push(ValidationPhase1Warning::new(
+                        Phase1Warning::PoolOwnerStakeKeyNotRegistered {
+                            pool_id: pool_id.clone(),
+                            owner_reward_address: owner.clone(),
+                        },
+                        location.clone(),
+                    ));
+                }
+            }
+        }
+
+        // Relay DNS names are limited to 64 bytes
+        for (relay_index, dns_name) in &pool_params.relay_dns_names {
+            if dns_name.len() > MAX_POOL_TEXT_LENGTH {
+                errors.push(ValidationPhase1Error::new(
+                    Phase1Error::PoolRelayDnsNameTooLong {
+                        pool_id: pool_id.clone(),
+                        relay_index: *relay_index,
+                        dns_name: dns_name.clone(),
+                        length: dns_name.len() as u32,
+                        max_length: MAX_POOL_TEXT_LENGTH as u32,
+                    },
+                    format!("{}.relays.{}", location, relay_index),
+                ));
+            }
+        }
+
+        // Metadata URL is limited to 64 bytes
+        if let Some(url) = &pool_params.metadata_url {
+            if url.len() > MAX_POOL_TEXT_LENGTH {
+                errors.push(ValidationPhase1Error::new(
+                    Phase1Error::PoolMetadataUrlTooLong {
+                        pool_id: pool_id.clone(),
+                        url: url.clone(),
+                        length: url.len() as u32,
+                        max_length: MAX_POOL_TEXT_LENGTH as u32,
+                    },
+                    format!("{}.pool_metadata.url", location),
+                ));
+            }
+        }
+
+        // VRF keys must be unique across pools starting with protocol version 10
+        let protocol_major_version = self
+            .validation_input_context
+            .protocol_parameters
+            .protocol_major_version();
+        if protocol_major_version >= POST_BOOTSTRAP_PROTOCOL_VERSION {
+            let registered_pool_id = self
+                .validation_input_context
+                .find_vrf_key_hash_context(&pool_params.vrf_key_hash)
+                .map(|context| context.pool_id.clone())
+                .filter(|registered_pool_id| registered_pool_id != pool_id)
+                .or_else(|| {
+                    self.registration_state
+                        .pool_vrf_key_hashes_in_tx
+                        .get(&pool_params.vrf_key_hash)
+                        .filter(|registered_pool_id| *registered_pool_id != pool_id)
+                        .cloned()
+                });
+            if let Some(registered_pool_id) = registered_pool_id {
+                errors.push(
+                    ValidationPhase1Error::new(
+                        Phase1Error::VrfKeyHashAlreadyRegistered {
+                            pool_id: pool_id.clone(),
+                            vrf_key_hash: pool_params.vrf_key_hash.clone(),
+                            registered_pool_id,
+                        },
+                        location,
+                    )
+                    .with_rule_version(POST_BOOTSTRAP_PROTOCOL_VERSION),
+                );
+            }
+        }
+    }
+
+    /// Returns true if the account keeps its existing delegations through this tx,
+    /// i.e. it is not (re-)registered or deregistered by any certificate.
+    fn keeps_existing_delegations(&self, reward_address: &str) -> bool {
+        let entity = RegistrableEntity::Account(reward_address.to_string());
+        !self.registration_state.registrations_in_tx.contains(&entity)
+            && !self.registration_state.deregistrations_in_tx.contains(&entity)
+    }
+
+    fn check_pool_delegation_health(
+        &self,
+        reward_address: &String,
+        pool_id: &String,
+        cert_index: u32,
+        warnings: &mut Vec<ValidationPhase1Warning>,
+    ) {
+        // Check if the pool is going to retire
+        if let Some(pool_context) = self.validation_input_context.find_pool_context(pool_id) {
+            if let Some(retirement_epoch) = pool_context.retirement_epoch {
+                warnings.push(ValidationPhase1Warning::new(
+                    Phase1Warning::DelegationToRetiringPool {
+                        pool_id: pool_id.clone(),
+                        retirement_epoch,
+                    },
+                    format!("transaction.body.certs.{}", cert_index),
+                ));
+            }
+        }
+
+        // Check if the account is already delegated to the same pool
+        if let Some(account_context) = self
+            .validation_input_context
+            .find_account_context(reward_address)
+        {
+            if let Some(delegated_to_pool) = &account_context.delegated_to_pool {
+                if account_context.is_registered
+                    && self.keeps_existing_delegations(reward_address)
+                    && normalize_pool_id(delegated_to_pool) == normalize_pool_id(pool_id)
+                {
+                    warnings.push(ValidationPhase1Warning::new(
+                        Phase1Warning::RedundantDelegation {
+                            reward_address: reward_address.clone(),
+                            delegation_target: pool_id.clone(),
+                        },
+                        format!("transaction.body.certs.{}", cert_index),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_drep_delegation_health(
+        &self,
+        reward_address: &String,
+        drep: &String,
+        current_epoch: u64,
+        cert_index: u32,
+        warnings: &mut Vec<ValidationPhase1Warning>,
+    ) {
+        // Check if the DRep is expired
+        if !drep.is_empty() {
+            if let Some(drep_context) = self.validation_input_context.find_drep_context(drep) {
+                if let Some(expiry_epoch) = drep_context.expiry_epoch {
+                    if drep_context.is_registered && expiry_epoch < current_epoch {
+                        warnings.push(ValidationPhase1Warning::new(
+                            Phase1Warning::DelegationToInactiveDRep {
+                                drep_id: drep.clone(),
+                                expiry_epoch,
+                                current_epoch,
+                            },
+                            format!("transaction.body.certs.{}", cert_index),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Check if the account is already delegated to the same DRep
+        if let Some(account_context) = self
+            .validation_input_context
+            .find_account_context(reward_address)
+        {
+            if let Some(delegated_to_drep) = &account_context.delegated_to_drep {
+                if !drep.is_empty()
+                    && account_context.is_registered
+                    && self.keeps_existing_delegations(reward_address)
+                    && normalize_drep_id(delegated_to_drep) == normalize_drep_id(drep)
+                {
+                    warnings.push(ValidationPhase1Warning::new(
+                        Phase1Warning::RedundantDelegation {
+                            reward_address: reward_address.clone(),
+                            delegation_target: drep.clone(),
+                        },
+                        format!("transaction.body.certs.{}", cert_index),
+                    ));
+                }
+            }
+        }
+    }
 }
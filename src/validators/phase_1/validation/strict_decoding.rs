@@ -0,0 +1,434 @@
+use crate::{
+    cbor::cbor_decoder::{get_tokenizer, get_value},
+    js_error::JsError,
+    validators::{
+        phase_1::errors::{Phase1Error, ValidationPhase1Error},
+        validation_result::ValidationResult,
+    },
+};
+use serde_json::Value;
+use std::collections::HashSet;
+
+const SET_TAG: &str = "Unassigned(258)";
+
+// Transaction body keys
+const BODY_INPUTS: u64 = 0;
+const BODY_OUTPUTS: u64 = 1;
+const BODY_CERTS: u64 = 4;
+const BODY_WITHDRAWALS: u64 = 5;
+const BODY_MINT: u64 = 9;
+const BODY_COLLATERAL: u64 = 13;
+const BODY_REQUIRED_SIGNERS: u64 = 14;
+const BODY_REFERENCE_INPUTS: u64 = 18;
+const BODY_VOTING_PROCEDURES: u64 = 19;
+const BODY_PROPOSAL_PROCEDURES: u64 = 20;
+
+// Witness set keys
+const WITNESS_REDEEMERS: u64 = 5;
+
+/// Re-checks the raw transaction CBOR against the decoding rules the ledger enforces
+/// since protocol version 9, which CSL accepts or silently normalizes.
+pub struct StrictDecodingValidator {
+    pub violations: Vec<(Phase1Error, String)>,
+}
+
+impl StrictDecodingValidator {
+    pub fn new(tx_bytes: &[u8]) -> Result<Self, JsError> {
+        let root = get_value(get_tokenizer(tx_bytes))?;
+        let tx = root
+            .as_array()
+            .and_then(|items| items.first())
+            .ok_or_else(|| JsError::new("Failed to decode transaction CBOR"))?;
+
+        let mut violations = Vec::new();
+        if let Some(tx_items) = array_values(tx) {
+            if let Some(body) = tx_items.first() {
+                check_body(body, &mut violations);
+            }
+            if let Some(witness_set) = tx_items.get(1) {
+                check_witness_set(witness_set, &mut violations);
+            }
+        }
+
+        Ok(Self { violations })
+    }
+
+    pub fn validate(&self) -> ValidationResult {
+        let errors = self
+            .violations
+            .iter()
+            .map(|(error, location)| ValidationPhase1Error::new(error.clone(), location.clone()))
+            .collect();
+        ValidationResult::new_phase_1(errors, vec![])
+    }
+}
+
+fn check_body(body: &Value, violations: &mut Vec<(Phase1Error, String)>) {
+    let location = "transaction.body";
+    check_map_keys(body, location, violations);
+
+    for (key, value) in map_entries(body) {
+        let key = match uint_value(key) {
+            Some(key) => key,
+            None => continue,
+        };
+        match key {
+            BODY_INPUTS => {
+                check_set(value, &format!("{}.inputs", location), false, violations);
+            }
+            BODY_OUTPUTS => {
+                for (index, output) in array_values(value).unwrap_or(&vec![]).iter().enumerate() {
+                    let output_location = format!("{}.outputs.{}", location, index);
+                    check_map_keys(output, &output_location, violations);
+                    // Post-Alonzo outputs are maps with the value under key 1,
+                    // legacy outputs are arrays with the value at position 1
+                    let amount = if get_type(output) == Some("Map") {
+                        map_entries(output)
+                            .into_iter()
+                            .find(|(key, _)| uint_value(key) == Some(1))
+                            .map(|(_, value)| value)
+                    } else {
+                        array_values(output).and_then(|items| items.get(1))
+                    };
+                    if let Some(multi_asset) = amount
+                        .and_then(array_values)
+                        .and_then(|items| items.get(1))
+                    {
+                        check_multi_asset(
+                            multi_asset,
+                            &format!("{}.amount", output_location),
+                            violations,
+                        );
+                    }
+                }
+            }
+            BODY_CERTS => {
+                check_set(value, &format!("{}.certs", location), true, violations);
+            }
+            BODY_WITHDRAWALS => {
+                let withdrawals_location = format!("{}.withdrawals", location);
+                check_non_empty_map(value, &withdrawals_location, violations);
+                check_map_keys(value, &withdrawals_location, violations);
+            }
+            BODY_MINT => {
+                check_non_empty_map(value, &format!("{}.mint", location), violations);
+                check_multi_asset(value, &format!("{}.mint", location), violations);
+            }
+            BODY_COLLATERAL => {
+                check_set(value, &format!("{}.collateral", location), true, violations);
+            }
+            BODY_REQUIRED_SIGNERS => {
+                check_set(
+                    value,
+                    &format!("{}.required_signers", location),
+                    true,
+                    violations,
+                );
+            }
+            BODY_REFERENCE_INPUTS => {
+                check_set(
+                    value,
+                    &format!("{}.reference_inputs", location),
+                    true,
+                    violations,
+                );
+            }
+            BODY_VOTING_PROCEDURES => {
+                let voting_location = format!("{}.voting_procedures", location);
+                check_non_empty_map(value, &voting_location, violations);
+                check_nested_map_keys(value, &voting_location, violations);
+            }
+            BODY_PROPOSAL_PROCEDURES => {
+                let proposals_location = format!("{}.voting_proposals", location);
+                check_set(value, &proposals_location, true, violations);
+                check_nested_map_keys(value, &proposals_location, violations);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_witness_set(witness_set: &Value, violations: &mut Vec<(Phase1Error, String)>) {
+    let location = "transaction.witness_set";
+    check_map_keys(witness_set, location, violations);
+
+    for (key, value) in map_entries(witness_set) {
+        let key = match uint_value(key) {
+            Some(key) => key,
+            None => continue,
+        };
+        let field_location = format!("{}.{}", location, witness_set_field_name(key));
+        if key == WITNESS_REDEEMERS {
+            // Conway redeemers are either a legacy array or a map keyed by [tag, index]
+            if get_type(value) == Some("Map") {
+                check_non_empty_map(value, &field_location, violations);
+                check_map_keys(value, &field_location, violations);
+            } else if array_values(value).map(|items| items.is_empty()).unwrap_or(false) {
+                violations.push((
+                    Phase1Error::EmptyNonEmptySet {
+                        field: field_location.clone(),
+                        offset: offset(value),
+                    },
+                    field_location,
+                ));
+            }
+        } else {
+            check_set(value, &field_location, true, violations);
+        }
+    }
+}
+
+fn witness_set_field_name(key: u64) -> String {
+    match key {
+        0 => "vkeys".to_string(),
+        1 => "native_scripts".to_string(),
+        2 => "bootstraps".to_string(),
+        3 => "plutus_scripts_v1".to_string(),
+        4 => "plutus_data".to_string(),
+        5 => "redeemers".to_string(),
+        6 => "plutus_scripts_v2".to_string(),
+        7 => "plutus_scripts_v3".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Checks a `set<a>` / `nonempty_set<a>` field: the optional tag must be 258,
+/// elements must be unique and, for non-empty sets, there must be at least one element.
+fn check_set(
+    value: &Value,
+    location: &str,
+    non_empty: bool,
+    violations: &mut Vec<(Phase1Error, String)>,
+) {
+    let elements_node = if get_type(value) == Some("Tag") {
+        let tag = value.get("tag").and_then(Value::as_str).unwrap_or_default();
+        if tag != SET_TAG {
+            violations.push((
+                Phase1Error::InvalidSetTag {
+                    field: location.to_string(),
+                    tag: tag.to_string(),
+                    offset: offset(value),
+                },
+                location.to_string(),
+            ));
+        }
+        match value.get("value") {
+            Some(inner) => inner,
+            None => return,
+        }
+    } else {
+        value
+    };
+
+    let elements = match array_values(elements_node) {
+        Some(elements) => elements,
+        None => return,
+    };
+
+    if non_empty && elements.is_empty() {
+        violations.push((
+            Phase1Error::EmptyNonEmptySet {
+                field: location.to_string(),
+                offset: offset(value),
+            },
+            location.to_string(),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for (index, element) in elements.iter().enumerate() {
+        if !seen.insert(semantic_key(element)) {
+            violations.push((
+                Phase1Error::DuplicateSetElement {
+                    field: location.to_string(),
+                    offset: offset(element),
+                },
+                format!("{}.{}", location, index),
+            ));
+        }
+    }
+}
+
+fn check_non_empty_map(value: &Value, location: &str, violations: &mut Vec<(Phase1Error, String)>) {
+    if get_type(value) == Some("Map") && map_entries(value).is_empty() {
+        violations.push((
+            Phase1Error::EmptyNonEmptySet {
+                field: location.to_string(),
+                offset: offset(value),
+            },
+            location.to_string(),
+        ));
+    }
+}
+
+/// Reports keys that occur more than once in a single map.
+fn check_map_keys(value: &Value, location: &str, violations: &mut Vec<(Phase1Error, String)>) {
+    if get_type(value) != Some("Map") {
+        return;
+    }
+    let mut seen = HashSet::new();
+    for (key, _) in map_entries(value) {
+        if !seen.insert(semantic_key(key)) {
+            violations.push((
+                Phase1Error::DuplicateMapKey {
+                    field: location.to_string(),
+                    offset: offset(key),
+                },
+                location.to_string(),
+            ));
+        }
+    }
+}
+
+/// Recursively checks duplicate keys in every map nested inside `value`.
+fn check_nested_map_keys(
+    value: &Value,
+    location: &str,
+    violations: &mut Vec<(Phase1Error, String)>,
+) {
+    match get_type(value) {
+        Some("Map") => {
+            check_map_keys(value, location, violations);
+            for (_, entry_value) in map_entries(value) {
+                check_nested_map_keys(entry_value, location, violations);
+            }
+        }
+        Some("Array") => {
+            for item in array_values(value).unwrap_or(&vec![]) {
+                check_nested_map_keys(item, location, violations);
+            }
+        }
+        Some("Tag") => {
+            if let Some(inner) = value.get("value") {
+                check_nested_map_keys(inner, location, violations);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks a `multiasset<a>` map: no duplicate policies or asset names, no empty
+/// asset maps and no zero quantities.
+fn check_multi_asset(value: &Value, location: &str, violations: &mut Vec<(Phase1Error, String)>) {
+    check_map_keys(value, location, violations);
+    for (policy_id, assets) in map_entries(value) {
+        let policy_id_hex = scalar_value(policy_id).unwrap_or_default();
+        check_map_keys(assets, location, violations);
+        if get_type(assets) == Some("Map") && map_entries(assets).is_empty() {
+            violations.push((
+                Phase1Error::EmptyNonEmptySet {
+                    field: format!("{}.{}", location, policy_id_hex),
+                    offset: offset(assets),
+                },
+                location.to_string(),
+            ));
+        }
+        for (asset_name, quantity) in map_entries(assets) {
+            if quantity.get("value").and_then(Value::as_i64) == Some(0) {
+                violations.push((
+                    Phase1Error::ZeroQuantityAsset {
+                        field: location.to_string(),
+                        policy_id: policy_id_hex.clone(),
+                        asset_name: scalar_value(asset_name).unwrap_or_default(),
+                        offset: offset(quantity),
+                    },
+                    location.to_string(),
+                ));
+            }
+        }
+    }
+}
+
+pub(crate) fn get_type(value: &Value) -> Option<&str> {
+    value.get("type").and_then(Value::as_str)
+}
+
+pub(crate) fn offset(value: &Value) -> u64 {
+    value
+        .get("position_info")
+        .and_then(|pos| pos.get("offset"))
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+pub(crate) fn array_values(value: &Value) -> Option<&Vec<Value>> {
+    match get_type(value) {
+        Some("Array") => value.get("values").and_then(Value::as_array),
+        _ => None,
+    }
+}
+
+pub(crate) fn map_entries(value: &Value) -> Vec<(&Value, &Value)> {
+    match get_type(value) {
+        Some("Map") => value
+            .get("values")
+            .and_then(Value::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| Some((entry.get("key")?, entry.get("value")?)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+pub(crate) fn uint_value(value: &Value) -> Option<u64> {
+    value.get("value").and_then(Value::as_u64)
+}
+
+pub(crate) fn scalar_value(value: &Value) -> Option<String> {
+    match value.get("value") {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(other) => Some(other.to_string()),
+        None => None,
+    }
+}
+
+/// Builds an encoding-independent representation of a decoded CBOR item, so that
+/// e.g. `0x01` and `0x1801` compare equal while positions are ignored.
+fn semantic_key(value: &Value) -> String {
+    let kind = get_type(value).unwrap_or_default();
+    match kind {
+        "Array" => format!(
+            "[{}]",
+            array_values(value)
+                .unwrap_or(&vec![])
+                .iter()
+                .map(semantic_key)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        "Map" => format!(
+            "{{{}}}",
+            map_entries(value)
+                .iter()
+                .map(|(k, v)| format!("{}:{}", semantic_key(k), semantic_key(v)))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        "Tag" => format!(
+            "{}({})",
+            value.get("tag").and_then(Value::as_str).unwrap_or_default(),
+            value.get("value").map(semantic_key).unwrap_or_default()
+        ),
+        "IndefiniteLengthString" | "IndefiniteLengthBytes" => {
+            let prefix = if kind == "IndefiniteLengthString" { "s" } else { "b" };
+            let content: String = value
+                .get("chunks")
+                .and_then(Value::as_array)
+                .unwrap_or(&vec![])
+                .iter()
+                .filter_map(scalar_value)
+                .collect();
+            format!("{}:{}", prefix, content)
+        }
+        "U8" | "U16" | "U32" | "U64" | "I8" | "I16" | "I32" | "I64" | "Int" => {
+            format!("i:{}", scalar_value(value).unwrap_or_default())
+        }
+        "Bytes" => format!("b:{}", scalar_value(value).unwrap_or_default()),
+        "String" => format!("s:{}", scalar_value(value).unwrap_or_default()),
+        other => format!("{}:{}", other, scalar_value(value).unwrap_or_default()),
+    }
+}
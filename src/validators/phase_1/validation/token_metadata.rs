@@ -0,0 +1,517 @@
+use crate::validators::{helpers::string_to_csl_address, phase_1::errors::Phase1Warning};
+use cardano_serialization_lib as csl;
+use std::collections::BTreeMap;
+
+const CIP25_LABEL: u64 = 721;
+const CIP27_LABEL: u64 = 777;
+
+/// CIP-67 asset name prefixes used by CIP-68 tokens
+const CIP68_REFERENCE_PREFIX: &str = "000643b0"; // (100)
+const CIP68_NFT_PREFIX: &str = "000de140"; // (222)
+const CIP68_FT_PREFIX: &str = "0014df10"; // (333)
+const CIP68_RFT_PREFIX: &str = "001bc280"; // (444)
+
+/// policy_id -> asset_name -> minted quantity, all hex encoded
+type MintedAssets = BTreeMap<String, BTreeMap<String, u64>>;
+
+/// Checks the metadata of minting transactions against the CIP-25, CIP-68 and CIP-27
+/// token standards. Returns (warning, location) pairs.
+pub fn collect_token_metadata_warnings(
+    tx_body: &csl::TransactionBody,
+    auxiliary_data: Option<&csl::AuxiliaryData>,
+) -> Vec<(Phase1Warning, String)> {
+    let mut warnings = Vec::new();
+    let minted = collect_minted_assets(tx_body);
+    if minted.is_empty() {
+        return warnings;
+    }
+
+    let metadata = auxiliary_data.and_then(|auxiliary_data| auxiliary_data.metadata());
+    if let Some(metadata) = &metadata {
+        if let Some(cip25) = metadata.get(&csl::BigNum::from(CIP25_LABEL)) {
+            check_cip25(&cip25, &minted, &mut warnings);
+        }
+        if let Some(cip27) = metadata.get(&csl::BigNum::from(CIP27_LABEL)) {
+            check_cip27(&cip27, &minted, &mut warnings);
+        }
+    }
+    check_cip68(tx_body, &minted, &mut warnings);
+
+    warnings
+}
+
+fn collect_minted_assets(tx_body: &csl::TransactionBody) -> MintedAssets {
+    let mut minted = MintedAssets::new();
+    let multiasset = match tx_body.mint() {
+        Some(mint) => mint.as_positive_multiasset(),
+        None => return minted,
+    };
+    let policies = multiasset.keys();
+    for i in 0..policies.len() {
+        let policy_id = policies.get(i);
+        if let Some(assets) = multiasset.get(&policy_id) {
+            let asset_names = assets.keys();
+            let policy_assets = minted.entry(policy_id.to_hex()).or_default();
+            for j in 0..asset_names.len() {
+                let asset_name = asset_names.get(j);
+                let quantity = assets.get(&asset_name).map(u64::from).unwrap_or(0);
+                policy_assets.insert(hex::encode(asset_name.name()), quantity);
+            }
+        }
+    }
+    minted
+}
+
+// ---------------------------------------------------------------------------
+// CIP-25
+// ---------------------------------------------------------------------------
+
+fn check_cip25(
+    metadatum: &csl::TransactionMetadatum,
+    minted: &MintedAssets,
+    warnings: &mut Vec<(Phase1Warning, String)>,
+) {
+    let location = format!("transaction.auxiliary_data.metadata.{}", CIP25_LABEL);
+    let policies = match metadatum.as_map() {
+        Ok(policies) => policies,
+        Err(_) => {
+            warnings.push((
+                Phase1Warning::Cip25MetadataMismatch {
+                    policy_id: String::new(),
+                    asset_name: String::new(),
+                    message: "CIP-25 metadata must be a map of policy ids".to_string(),
+                },
+                location,
+            ));
+            return;
+        }
+    };
+
+    let version = policies
+        .get_str("version")
+        .ok()
+        .and_then(|version| version.as_int().ok())
+        .and_then(|version| version.as_i32_or_nothing())
+        .unwrap_or(1);
+
+    let mut described = MintedAssets::new();
+    let policy_keys = policies.keys();
+    for i in 0..policy_keys.len() {
+        let policy_key = policy_keys.get(i);
+        if policy_key
+            .as_text()
+            .map(|key| key == "version")
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let policy_id = match metadatum_key_to_hex(&policy_key, version, true) {
+            Ok(policy_id) => policy_id,
+            Err(message) => {
+                warnings.push((
+                    Phase1Warning::Cip25MetadataMismatch {
+                        policy_id: metadatum_key_to_string(&policy_key),
+                        asset_name: String::new(),
+                        message,
+                    },
+                    location.clone(),
+                ));
+                metadatum_key_to_string(&policy_key)
+            }
+        };
+        let policy_location = format!("{}.{}", location, policy_id);
+
+        let assets = match policies.get(&policy_key).and_then(|assets| assets.as_map()) {
+            Ok(assets) => assets,
+            Err(_) => {
+                warnings.push((
+                    Phase1Warning::Cip25MetadataMismatch {
+                        policy_id: policy_id.clone(),
+                        asset_name: String::new(),
+                        message: "Policy entry must be a map of asset names".to_string(),
+                    },
+                    policy_location,
+                ));
+                continue;
+            }
+        };
+
+        let asset_keys = assets.keys();
+        for j in 0..asset_keys.len() {
+            let asset_key = asset_keys.get(j);
+            let asset_name = match metadatum_key_to_hex(&asset_key, version, false) {
+                Ok(asset_name) => asset_name,
+                Err(message) => {
+                    warnings.push((
+                        Phase1Warning::Cip25MetadataMismatch {
+                            policy_id: policy_id.clone(),
+                            asset_name: metadatum_key_to_string(&asset_key),
+                            message,
+                        },
+                        policy_location.clone(),
+                    ));
+                    continue;
+                }
+            };
+            let asset_location = format!("{}.{}", policy_location, asset_name);
+            described
+                .entry(policy_id.clone())
+                .or_default()
+                .insert(asset_name.clone(), 0);
+
+            let is_minted = minted
+                .get(&policy_id)
+                .map(|assets| assets.contains_key(&asset_name))
+                .unwrap_or(false);
+            if !is_minted {
+                warnings.push((
+                    Phase1Warning::Cip25MetadataMismatch {
+                        policy_id: policy_id.clone(),
+                        asset_name: asset_name.clone(),
+                        message:
+                            "Asset is described in the metadata but not minted in this transaction"
+                                .to_string(),
+                    },
+                    asset_location.clone(),
+                ));
+            }
+
+            if let Ok(details) = assets.get(&asset_key).and_then(|details| details.as_map()) {
+                check_cip25_details(&details, &policy_id, &asset_name, &asset_location, warnings);
+            }
+        }
+    }
+
+    // Every asset minted under a described policy should be described too
+    for (policy_id, assets) in minted {
+        if let Some(described_assets) = described.get(policy_id) {
+            for asset_name in assets.keys() {
+                if !described_assets.contains_key(asset_name) && cip68_label(asset_name).is_none() {
+                    warnings.push((
+                        Phase1Warning::Cip25MetadataMismatch {
+                            policy_id: policy_id.clone(),
+                            asset_name: asset_name.clone(),
+                            message: "Minted asset has no CIP-25 metadata".to_string(),
+                        },
+                        "transaction.body.mint".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn check_cip25_details(
+    details: &csl::MetadataMap,
+    policy_id: &str,
+    asset_name: &str,
+    location: &str,
+    warnings: &mut Vec<(Phase1Warning, String)>,
+) {
+    let mut push = |message: String| {
+        warnings.push((
+            Phase1Warning::Cip25MetadataMismatch {
+                policy_id: policy_id.to_string(),
+                asset_name: asset_name.to_string(),
+                message,
+            },
+            location.to_string(),
+        ));
+    };
+
+    if details
+        .get_str("name")
+        .and_then(|name| name.as_text())
+        .is_err()
+    {
+        push("Required field \"name\" is missing or is not a string".to_string());
+    }
+    match details.get_str("image") {
+        Ok(image) => {
+            if !is_chunked_text(&image) {
+                push(
+                    "Field \"image\" must be a string or a list of strings of at most 64 bytes"
+                        .to_string(),
+                );
+            }
+        }
+        Err(_) => push("Required field \"image\" is missing".to_string()),
+    }
+    if let Ok(files) = details.get_str("files").and_then(|files| files.as_list()) {
+        for i in 0..files.len() {
+            let src = files.get(i).as_map().and_then(|file| file.get_str("src"));
+            if !src.map(|src| is_chunked_text(&src)).unwrap_or(false) {
+                push(format!(
+                    "Field \"files.{}.src\" must be a string or a list of strings of at most 64 bytes",
+                    i
+                ));
+            }
+        }
+    }
+}
+
+/// Converts a CIP-25 policy or asset key to hex. Version 1 uses text keys (the policy id
+/// as hex text and the asset name as UTF-8 text), version 2 uses raw bytes.
+fn metadatum_key_to_hex(
+    key: &csl::TransactionMetadatum,
+    version: i32,
+    is_policy: bool,
+) -> Result<String, String> {
+    let key_kind = if is_policy { "Policy id" } else { "Asset name" };
+    match (key.as_text(), key.as_bytes()) {
+        (Ok(text), _) => {
+            if version >= 2 {
+                return Err(format!(
+                    "{} key is a string, but CIP-25 version 2 requires raw bytes",
+                    key_kind
+                ));
+            }
+            Ok(if is_policy {
+                text
+            } else {
+                hex::encode(text.as_bytes())
+            })
+        }
+        (_, Ok(bytes)) => {
+            if version < 2 {
+                return Err(format!(
+                    "{} key is raw bytes, but CIP-25 version 1 requires a string (set \"version\": 2)",
+                    key_kind
+                ));
+            }
+            Ok(hex::encode(bytes))
+        }
+        _ => Err(format!("{} key must be a string or bytes", key_kind)),
+    }
+}
+
+fn metadatum_key_to_string(key: &csl::TransactionMetadatum) -> String {
+    key.as_text()
+        .or_else(|_| key.as_bytes().map(hex::encode))
+        .unwrap_or_default()
+}
+
+/// A string, or a list of strings that is concatenated (the CIP-25 way to store long URIs)
+fn is_chunked_text(metadatum: &csl::TransactionMetadatum) -> bool {
+    match metadatum.as_list() {
+        Ok(chunks) => (0..chunks.len()).all(|i| chunks.get(i).as_text().is_ok()),
+        Err(_) => metadatum.as_text().is_ok(),
+    }
+}
+
+fn chunked_text(metadatum: &csl::TransactionMetadatum) -> Option<String> {
+    match metadatum.as_list() {
+        Ok(chunks) => (0..chunks.len())
+            .map(|i| chunks.get(i).as_text().ok())
+            .collect::<Option<Vec<_>>>()
+            .map(|chunks| chunks.concat()),
+        Err(_) => metadatum.as_text().ok(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CIP-68
+// ---------------------------------------------------------------------------
+
+fn cip68_label(asset_name: &str) -> Option<u32> {
+    if asset_name.len() < 8 {
+        return None;
+    }
+    match &asset_name[..8] {
+        CIP68_REFERENCE_PREFIX => Some(100),
+        CIP68_NFT_PREFIX => Some(222),
+        CIP68_FT_PREFIX => Some(333),
+        CIP68_RFT_PREFIX => Some(444),
+        _ => None,
+    }
+}
+
+fn check_cip68(
+    tx_body: &csl::TransactionBody,
+    minted: &MintedAssets,
+    warnings: &mut Vec<(Phase1Warning, String)>,
+) {
+    for (policy_id, assets) in minted {
+        for (asset_name, quantity) in assets {
+            let label = match cip68_label(asset_name) {
+                Some(label) => label,
+                None => continue,
+            };
+            let suffix = &asset_name[8..];
+            let mut push = |message: String, location: String| {
+                warnings.push((
+                    Phase1Warning::Cip68TokenMismatch {
+                        policy_id: policy_id.clone(),
+                        asset_name: asset_name.clone(),
+                        message,
+                    },
+                    location,
+                ));
+            };
+
+            if label == 100 {
+                let has_user_token = [CIP68_NFT_PREFIX, CIP68_FT_PREFIX, CIP68_RFT_PREFIX]
+                    .iter()
+                    .any(|prefix| assets.contains_key(&format!("{}{}", prefix, suffix)));
+                if !has_user_token {
+                    push(
+                        "Reference token (100) is minted without a matching user token (222/333/444)"
+                            .to_string(),
+                        "transaction.body.mint".to_string(),
+                    );
+                }
+                if *quantity != 1 {
+                    push(
+                        format!(
+                            "Reference token (100) must be minted once, found {}",
+                            quantity
+                        ),
+                        "transaction.body.mint".to_string(),
+                    );
+                }
+                if let Some((message, location)) =
+                    check_cip68_reference_output(tx_body, policy_id, asset_name)
+                {
+                    push(message, location);
+                }
+            } else {
+                let reference_name = format!("{}{}", CIP68_REFERENCE_PREFIX, suffix);
+                if !assets.contains_key(&reference_name) {
+                    push(
+                        format!(
+                            "User token ({}) is minted without its reference token (100) {} in this transaction",
+                            label, reference_name
+                        ),
+                        "transaction.body.mint".to_string(),
+                    );
+                }
+                if label == 222 && *quantity != 1 {
+                    push(
+                        format!(
+                            "NFT user token (222) must be minted once, found {}",
+                            quantity
+                        ),
+                        "transaction.body.mint".to_string(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The reference token must sit in an output with an inline datum
+/// `Constr 0 [metadata: map, version: int, extra: data]`.
+fn check_cip68_reference_output(
+    tx_body: &csl::TransactionBody,
+    policy_id: &str,
+    asset_name: &str,
+) -> Option<(String, String)> {
+    let policy = csl::ScriptHash::from_hex(policy_id).ok()?;
+    let name = csl::AssetName::new(hex::decode(asset_name).ok()?).ok()?;
+    let outputs = tx_body.outputs();
+    let output_index = (0..outputs.len()).find(|i| {
+        outputs
+            .get(*i)
+            .amount()
+            .multiasset()
+            .map(|multiasset| !multiasset.get_asset(&policy, &name).is_zero())
+            .unwrap_or(false)
+    });
+    let output_index = match output_index {
+        Some(output_index) => output_index,
+        None => {
+            return Some((
+                "Reference token (100) is not sent to any output".to_string(),
+                "transaction.body.outputs".to_string(),
+            ))
+        }
+    };
+    let location = format!("transaction.body.outputs.{}", output_index);
+
+    let datum = match outputs.get(output_index).plutus_data() {
+        Some(datum) => datum,
+        None => {
+            return Some((
+                "Output holding the reference token (100) has no inline datum".to_string(),
+                location,
+            ))
+        }
+    };
+    let is_valid_datum = datum
+        .as_constr_plutus_data()
+        .filter(|constr| constr.alternative().is_zero())
+        .map(|constr| {
+            let fields = constr.data();
+            fields.len() >= 2
+                && fields.get(0).as_map().is_some()
+                && fields.get(1).as_integer().is_some()
+        })
+        .unwrap_or(false);
+    if !is_valid_datum {
+        return Some((
+            "Reference token datum must be Constr 0 [metadata map, version int, extra]".to_string(),
+            location,
+        ));
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// CIP-27
+// ---------------------------------------------------------------------------
+
+fn check_cip27(
+    metadatum: &csl::TransactionMetadatum,
+    minted: &MintedAssets,
+    warnings: &mut Vec<(Phase1Warning, String)>,
+) {
+    let location = format!("transaction.auxiliary_data.metadata.{}", CIP27_LABEL);
+    let mut push = |message: &str| {
+        warnings.push((
+            Phase1Warning::Cip27RoyaltiesInvalid {
+                message: message.to_string(),
+            },
+            location.clone(),
+        ));
+    };
+
+    let royalties = match metadatum.as_map() {
+        Ok(royalties) => royalties,
+        Err(_) => {
+            push("CIP-27 metadata must be a map with \"rate\" and \"addr\"");
+            return;
+        }
+    };
+
+    // "pct" is the deprecated name of "rate"
+    let rate = royalties
+        .get_str("rate")
+        .or_else(|_| royalties.get_str("pct"))
+        .ok()
+        .and_then(|rate| rate.as_text().ok())
+        .and_then(|rate| rate.parse::<f64>().ok());
+    match rate {
+        Some(rate) if (0.0..=1.0).contains(&rate) => {}
+        Some(_) => push("Royalty \"rate\" must be between 0 and 1"),
+        None => push("Royalty \"rate\" is missing or is not a decimal string (e.g. \"0.05\")"),
+    }
+
+    let address = royalties
+        .get_str("addr")
+        .ok()
+        .and_then(|address| chunked_text(&address));
+    match address {
+        Some(address) => {
+            if string_to_csl_address(&address).is_err() {
+                push("Royalty \"addr\" is not a valid address");
+            }
+        }
+        None => push("Royalty \"addr\" is missing or is not a string or a list of strings"),
+    }
+
+    // The royalty token is the token with an empty asset name under the policy
+    let mints_royalty_token = minted.values().any(|assets| assets.contains_key(""));
+    if !mints_royalty_token {
+        push("CIP-27 metadata is present, but no royalty token (empty asset name) is minted");
+    }
+}
@@ -2,7 +2,8 @@ use crate::{
     common::TxInput,
     js_error::JsError,
     validators::{
-        helpers::normalize_script_ref,
+        common::TransactionEra,
+        helpers::{normalize_script_ref, stability_window_network},
         input_contexts::{UtxoInputContext, ValidationInputContext},
         phase_1::errors::{
             Phase1Error, Phase1Warning, ValidationPhase1Error, ValidationPhase1Warning,
@@ -24,6 +25,8 @@ pub struct TransactionLimitsValidator<'a> {
     pub max_ref_scripts_size: u64,
     pub current_slot: u64,
     pub validity_interval: (Option<u64>, Option<u64>), // (start, end)
+    pub has_plutus_scripts: bool,
+    pub forecast_horizon: u64,
     pub inputs_sorted: bool,
     pub inputs_count: usize,
     pub ref_inputs: Vec<TxInput>,
@@ -80,6 +83,9 @@ impl<'a> TransactionLimitsValidator<'a> {
 
         let current_slot = validation_input_context.slot;
         let validity_interval = get_validity_interval(tx_body);
+        let has_plutus_scripts = redeemers.len() > 0;
+        let forecast_horizon = current_slot
+            .saturating_add(stability_window_network(&validation_input_context.network_type));
 
         let inputs_sorted = check_inputs_sorted(tx_body);
         let inputs_count = tx_body.inputs().len();
@@ -123,6 +129,8 @@ impl<'a> TransactionLimitsValidator<'a> {
             max_ref_scripts_size: MAX_REFERENCE_SCRIPTS_SIZE,
             current_slot,
             validity_interval,
+            has_plutus_scripts,
+            forecast_horizon,
             inputs_sorted,
             inputs_count,
             ref_inputs,
@@ -166,7 +174,10 @@ impl<'a> TransactionLimitsValidator<'a> {
             ));
         }
 
-        if self.actual_ref_scripts_size > self.max_ref_scripts_size {
+        // The reference scripts size limit was introduced in Conway
+        if self.validation_input_context.transaction_era() >= TransactionEra::Conway
+            && self.actual_ref_scripts_size > self.max_ref_scripts_size
+        {
             errors.push(ValidationPhase1Error::new(
                 Phase1Error::RefScriptsSizeTooBig {
                     actual_size: self.actual_ref_scripts_size,
@@ -201,6 +212,29 @@ impl<'a> TransactionLimitsValidator<'a> {
             ));
         }
 
+        // Script contexts carry the validity interval as POSIX time, so the ledger
+        // refuses bounds it cannot translate with the current epoch info
+        if self.has_plutus_scripts {
+            let (validity_start, ttl) = self.validity_interval;
+            for (slot, location) in [
+                (validity_start, "transaction.body.validity_start_interval"),
+                (ttl, "transaction.body.ttl"),
+            ] {
+                if let Some(slot) = slot {
+                    if slot > self.forecast_horizon {
+                        errors.push(ValidationPhase1Error::new(
+                            Phase1Error::OutsideForecast {
+                                slot,
+                                current_slot: self.current_slot,
+                                forecast_horizon: self.forecast_horizon,
+                            },
+                            location.to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+
         for input in self.ref_inputs.iter() {
             let utxo = self
                 .validation_input_context
@@ -274,7 +308,9 @@ fn collect_utxos<'a>(
     let inputs = tx_body.inputs();
     let mut input_utxos: HashSet<&'a UtxoInputContext> = inputs
         .into_iter()
-        .map(|input| validation_input_context.find_utxo(input.to_hex(), input.index()))
+        .map(|input| {
+            validation_input_context.find_utxo(input.transaction_id().to_hex(), input.index())
+        })
         .filter_map(|utxo| utxo)
         .collect();
 
@@ -282,7 +318,9 @@ fn collect_utxos<'a>(
     let ref_utxos: Vec<&'a UtxoInputContext> = ref_inputs
         .unwrap_or(csl::TransactionInputs::new())
         .into_iter()
-        .map(|input| validation_input_context.find_utxo(input.to_hex(), input.index()))
+        .map(|input| {
+            validation_input_context.find_utxo(input.transaction_id().to_hex(), input.index())
+        })
         .filter_map(|utxo| utxo)
         .collect();
 
@@ -0,0 +1,436 @@
+use crate::validators::{
+    helpers::network_type_to_network_id,
+    input_contexts::ValidationInputContext,
+    phase_1::errors::{Phase1Warning, ValidationPhase1Warning},
+    validation_result::ValidationResult,
+};
+use cardano_serialization_lib as csl;
+use cryptoxide::hashing::blake2b_256;
+
+pub const VOTING_REGISTRATION_LABEL: u64 = 61284;
+pub const VOTING_REGISTRATION_WITNESS_LABEL: u64 = 61285;
+
+const VOTING_KEY_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VotingRegistrationStandard {
+    /// Single voting key, reward address as the payment address
+    Cip15,
+    /// Weighted delegations, any Shelley payment address and a voting purpose
+    Cip36,
+}
+
+impl VotingRegistrationStandard {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VotingRegistrationStandard::Cip15 => "CIP-15",
+            VotingRegistrationStandard::Cip36 => "CIP-36",
+        }
+    }
+}
+
+/// Catalyst voting registration parsed from metadata labels 61284/61285.
+/// Fields that can't be parsed are left empty and reported in `violations`.
+#[derive(Debug, Clone)]
+pub struct VotingRegistration {
+    pub standard: VotingRegistrationStandard,
+    /// (voting key, weight); CIP-15 registrations have a single key with weight 1
+    pub delegations: Vec<(Vec<u8>, u32)>,
+    pub stake_public_key: Option<Vec<u8>>,
+    pub payment_address: Option<Vec<u8>>,
+    pub nonce: Option<u64>,
+    pub voting_purpose: u64,
+    pub signature: Option<Vec<u8>>,
+    /// blake2b-256 of `{ 61284: registration }`, the message signed by the stake key
+    pub registration_hash: Vec<u8>,
+    /// (message, location)
+    pub violations: Vec<(String, String)>,
+}
+
+impl VotingRegistration {
+    /// Returns None when the metadata has neither of the voting registration labels
+    pub fn from_metadata(metadata: &csl::GeneralTransactionMetadata) -> Option<Self> {
+        let registration_label = csl::BigNum::from(VOTING_REGISTRATION_LABEL);
+        let registration = metadata.get(&registration_label);
+        let witness = metadata.get(&csl::BigNum::from(VOTING_REGISTRATION_WITNESS_LABEL));
+        if registration.is_none() && witness.is_none() {
+            return None;
+        }
+
+        let location = metadata_location(VOTING_REGISTRATION_LABEL);
+        let mut result = Self {
+            standard: VotingRegistrationStandard::Cip15,
+            delegations: Vec::new(),
+            stake_public_key: None,
+            payment_address: None,
+            nonce: None,
+            voting_purpose: 0,
+            signature: None,
+            registration_hash: Vec::new(),
+            violations: Vec::new(),
+        };
+
+        match &registration {
+            Some(registration) => {
+                let mut registration_metadata = csl::GeneralTransactionMetadata::new();
+                registration_metadata.insert(&registration_label, registration);
+                result.registration_hash = blake2b_256(&registration_metadata.to_bytes()).to_vec();
+                result.parse_registration(registration, &location);
+            }
+            None => result.violations.push((
+                "Registration witness (61285) is present without a registration (61284)"
+                    .to_string(),
+                location,
+            )),
+        }
+
+        let witness_location = metadata_location(VOTING_REGISTRATION_WITNESS_LABEL);
+        match &witness {
+            Some(witness) => {
+                result.signature =
+                    result.bytes_field(witness, 1, SIGNATURE_SIZE, &witness_location);
+            }
+            None => result.violations.push((
+                "Registration (61284) is present without a registration witness (61285)"
+                    .to_string(),
+                witness_location,
+            )),
+        }
+
+        Some(result)
+    }
+
+    fn parse_registration(&mut self, registration: &csl::TransactionMetadatum, location: &str) {
+        if registration.as_map().is_err() {
+            self.violations.push((
+                "Registration must be a map".to_string(),
+                location.to_string(),
+            ));
+            return;
+        }
+
+        // Key 1 is a single voting key (CIP-15 or legacy CIP-36) or a list of
+        // [voting key, weight] (CIP-36)
+        let delegations_location = format!("{}.1", location);
+        match get_field(registration, 1) {
+            Some(field) => match field.as_list() {
+                Ok(delegations) => {
+                    self.standard = VotingRegistrationStandard::Cip36;
+                    self.parse_delegations(&delegations, &delegations_location);
+                }
+                Err(_) => {
+                    if let Some(voting_key) =
+                        self.bytes_field(registration, 1, VOTING_KEY_SIZE, location)
+                    {
+                        self.delegations.push((voting_key, 1));
+                    }
+                }
+            },
+            None => self.violations.push((
+                "Missing voting key or delegations (key 1)".to_string(),
+                delegations_location,
+            )),
+        }
+
+        self.stake_public_key = self.bytes_field(registration, 2, VOTING_KEY_SIZE, location);
+        self.payment_address = self.bytes_field(registration, 3, 0, location);
+        self.nonce = self.uint_field(registration, 4, location);
+        // CIP-36 registrations with a single key are told apart by the voting purpose or by a
+        // payment address CIP-15 doesn't allow
+        if get_field(registration, 5).is_some() {
+            self.standard = VotingRegistrationStandard::Cip36;
+            self.voting_purpose = self.uint_field(registration, 5, location).unwrap_or(0);
+        }
+        let is_cip36_payment_address = self
+            .payment_address
+            .as_ref()
+            .and_then(|address| csl::Address::from_bytes(address.clone()).ok())
+            .map(|address| {
+                matches!(
+                    address.kind(),
+                    csl::AddressKind::Base
+                        | csl::AddressKind::Enterprise
+                        | csl::AddressKind::Pointer
+                )
+            })
+            .unwrap_or(false);
+        if is_cip36_payment_address {
+            self.standard = VotingRegistrationStandard::Cip36;
+        }
+    }
+
+    fn parse_delegations(&mut self, delegations: &csl::MetadataList, location: &str) {
+        if delegations.len() == 0 {
+            self.violations.push((
+                "Delegations list is empty".to_string(),
+                location.to_string(),
+            ));
+        }
+        for i in 0..delegations.len() {
+            let delegation_location = format!("{}.{}", location, i);
+            let delegation = match delegations.get(i).as_list() {
+                Ok(delegation) if delegation.len() == 2 => delegation,
+                _ => {
+                    self.violations.push((
+                        "Delegation must be a [voting key, weight] pair".to_string(),
+                        delegation_location,
+                    ));
+                    continue;
+                }
+            };
+            let voting_key = delegation.get(0).as_bytes().ok();
+            let weight =
+                metadatum_to_u64(&delegation.get(1)).filter(|weight| *weight <= u32::MAX as u64);
+            match (voting_key, weight) {
+                (Some(voting_key), Some(weight)) if voting_key.len() == VOTING_KEY_SIZE => {
+                    self.delegations.push((voting_key, weight as u32))
+                }
+                (Some(voting_key), Some(_)) => self.violations.push((
+                    format!(
+                        "Voting key is {} bytes, expected {}",
+                        voting_key.len(),
+                        VOTING_KEY_SIZE
+                    ),
+                    format!("{}.0", delegation_location),
+                )),
+                (None, _) => self.violations.push((
+                    "Voting key must be bytes".to_string(),
+                    format!("{}.0", delegation_location),
+                )),
+                (_, None) => self.violations.push((
+                    "Weight must be an unsigned 32-bit integer".to_string(),
+                    format!("{}.1", delegation_location),
+                )),
+            }
+        }
+    }
+
+    /// Reads a bytes field; `expected_size` 0 means any size
+    fn bytes_field(
+        &mut self,
+        map: &csl::TransactionMetadatum,
+        key: i32,
+        expected_size: usize,
+        location: &str,
+    ) -> Option<Vec<u8>> {
+        let field_location = format!("{}.{}", location, key);
+        let bytes = match get_field(map, key).map(|field| field.as_bytes()) {
+            Some(Ok(bytes)) => bytes,
+            Some(Err(_)) => {
+                self.violations
+                    .push((format!("Key {} must be bytes", key), field_location));
+                return None;
+            }
+            None => {
+                self.violations
+                    .push((format!("Missing required key {}", key), field_location));
+                return None;
+            }
+        };
+        if expected_size != 0 && bytes.len() != expected_size {
+            self.violations.push((
+                format!(
+                    "Key {} is {} bytes, expected {}",
+                    key,
+                    bytes.len(),
+                    expected_size
+                ),
+                field_location,
+            ));
+            return None;
+        }
+        Some(bytes)
+    }
+
+    fn uint_field(
+        &mut self,
+        map: &csl::TransactionMetadatum,
+        key: i32,
+        location: &str,
+    ) -> Option<u64> {
+        let field_location = format!("{}.{}", location, key);
+        match get_field(map, key) {
+            Some(field) => {
+                let value = metadatum_to_u64(&field);
+                if value.is_none() {
+                    self.violations.push((
+                        format!("Key {} must be an unsigned integer", key),
+                        field_location,
+                    ));
+                }
+                value
+            }
+            None => {
+                self.violations
+                    .push((format!("Missing required key {}", key), field_location));
+                None
+            }
+        }
+    }
+
+    /// Whether the witness signature verifies against the stake key.
+    /// None when either of them is missing or malformed.
+    pub fn is_signature_valid(&self) -> Option<bool> {
+        let stake_public_key = csl::PublicKey::from_bytes(self.stake_public_key.as_ref()?).ok()?;
+        let signature = csl::Ed25519Signature::from_bytes(self.signature.clone()?).ok()?;
+        Some(stake_public_key.verify(&self.registration_hash, &signature))
+    }
+}
+
+/// Validates Catalyst voting registrations (CIP-15 and CIP-36) carried in the metadata.
+/// The ledger accepts malformed registrations, so every finding is reported as a warning.
+pub struct VotingRegistrationValidator {
+    pub registration: Option<VotingRegistration>,
+    pub expected_network_id: u8,
+}
+
+impl VotingRegistrationValidator {
+    pub fn new(
+        auxiliary_data: Option<&csl::AuxiliaryData>,
+        validation_input_context: &ValidationInputContext,
+    ) -> Self {
+        let registration = auxiliary_data
+            .and_then(|auxiliary_data| auxiliary_data.metadata())
+            .and_then(|metadata| VotingRegistration::from_metadata(&metadata));
+        Self {
+            registration,
+            expected_network_id: network_type_to_network_id(&validation_input_context.network_type),
+        }
+    }
+
+    pub fn validate(&self) -> ValidationResult {
+        let mut warnings = Vec::new();
+        let registration = match &self.registration {
+            Some(registration) => registration,
+            None => return ValidationResult::new_phase_1(Vec::new(), warnings),
+        };
+        let standard = registration.standard.name().to_string();
+        let location = metadata_location(VOTING_REGISTRATION_LABEL);
+
+        for (message, location) in &registration.violations {
+            warnings.push(ValidationPhase1Warning::new(
+                Phase1Warning::InvalidVotingRegistration {
+                    standard: standard.clone(),
+                    message: message.clone(),
+                },
+                location.clone(),
+            ));
+        }
+
+        if registration.standard == VotingRegistrationStandard::Cip36
+            && !registration.delegations.is_empty()
+            && registration
+                .delegations
+                .iter()
+                .all(|(_, weight)| *weight == 0)
+        {
+            warnings.push(ValidationPhase1Warning::new(
+                Phase1Warning::InvalidVotingRegistration {
+                    standard: standard.clone(),
+                    message: "All delegation weights are zero, so no voting power is delegated"
+                        .to_string(),
+                },
+                format!("{}.1", location),
+            ));
+        }
+
+        if let Some(payment_address) = &registration.payment_address {
+            self.check_payment_address(registration, payment_address, &location, &mut warnings);
+        }
+
+        if registration.is_signature_valid() == Some(false) {
+            warnings.push(ValidationPhase1Warning::new(
+                Phase1Warning::VotingRegistrationInvalidSignature {
+                    stake_public_key: hex::encode(
+                        registration
+                            .stake_public_key
+                            .as_ref()
+                            .unwrap_or(&Vec::new()),
+                    ),
+                },
+                metadata_location(VOTING_REGISTRATION_WITNESS_LABEL),
+            ));
+        }
+
+        ValidationResult::new_phase_1(Vec::new(), warnings)
+    }
+
+    fn check_payment_address(
+        &self,
+        registration: &VotingRegistration,
+        payment_address: &[u8],
+        location: &str,
+        warnings: &mut Vec<ValidationPhase1Warning>,
+    ) {
+        let standard = registration.standard.name().to_string();
+        let address_location = format!("{}.3", location);
+        let address = match csl::Address::from_bytes(payment_address.to_vec()) {
+            Ok(address) => address,
+            Err(_) => {
+                warnings.push(ValidationPhase1Warning::new(
+                    Phase1Warning::InvalidVotingRegistration {
+                        standard,
+                        message: "Payment address (key 3) can't be decoded".to_string(),
+                    },
+                    address_location,
+                ));
+                return;
+            }
+        };
+
+        let address_kind = address.kind();
+        let is_allowed = match registration.standard {
+            VotingRegistrationStandard::Cip15 => address_kind == csl::AddressKind::Reward,
+            VotingRegistrationStandard::Cip36 => {
+                address_kind != csl::AddressKind::Byron
+                    && address_kind != csl::AddressKind::Malformed
+            }
+        };
+        if !is_allowed {
+            let expected = match registration.standard {
+                VotingRegistrationStandard::Cip15 => "a reward address",
+                VotingRegistrationStandard::Cip36 => "a Shelley address",
+            };
+            warnings.push(ValidationPhase1Warning::new(
+                Phase1Warning::InvalidVotingRegistration {
+                    standard,
+                    message: format!(
+                        "Payment address (key 3) is a {:?} address, expected {}",
+                        address_kind, expected
+                    ),
+                },
+                address_location.clone(),
+            ));
+        }
+
+        if let Ok(network_id) = address.network_id() {
+            if network_id != self.expected_network_id {
+                warnings.push(ValidationPhase1Warning::new(
+                    Phase1Warning::VotingRegistrationWrongNetwork {
+                        payment_address: address
+                            .to_bech32(None)
+                            .unwrap_or_else(|_| address.to_hex()),
+                        expected_network: self.expected_network_id,
+                    },
+                    address_location,
+                ));
+            }
+        }
+    }
+}
+
+fn metadata_location(label: u64) -> String {
+    format!("transaction.auxiliary_data.metadata.{}", label)
+}
+
+fn get_field(map: &csl::TransactionMetadatum, key: i32) -> Option<csl::TransactionMetadatum> {
+    map.as_map()
+        .ok()?
+        .get(&csl::TransactionMetadatum::new_int(&csl::Int::new_i32(key)))
+        .ok()
+}
+
+fn metadatum_to_u64(metadatum: &csl::TransactionMetadatum) -> Option<u64> {
+    metadatum.as_int().ok()?.as_positive().map(u64::from)
+}
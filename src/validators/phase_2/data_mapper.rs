@@ -3,8 +3,9 @@ use std::convert::{TryFrom, TryInto};
 use cardano_serialization_lib::Address;
 use pallas_codec::utils::{Bytes, CborWrap, NonEmptyKeyValuePairs, PositiveCoin};
 use uplc::{tx::ResolvedInput, TransactionInput, Hash};
+use pallas_traverse::MultiEraTx;
 use pallas_primitives::{conway::{
-    AssetName, Coin, DatumOption, PlutusData, PolicyId,
+    AssetName, Coin, DatumOption, MintedTx, PlutusData, PolicyId,
     PostAlonzoTransactionOutput, ScriptRef, TransactionOutput, Value,
 }, DatumHash, Fragment};
 use crate::js_error::JsError;
@@ -13,6 +14,21 @@ use crate::validators::helpers::normalize_script_ref_raw;
 use crate::validators::input_contexts::UtxoInputContext;
 use cardano_serialization_lib as csl;
 
+/// Decodes a transaction for the script evaluator. Alonzo and Babbage transactions decode with
+/// the Conway codec as well, the evaluator builds the script context matching each script's
+/// language. `None` is an older era transaction without redeemers, it has no scripts to run.
+pub fn decode_conway_tx(tx_bytes: &[u8]) -> Result<Option<MintedTx<'_>>, JsError> {
+    let mtx = MultiEraTx::decode(tx_bytes).map_err(|e| JsError::new(&e.to_string()))?;
+    match mtx {
+        MultiEraTx::Conway(tx) => Ok(Some(tx.into_owned())),
+        mtx if mtx.redeemers().is_empty() => Ok(None),
+        mtx => Err(JsError::new(&format!(
+            "Scripts of {} era transactions that can't be decoded with the Conway codec are not supported",
+            mtx.era()
+        ))),
+    }
+}
+
 pub fn to_pallas_cost_modesl(cost_models: &CostModels) -> pallas_primitives::conway::CostModels {
     pallas_primitives::conway::CostModels {
         plutus_v1: cost_models.plutus_v1.clone().map(|v| v.into_iter().map(|i| i as i64).collect::<Vec<i64>>()),
@@ -21,41 +37,43 @@ pub fn to_pallas_cost_modesl(cost_models: &CostModels) -> pallas_primitives::con
     }
 }
 
-pub fn to_pallas_utxos(utxos: &Vec<UtxoInputContext>) -> Result<Vec<ResolvedInput>, JsError> {
-    let mut resolved_inputs = Vec::new();
-    for utxo in utxos {
-        let utxo = &utxo.utxo;
-        let tx_hash: [u8; 32] = hex::decode(&utxo.input.tx_hash)
-            .map_err(|err| JsError::new(&format!("Invalid tx hash found: {}", err)))?
-            .try_into()
-            .map_err(|_e| JsError::new("Invalid tx hash length found"))?;
+pub fn to_pallas_utxos(utxos: &[UtxoInputContext]) -> Result<Vec<ResolvedInput>, JsError> {
+    utxos
+        .iter()
+        .map(|utxo| to_pallas_utxo(utxo).map_err(|err| JsError::new(&err)))
+        .collect()
+}
 
-        let resolved_input = ResolvedInput {
-            input: TransactionInput {
-                transaction_id: Hash::from(tx_hash),
-                index: utxo.input.output_index.into(),
-            },
-            output: TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
-                address: Bytes::from(Address::from_bech32(&utxo.output.address).map_err(
-                    |err| JsError::new(&format!("Invalid address found: {:?}", err)),
-                )?.to_bytes()),
-                value: to_pallas_value(&utxo.output.amount)?,
-                datum_option: to_pallas_datum(&utxo.output)?,
-                script_ref: to_pallas_script_ref(&utxo.output.script_ref)?,
-            }),
-        };
-        resolved_inputs.push(resolved_input);
-    }
-    Ok(resolved_inputs)
+pub fn to_pallas_utxo(utxo: &UtxoInputContext) -> Result<ResolvedInput, String> {
+    let utxo = &utxo.utxo;
+    let tx_hash: [u8; 32] = hex::decode(&utxo.input.tx_hash)
+        .map_err(|err| format!("Invalid tx hash found: {}", err))?
+        .try_into()
+        .map_err(|_e| "Invalid tx hash length found".to_string())?;
+
+    Ok(ResolvedInput {
+        input: TransactionInput {
+            transaction_id: Hash::from(tx_hash),
+            index: utxo.input.output_index.into(),
+        },
+        output: TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+            address: Bytes::from(Address::from_bech32(&utxo.output.address).map_err(
+                |err| format!("Invalid address found: {:?}", err),
+            )?.to_bytes()),
+            value: to_pallas_value(&utxo.output.amount)?,
+            datum_option: to_pallas_datum(&utxo.output)?,
+            script_ref: to_pallas_script_ref(&utxo.output.script_ref)?,
+        }),
+    })
 }
 
 pub fn to_pallas_script_ref(
     script_ref: &Option<String>,
-) -> Result<Option<CborWrap<ScriptRef>>, JsError> {
+) -> Result<Option<CborWrap<ScriptRef>>, String> {
     if let Some(script_ref) = script_ref {
-        let normalized = normalize_script_ref_raw(script_ref).map_err(|err| JsError::new(&format!("Invalid script ref found: {}", err)))?;
+        let normalized = normalize_script_ref_raw(script_ref).map_err(|err| format!("Invalid script ref found: {}", err))?;
         let pallas_script = CborWrap::<ScriptRef>::decode_fragment(&normalized)
-            .map_err(|err| JsError::new(&format!("Invalid script found: {}", err)))?;
+            .map_err(|err| format!("Invalid script found: {}", err))?;
 
         Ok(Some(pallas_script))
     } else {
@@ -63,23 +81,23 @@ pub fn to_pallas_script_ref(
     }
 }
 
-pub fn to_pallas_datum(utxo_output: &TxOutput) -> Result<Option<DatumOption>, JsError> {
+pub fn to_pallas_datum(utxo_output: &TxOutput) -> Result<Option<DatumOption>, String> {
     if let Some(inline_datum) = &utxo_output.plutus_data {
         if let Some(plutus_data) = try_decode_from_json(inline_datum) {
             return Ok(Some(DatumOption::Data(CborWrap(plutus_data))));
         }
         let plutus_data_bytes = hex::decode(inline_datum)
-            .map_err(|err| JsError::new(&format!("Invalid plutus data found: {}", err)))?;
+            .map_err(|err| format!("Invalid plutus data found: {}", err))?;
         let datum = CborWrap(
             PlutusData::decode_fragment(&plutus_data_bytes)
-                .map_err(|_e| JsError::new("Invalid plutus data found"))?,
+                .map_err(|_e| "Invalid plutus data found".to_string())?,
         );
         Ok(Some(DatumOption::Data(datum)))
     } else if let Some(datum_hash) = &utxo_output.data_hash {
         let datum_hash_bytes: [u8; 32] = hex::decode(datum_hash)
-            .map_err(|err| JsError::new(&format!("Invalid datum hash found: {}", err)))?
+            .map_err(|err| format!("Invalid datum hash found: {}", err))?
             .try_into()
-            .map_err(|_e| JsError::new("Invalid byte length of datum hash found"))?;
+            .map_err(|_e| "Invalid byte length of datum hash found".to_string())?;
         Ok(Some(DatumOption::Hash(DatumHash::from(datum_hash_bytes))))
     } else {
         Ok(None)
@@ -87,34 +105,33 @@ pub fn to_pallas_datum(utxo_output: &TxOutput) -> Result<Option<DatumOption>, Js
 }
 
 pub fn try_decode_from_json(json: &str) -> Option<PlutusData> {
-    csl::PlutusData::from_json(json, csl::PlutusDatumSchema::DetailedSchema)
-        .map_err(|_| JsError::new("Invalid plutus data found"))
-        .and_then(|plutus_data| {
-            PlutusData::decode_fragment(&plutus_data.to_bytes())
-                .map_err(|_| JsError::new("Invalid plutus data found"))
-        })
-        .ok()
+    let plutus_data = csl::PlutusData::from_json(json, csl::PlutusDatumSchema::DetailedSchema).ok()?;
+    PlutusData::decode_fragment(&plutus_data.to_bytes()).ok()
 }
 
-pub fn to_pallas_value(assets: &Vec<Asset>) -> Result<Value, JsError> {
+pub fn to_pallas_value(assets: &Vec<Asset>) -> Result<Value, String> {
     if assets.len() == 1 {
         match assets[0].unit.as_str() {
-            "lovelace" => Ok(Value::Coin(assets[0].quantity.parse::<u64>().unwrap())),
-            _ => Err(JsError::new(&"Invalid value")),
+            "lovelace" => Ok(Value::Coin(parse_quantity(&assets[0].quantity)?)),
+            _ => Err("Invalid value".to_string()),
         }
     } else {
         to_pallas_multi_asset_value(assets)
     }
 }
 
-pub fn to_pallas_multi_asset_value(assets: &Vec<Asset>) -> Result<Value, JsError> {
+pub fn to_pallas_multi_asset_value(assets: &Vec<Asset>) -> Result<Value, String> {
     let mut coins: Coin = 0;
     let mut asset_mapping: HashMap<String, Vec<(String, String)>> = HashMap::new();
     for asset in assets {
         if asset.unit == "lovelace" || asset.unit.is_empty() {
-            coins = asset.quantity.parse::<u64>().unwrap();
+            coins = parse_quantity(&asset.quantity)?;
         } else {
             let asset_unit = &asset.unit;
+            // The unit is the hex policy id followed by the hex asset name
+            if asset_unit.len() < 56 || !asset_unit.is_char_boundary(56) {
+                return Err(format!("Invalid asset unit found: {}", asset_unit));
+            }
             let (policy_id, asset_name) = asset_unit.split_at(56);
             asset_mapping
                 .entry(policy_id.to_string())
@@ -126,9 +143,9 @@ pub fn to_pallas_multi_asset_value(assets: &Vec<Asset>) -> Result<Value, JsError
     let mut multi_asset = Vec::new();
     for (policy_id, asset_list) in &asset_mapping {
         let policy_id_bytes: [u8; 28] = hex::decode(policy_id)
-            .map_err(|err| JsError::new(&format!("Invalid policy id found: {}", err)))?
+            .map_err(|err| format!("Invalid policy id found: {}", err))?
             .try_into()
-            .map_err(|_e| JsError::new("Invalid length policy id found"))?;
+            .map_err(|_e| "Invalid length policy id found".to_string())?;
 
         let policy_id = PolicyId::from(policy_id_bytes);
         let mut mapped_assets = Vec::new();
@@ -136,15 +153,20 @@ pub fn to_pallas_multi_asset_value(assets: &Vec<Asset>) -> Result<Value, JsError
             let (asset_name, asset_quantity) = asset;
             let asset_name_bytes =
                 AssetName::from(hex::decode(asset_name).map_err(|err| {
-                    JsError::new(&format!("Invalid asset name found: {}", err))
+                    format!("Invalid asset name found: {}", err)
                 })?);
-            mapped_assets.push((
-                asset_name_bytes,
-                PositiveCoin::try_from(asset_quantity.parse::<u64>().unwrap()).unwrap(),
-            ));
+            let quantity = PositiveCoin::try_from(parse_quantity(asset_quantity)?)
+                .map_err(|_e| format!("Asset quantity must be positive: {}", asset_quantity))?;
+            mapped_assets.push((asset_name_bytes, quantity));
         }
         multi_asset.push((policy_id, NonEmptyKeyValuePairs::Def(mapped_assets)));
     }
     let pallas_multi_asset = NonEmptyKeyValuePairs::Def(multi_asset);
     Ok(Value::Multiasset(coins, pallas_multi_asset))
-}
\ No newline at end of file
+}
+
+fn parse_quantity(quantity: &str) -> Result<u64, String> {
+    quantity
+        .parse::<u64>()
+        .map_err(|err| format!("Invalid quantity found: {} ({})", quantity, err))
+}
@@ -80,6 +80,15 @@ pub enum Phase2Error {
     ScriptDecodeError { error: String },
     BuildTxContextError { error: String },
     MissingScriptForRedeemer { error: String },
+    /// The scripts together need more execution units than a transaction may use
+    TxExecutionBudgetTooBig {
+        required_budget: ExUnits,
+        max_budget: ExUnits,
+    },
+    ScriptExceedsTxMaxBudget {
+        actual_budget: ExUnits,
+        max_budget: ExUnits,
+    },
 }
 
 impl Phase2Error {
@@ -112,6 +121,18 @@ impl Phase2Error {
             Phase2Error::MissingScriptForRedeemer { error } => {
                 format!("Missing script for redeemer: {}", error)
             }
+            Phase2Error::TxExecutionBudgetTooBig { required_budget, max_budget } => {
+                format!(
+                    "Scripts need more execution units than the transaction maximum. Required: {:?}, Max: {:?}",
+                    required_budget, max_budget
+                )
+            }
+            Phase2Error::ScriptExceedsTxMaxBudget { actual_budget, max_budget } => {
+                format!(
+                    "Script exceeds the tx maximum execution units. Actual: {:?}, Max: {:?}",
+                    actual_budget, max_budget
+                )
+            }
         }
     }
 }
@@ -1,8 +1,9 @@
 use pallas_codec::utils::Bytes;
+use std::convert::TryFrom;
 use pallas_primitives::conway::{CostModel, CostModels, Language, MintedTx, Redeemer, RedeemerTag};
 use uplc::{
     ast::{FakeNamedDeBruijn, NamedDeBruijn, Program},
-    machine::cost_model::ExBudget,
+    machine::{cost_model::ExBudget, Error as MachineError},
     tx::{
         script_context::{
             find_script, DataLookupTable, ScriptContext, ScriptVersion, TxInfo, TxInfoV1, TxInfoV2,
@@ -14,10 +15,14 @@ use uplc::{
     PlutusData,
 };
 
+use cardano_serialization_lib as csl;
+
 use crate::{
-    common::ExUnits,
+    common::{ExUnitPrices, ExUnits},
     validators::{
-        common::NetworkType, phase_2::errors::Phase2Error, validation_result::EvalRedeemerResult,
+        common::NetworkType,
+        phase_2::{errors::Phase2Error, profiler::Profiler},
+        validation_result::{EvalRedeemerResult, ExecutionProfile},
     },
 };
 
@@ -43,6 +48,7 @@ pub fn slot_config_network(network: &NetworkType) -> SlotConfig {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn eval_redeemer(
     tx: &MintedTx,
     utxos: &[ResolvedInput],
@@ -50,20 +56,25 @@ pub fn eval_redeemer(
     redeemer: &Redeemer,
     lookup_table: &DataLookupTable,
     cost_mdls_opt: Option<&CostModels>,
-    initial_budget: &ExBudget,
+    max_tx_execution_units: &ExUnits,
+    profile_prices: Option<&ExUnitPrices>,
 ) -> (EvalRedeemerResult, Option<Phase2Error>) {
+    #[allow(clippy::too_many_arguments)]
     fn do_eval_redeemer(
         cost_mdl_opt: Option<&CostModel>,
         initial_budget: &ExBudget,
+        max_budget: &ExBudget,
+        profile_prices: Option<&ExUnitPrices>,
+        tx_fee: u64,
         lang: &Language,
         datum: Option<PlutusData>,
         redeemer: &Redeemer,
         tx_info: TxInfo,
         program: Program<NamedDeBruijn>,
-    ) -> (EvalRedeemerResult, Option<Phase2Error>) {
+    ) -> Result<(EvalRedeemerResult, Option<Phase2Error>), Phase2Error> {
         let script_context = tx_info
             .into_script_context(redeemer, datum.as_ref())
-            .expect("couldn't create script context from transaction?");
+            .ok_or_else(|| redeemer_not_in_tx_info(redeemer))?;
 
         let program = match script_context {
             ScriptContext::V1V2 { .. } => if let Some(datum) = datum {
@@ -77,17 +88,79 @@ pub fn eval_redeemer(
             ScriptContext::V3 { .. } => program.apply_data(script_context.to_plutus_data()),
         };
 
-        let mut eval_result = if let Some(costs) = cost_mdl_opt {
-            program.eval_as(lang, costs, Some(initial_budget))
-        } else {
-            program.eval_version(ExBudget::max(), lang)
+        let profile = profile_prices.map(|prices| {
+            // Bounded like the uplc evaluations below: the declared budget, then the tx maximum
+            let mut profiler = Profiler::new(
+                lang,
+                cost_mdl_opt.map(|costs| costs.as_slice()),
+                ExBudget {
+                    mem: initial_budget.mem.max(max_budget.mem),
+                    cpu: initial_budget.cpu.max(max_budget.cpu),
+                },
+            );
+            // The evaluation outcome is the one of uplc below, only the costs are kept
+            let _ = profiler.run(program.term.clone());
+            let execution_fee = execution_fee(&redeemer.ex_units, prices);
+            ExecutionProfile {
+                builtins: profiler.builtins(),
+                machine_steps: profiler.machine_steps(),
+                execution_fee,
+                tx_fee_share: if tx_fee == 0 {
+                    0.0
+                } else {
+                    execution_fee as f64 / tx_fee as f64
+                },
+            }
+        });
+
+        let eval = |program: Program<NamedDeBruijn>, budget: &ExBudget| {
+            if let Some(costs) = cost_mdl_opt {
+                program.eval_as(lang, costs, Some(budget))
+            } else {
+                program.eval_version(*budget, lang)
+            }
         };
 
-        let cost = eval_result.cost();
-        let logs = eval_result.logs();
+        // Like the ledger, the script may only use the ex units declared by its redeemer
+        let mut eval_result = eval(program.clone(), initial_budget);
+        let mut cost = eval_result.cost();
+        let mut logs = eval_result.logs();
 
         let error = match eval_result.result() {
             Ok(_) => None,
+            Err(MachineError::OutOfExError(_)) => {
+                // Evaluate again with the transaction maximum to find out what the script needs
+                let mut max_budget_result = eval(program, max_budget);
+                cost = max_budget_result.cost();
+                logs = max_budget_result.logs();
+                match max_budget_result.result() {
+                    Ok(_) => Some(Phase2Error::NoEnoughBudget {
+                        expected_budget: ExUnits {
+                            mem: cost.mem as u64,
+                            steps: cost.cpu as u64,
+                        },
+                        actual_budget: ExUnits {
+                            mem: redeemer.ex_units.mem,
+                            steps: redeemer.ex_units.steps,
+                        },
+                    }),
+                    Err(MachineError::OutOfExError(_)) => {
+                        Some(Phase2Error::ScriptExceedsTxMaxBudget {
+                            actual_budget: ExUnits {
+                                mem: redeemer.ex_units.mem,
+                                steps: redeemer.ex_units.steps,
+                            },
+                            max_budget: ExUnits {
+                                mem: max_budget.mem as u64,
+                                steps: max_budget.cpu as u64,
+                            },
+                        })
+                    }
+                    Err(err) => Some(Phase2Error::MachineError {
+                        error: err.to_string(),
+                    }),
+                }
+            }
             Err(err) => Some(Phase2Error::MachineError {
                 error: err.to_string(),
             }),
@@ -107,11 +180,21 @@ pub fn eval_redeemer(
             success: error.as_ref().is_none(),
             error: error.as_ref().map(|e| e.to_string()),
             logs: logs,
+            profile,
         };
 
-        (new_redeemer, error)
+        Ok((new_redeemer, error))
     }
 
+    let initial_budget = &ExBudget {
+        mem: i64::try_from(redeemer.ex_units.mem).unwrap_or(i64::MAX),
+        cpu: i64::try_from(redeemer.ex_units.steps).unwrap_or(i64::MAX),
+    };
+    let max_budget = &ExBudget {
+        mem: i64::try_from(max_tx_execution_units.mem).unwrap_or(i64::MAX),
+        cpu: i64::try_from(max_tx_execution_units.steps).unwrap_or(i64::MAX),
+    };
+
     let program = |script: Bytes| {
         let mut buffer = Vec::new();
         Program::<FakeNamedDeBruijn>::from_cbor(&script, &mut buffer)
@@ -130,7 +213,7 @@ pub fn eval_redeemer(
                 Err(Phase2Error::NativeScriptIsReferencedByRedeemer)
             }
 
-            Ok((ScriptVersion::V1(script), datum)) => Ok(do_eval_redeemer(
+            Ok((ScriptVersion::V1(script), datum)) => do_eval_redeemer(
                 cost_mdls_opt
                     .map(|cost_mdls| {
                         cost_mdls
@@ -142,6 +225,9 @@ pub fn eval_redeemer(
                     })
                     .transpose()?,
                 initial_budget,
+                max_budget,
+                profile_prices,
+                tx.transaction_body.fee,
                 &Language::PlutusV1,
                 datum,
                 redeemer,
@@ -153,9 +239,9 @@ pub fn eval_redeemer(
                 program(script.0).map_err(|err| Phase2Error::ScriptDecodeError {
                     error: err.to_string(),
                 })?,
-            )),
+            ),
 
-            Ok((ScriptVersion::V2(script), datum)) => Ok(do_eval_redeemer(
+            Ok((ScriptVersion::V2(script), datum)) => do_eval_redeemer(
                 cost_mdls_opt
                     .map(|cost_mdls| {
                         cost_mdls
@@ -167,6 +253,9 @@ pub fn eval_redeemer(
                     })
                     .transpose()?,
                 initial_budget,
+                max_budget,
+                profile_prices,
+                tx.transaction_body.fee,
                 &Language::PlutusV2,
                 datum,
                 redeemer,
@@ -178,9 +267,9 @@ pub fn eval_redeemer(
                 program(script.0).map_err(|err| Phase2Error::ScriptDecodeError {
                     error: err.to_string(),
                 })?,
-            )),
+            ),
 
-            Ok((ScriptVersion::V3(script), datum)) => Ok(do_eval_redeemer(
+            Ok((ScriptVersion::V3(script), datum)) => do_eval_redeemer(
                 cost_mdls_opt
                     .map(|cost_mdls| {
                         cost_mdls
@@ -192,6 +281,9 @@ pub fn eval_redeemer(
                     })
                     .transpose()?,
                 initial_budget,
+                max_budget,
+                profile_prices,
+                tx.transaction_body.fee,
                 &Language::PlutusV3,
                 datum,
                 redeemer,
@@ -203,14 +295,60 @@ pub fn eval_redeemer(
                 program(script.0).map_err(|err| Phase2Error::ScriptDecodeError {
                     error: err.to_string(),
                 })?,
-            )),
+            ),
             Err(e) => Err(e),
         }
     })()
     .unwrap_or_else(|e| eval_redeemer_result(redeemer, e))
 }
 
-fn eval_redeemer_result(
+/// Language of the redeemer's script with the datum and the script context it is applied to
+pub(crate) fn script_arguments(
+    tx: &MintedTx,
+    utxos: &[ResolvedInput],
+    slot_config: &SlotConfig,
+    redeemer: &Redeemer,
+    lookup_table: &DataLookupTable,
+) -> Result<(Language, Option<PlutusData>, PlutusData), Phase2Error> {
+    let (script, datum) = find_script(redeemer, tx, utxos, lookup_table).map_err(|e| {
+        Phase2Error::MissingScriptForRedeemer {
+            error: e.to_string(),
+        }
+    })?;
+    let (language, tx_info) = match script {
+        ScriptVersion::Native(_) => return Err(Phase2Error::NativeScriptIsReferencedByRedeemer),
+        ScriptVersion::V1(_) => (
+            Language::PlutusV1,
+            TxInfoV1::from_transaction(tx, utxos, slot_config),
+        ),
+        ScriptVersion::V2(_) => (
+            Language::PlutusV2,
+            TxInfoV2::from_transaction(tx, utxos, slot_config),
+        ),
+        ScriptVersion::V3(_) => (
+            Language::PlutusV3,
+            TxInfoV3::from_transaction(tx, utxos, slot_config),
+        ),
+    };
+    let script_context = tx_info
+        .map_err(|err| Phase2Error::BuildTxContextError {
+            error: err.to_string(),
+        })?
+        .into_script_context(redeemer, datum.as_ref())
+        .ok_or_else(|| redeemer_not_in_tx_info(redeemer))?;
+    Ok((language, datum, script_context.to_plutus_data()))
+}
+
+fn redeemer_not_in_tx_info(redeemer: &Redeemer) -> Phase2Error {
+    Phase2Error::BuildTxContextError {
+        error: format!(
+            "Redeemer {:?}[{}] is not part of the transaction info",
+            redeemer.tag, redeemer.index
+        ),
+    }
+}
+
+pub(crate) fn eval_redeemer_result(
     redeemer: &Redeemer,
     error: Phase2Error,
 ) -> (EvalRedeemerResult, Option<Phase2Error>) {
@@ -225,11 +363,23 @@ fn eval_redeemer_result(
         success: false,
         error: Some(error.to_string()),
         logs: vec![],
+        profile: None,
     };
     (new_redeemer, Some(error))
 }
 
-fn language_to_string(language: &Language) -> String {
+/// Lovelace paid for the ex units, rounded up like the ledger does for the whole transaction
+fn execution_fee(ex_units: &pallas_primitives::conway::ExUnits, prices: &ExUnitPrices) -> u64 {
+    let ex_units = csl::ExUnits::new(
+        &csl::BigNum::from(ex_units.mem),
+        &csl::BigNum::from(ex_units.steps),
+    );
+    csl::calculate_ex_units_ceil_cost(&ex_units, &prices.to_csl())
+        .map(u64::from)
+        .unwrap_or(0)
+}
+
+pub(crate) fn language_to_string(language: &Language) -> String {
     match language {
         Language::PlutusV1 => "PlutusV1".to_string(),
         Language::PlutusV2 => "PlutusV2".to_string(),
@@ -237,7 +387,7 @@ fn language_to_string(language: &Language) -> String {
     }
 }
 
-fn map_tag_to_redeemer_tag(tag: &RedeemerTag) -> ValidatorRedeemerTag {
+pub(crate) fn map_tag_to_redeemer_tag(tag: &RedeemerTag) -> ValidatorRedeemerTag {
     match tag {
         RedeemerTag::Mint => ValidatorRedeemerTag::Mint,
         RedeemerTag::Spend => ValidatorRedeemerTag::Spend,
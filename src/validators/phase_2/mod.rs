@@ -1,5 +1,6 @@
 pub mod validation;
 pub mod errors;
 pub mod hints;
-mod data_mapper;
-mod eval_redeemer;
\ No newline at end of file
+pub(crate) mod data_mapper;
+pub(crate) mod eval_redeemer;
+mod profiler;
\ No newline at end of file
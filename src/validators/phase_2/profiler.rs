@@ -0,0 +1,415 @@
+//! CEK machine recording what each step kind and builtin function costs. uplc's `Machine`
+//! only exposes the remaining budget, so this is a copy of its evaluation loop from uplc 1.1.10
+//! and is run on its own when a redeemer profile is requested. It has to be updated together
+//! with the uplc dependency. Evaluation errors aren't reported,
+//! the result of the uplc evaluation is authoritative.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::rc::Rc;
+
+use pallas_primitives::conway::Language;
+use uplc::{
+    ast::{NamedDeBruijn, Term},
+    builtins::DefaultFunction,
+    machine::{
+        cost_model::{initialize_cost_model, CostModel, ExBudget, StepKind},
+        runtime::BuiltinRuntime,
+        value::Value,
+        Error,
+    },
+};
+
+use crate::common::ExUnits;
+use crate::validators::validation_result::{BuiltinProfile, MachineStepProfile};
+
+type Env = Rc<Vec<Value>>;
+
+/// Machine steps uplc counts before charging them, the slippage of `Program::eval`
+const SLIPPAGE: u32 = 200;
+
+/// Names of the `StepKind` variants, in discriminant order
+const STEP_KINDS: [&str; 10] = [
+    "Constant", "Var", "Lambda", "Apply", "Delay", "Force", "Builtin", "Constr", "Case", "StartUp",
+];
+
+enum MachineState {
+    Return(Context, Value),
+    Compute(Context, Env, Term<NamedDeBruijn>),
+    Done,
+}
+
+enum Context {
+    FrameAwaitArg(Value, Box<Context>),
+    FrameAwaitFunTerm(Env, Term<NamedDeBruijn>, Box<Context>),
+    FrameAwaitFunValue(Value, Box<Context>),
+    FrameForce(Box<Context>),
+    FrameConstr(
+        Env,
+        usize,
+        Vec<Term<NamedDeBruijn>>,
+        Vec<Value>,
+        Box<Context>,
+    ),
+    FrameCases(Env, Vec<Term<NamedDeBruijn>>, Box<Context>),
+    NoFrame,
+}
+
+#[derive(Default, Clone, Copy)]
+struct Usage {
+    count: u64,
+    mem: i64,
+    cpu: i64,
+}
+
+impl Usage {
+    fn add(&mut self, occurrences: u64, budget: ExBudget) {
+        self.count += occurrences;
+        self.mem += budget.mem;
+        self.cpu += budget.cpu;
+    }
+
+    fn ex_units(&self) -> ExUnits {
+        ExUnits {
+            mem: self.mem.max(0) as u64,
+            steps: self.cpu.max(0) as u64,
+        }
+    }
+}
+
+pub struct Profiler {
+    costs: CostModel,
+    version: Language,
+    logs: Vec<String>,
+    budget: ExBudget,
+    spent: ExBudget,
+    /// Steps not charged yet per kind, the last item is their total like in uplc
+    unbudgeted_steps: [u32; 10],
+    steps: [Usage; 10],
+    builtins: BTreeMap<DefaultFunction, Usage>,
+}
+
+impl Profiler {
+    /// Uses the default cost model of uplc when `costs` is `None`, like `Program::eval_version`.
+    /// The evaluation stops once `budget` is spent.
+    pub fn new(version: &Language, costs: Option<&[i64]>, budget: ExBudget) -> Self {
+        Self {
+            costs: costs
+                .map(|costs| initialize_cost_model(version, costs))
+                .unwrap_or_default(),
+            version: version.clone(),
+            logs: vec![],
+            budget,
+            spent: ExBudget { mem: 0, cpu: 0 },
+            unbudgeted_steps: [0; 10],
+            steps: [Usage::default(); 10],
+            builtins: BTreeMap::new(),
+        }
+    }
+
+    /// Evaluates the term, stopping at the first error or once the budget is spent
+    pub fn run(&mut self, term: Term<NamedDeBruijn>) -> Result<(), Error> {
+        self.spend_steps(StepKind::StartUp as usize, 1)?;
+
+        let mut state = MachineState::Compute(Context::NoFrame, Rc::new(vec![]), term);
+        loop {
+            state = match state {
+                MachineState::Compute(context, env, term) => self.compute(context, env, term)?,
+                MachineState::Return(context, value) => self.return_compute(context, value)?,
+                MachineState::Done => return Ok(()),
+            };
+        }
+    }
+
+    /// Builtins sorted by CPU, most expensive first
+    pub fn builtins(&self) -> Vec<BuiltinProfile> {
+        let mut builtins = self
+            .builtins
+            .iter()
+            .map(|(fun, usage)| BuiltinProfile {
+                builtin: fun.to_string(),
+                calls: usage.count,
+                ex_units: usage.ex_units(),
+            })
+            .collect::<Vec<_>>();
+        builtins.sort_by_key(|builtin| std::cmp::Reverse(builtin.ex_units.steps));
+        builtins
+    }
+
+    /// Step kinds the machine went through
+    pub fn machine_steps(&self) -> Vec<MachineStepProfile> {
+        STEP_KINDS
+            .iter()
+            .zip(self.steps.iter())
+            .filter(|(_, usage)| usage.count > 0)
+            .map(|(step, usage)| MachineStepProfile {
+                step: step.to_string(),
+                count: usage.count,
+                ex_units: usage.ex_units(),
+            })
+            .collect()
+    }
+
+    fn compute(
+        &mut self,
+        context: Context,
+        env: Env,
+        term: Term<NamedDeBruijn>,
+    ) -> Result<MachineState, Error> {
+        match term {
+            Term::Var(name) => {
+                self.step(StepKind::Var)?;
+                let value = env
+                    .len()
+                    .checked_sub(usize::from(name.index))
+                    .and_then(|index| env.get(index))
+                    .cloned()
+                    .ok_or_else(|| Error::OpenTermEvaluated(Term::Var(name.clone())))?;
+                Ok(MachineState::Return(context, value))
+            }
+            Term::Delay(body) => {
+                self.step(StepKind::Delay)?;
+                Ok(MachineState::Return(context, Value::Delay(body, env)))
+            }
+            Term::Lambda {
+                parameter_name,
+                body,
+            } => {
+                self.step(StepKind::Lambda)?;
+                Ok(MachineState::Return(
+                    context,
+                    Value::Lambda {
+                        parameter_name,
+                        body,
+                        env,
+                    },
+                ))
+            }
+            Term::Apply { function, argument } => {
+                self.step(StepKind::Apply)?;
+                Ok(MachineState::Compute(
+                    Context::FrameAwaitFunTerm(
+                        env.clone(),
+                        argument.as_ref().clone(),
+                        context.into(),
+                    ),
+                    env,
+                    function.as_ref().clone(),
+                ))
+            }
+            Term::Constant(constant) => {
+                self.step(StepKind::Constant)?;
+                Ok(MachineState::Return(context, Value::Con(constant)))
+            }
+            Term::Force(body) => {
+                self.step(StepKind::Force)?;
+                Ok(MachineState::Compute(
+                    Context::FrameForce(context.into()),
+                    env,
+                    body.as_ref().clone(),
+                ))
+            }
+            Term::Error => Err(Error::EvaluationFailure),
+            Term::Builtin(fun) => {
+                self.step(StepKind::Builtin)?;
+                Ok(MachineState::Return(
+                    context,
+                    Value::Builtin {
+                        fun,
+                        runtime: BuiltinRuntime::from(fun),
+                    },
+                ))
+            }
+            Term::Constr { tag, mut fields } => {
+                self.step(StepKind::Constr)?;
+                fields.reverse();
+                match fields.pop() {
+                    Some(field) => Ok(MachineState::Compute(
+                        Context::FrameConstr(env.clone(), tag, fields, vec![], context.into()),
+                        env,
+                        field,
+                    )),
+                    None => Ok(MachineState::Return(
+                        context,
+                        Value::Constr {
+                            tag,
+                            fields: vec![],
+                        },
+                    )),
+                }
+            }
+            Term::Case { constr, branches } => {
+                self.step(StepKind::Case)?;
+                Ok(MachineState::Compute(
+                    Context::FrameCases(env.clone(), branches, context.into()),
+                    env,
+                    constr.as_ref().clone(),
+                ))
+            }
+        }
+    }
+
+    fn return_compute(&mut self, context: Context, value: Value) -> Result<MachineState, Error> {
+        match context {
+            Context::NoFrame => {
+                if self.unbudgeted_steps[9] > 0 {
+                    self.spend_unbudgeted_steps()?;
+                }
+                Ok(MachineState::Done)
+            }
+            Context::FrameForce(context) => self.force_evaluate(*context, value),
+            Context::FrameAwaitFunTerm(env, argument, context) => Ok(MachineState::Compute(
+                Context::FrameAwaitArg(value, context),
+                env,
+                argument,
+            )),
+            Context::FrameAwaitArg(function, context) => {
+                self.apply_evaluate(*context, function, value)
+            }
+            Context::FrameAwaitFunValue(argument, context) => {
+                self.apply_evaluate(*context, value, argument)
+            }
+            Context::FrameConstr(env, tag, mut fields, mut resolved_fields, context) => {
+                resolved_fields.push(value);
+                match fields.pop() {
+                    Some(field) => Ok(MachineState::Compute(
+                        Context::FrameConstr(env.clone(), tag, fields, resolved_fields, context),
+                        env,
+                        field,
+                    )),
+                    None => Ok(MachineState::Return(
+                        *context,
+                        Value::Constr {
+                            tag,
+                            fields: resolved_fields,
+                        },
+                    )),
+                }
+            }
+            Context::FrameCases(env, branches, context) => match value {
+                Value::Constr { tag, fields } => match branches.get(tag) {
+                    Some(branch) => Ok(MachineState::Compute(
+                        transfer_arg_stack(fields, *context),
+                        env,
+                        branch.clone(),
+                    )),
+                    None => Err(Error::MissingCaseBranch(
+                        branches,
+                        Value::Constr { tag, fields },
+                    )),
+                },
+                value => Err(Error::NonConstrScrutinized(value)),
+            },
+        }
+    }
+
+    fn force_evaluate(&mut self, context: Context, value: Value) -> Result<MachineState, Error> {
+        match value {
+            Value::Delay(body, env) => {
+                Ok(MachineState::Compute(context, env, body.as_ref().clone()))
+            }
+            Value::Builtin { fun, mut runtime } if runtime.needs_force() => {
+                runtime.consume_force();
+                let value = if runtime.is_ready() {
+                    self.eval_builtin_app(fun, runtime)?
+                } else {
+                    Value::Builtin { fun, runtime }
+                };
+                Ok(MachineState::Return(context, value))
+            }
+            Value::Builtin { fun, .. } => {
+                Err(Error::BuiltinTermArgumentExpected(Term::Builtin(fun)))
+            }
+            value => Err(Error::NonPolymorphicInstantiation(value)),
+        }
+    }
+
+    fn apply_evaluate(
+        &mut self,
+        context: Context,
+        function: Value,
+        argument: Value,
+    ) -> Result<MachineState, Error> {
+        match function {
+            Value::Lambda { body, mut env, .. } => {
+                Rc::make_mut(&mut env).push(argument);
+                Ok(MachineState::Compute(context, env, body.as_ref().clone()))
+            }
+            Value::Builtin { fun, mut runtime } if runtime.is_arrow() && !runtime.needs_force() => {
+                runtime.push(argument)?;
+                let value = if runtime.is_ready() {
+                    self.eval_builtin_app(fun, runtime)?
+                } else {
+                    Value::Builtin { fun, runtime }
+                };
+                Ok(MachineState::Return(context, value))
+            }
+            Value::Builtin { fun, .. } => {
+                Err(Error::UnexpectedBuiltinTermArgument(Term::Builtin(fun)))
+            }
+            function => Err(Error::NonFunctionalApplication(function, argument)),
+        }
+    }
+
+    fn eval_builtin_app(
+        &mut self,
+        fun: DefaultFunction,
+        runtime: BuiltinRuntime,
+    ) -> Result<Value, Error> {
+        let cost = runtime.to_ex_budget(&self.costs.builtin_costs)?;
+        self.builtins.entry(fun).or_default().add(1, cost);
+        self.spend(cost)?;
+        runtime.call(&self.version, &mut self.logs)
+    }
+
+    fn step(&mut self, step: StepKind) -> Result<(), Error> {
+        self.unbudgeted_steps[step as usize] += 1;
+        self.unbudgeted_steps[9] += 1;
+        if self.unbudgeted_steps[9] >= SLIPPAGE {
+            self.spend_unbudgeted_steps()?;
+        }
+        Ok(())
+    }
+
+    /// Charges the counted steps kind by kind, so running out of budget stops at the same
+    /// point as uplc
+    fn spend_unbudgeted_steps(&mut self) -> Result<(), Error> {
+        for index in 0..self.unbudgeted_steps.len() - 1 {
+            self.spend_steps(index, self.unbudgeted_steps[index])?;
+            self.unbudgeted_steps[index] = 0;
+        }
+        self.unbudgeted_steps[9] = 0;
+        Ok(())
+    }
+
+    fn spend_steps(&mut self, index: usize, occurrences: u32) -> Result<(), Error> {
+        // `MachineCosts::get` takes the step kind by value and it isn't `Copy`
+        let mut cost = self
+            .costs
+            .machine_costs
+            .get(StepKind::try_from(index as u8).unwrap_or(StepKind::StartUp));
+        cost.occurrences(occurrences as i64);
+        self.steps[index].add(occurrences as u64, cost);
+        self.spend(cost)
+    }
+
+    fn spend(&mut self, cost: ExBudget) -> Result<(), Error> {
+        self.spent.mem += cost.mem;
+        self.spent.cpu += cost.cpu;
+        if self.spent.mem > self.budget.mem || self.spent.cpu > self.budget.cpu {
+            Err(Error::OutOfExError(ExBudget {
+                mem: self.budget.mem - self.spent.mem,
+                cpu: self.budget.cpu - self.spent.cpu,
+            }))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn transfer_arg_stack(mut args: Vec<Value>, context: Context) -> Context {
+    match args.pop() {
+        Some(arg) => transfer_arg_stack(args, Context::FrameAwaitFunValue(arg, context.into())),
+        None => context,
+    }
+}
@@ -1,13 +1,13 @@
 use crate::js_error::JsError;
 use crate::validators::input_contexts::ValidationInputContext;
-use crate::validators::phase_2::data_mapper::{to_pallas_cost_modesl, to_pallas_utxos};
+use crate::validators::input_contexts::UtxoInputContext;
+use crate::validators::phase_2::data_mapper::{decode_conway_tx, to_pallas_cost_modesl, to_pallas_utxo};
 use crate::validators::phase_2::errors::{Phase2Error, Phase2Warning, ValidationPhase2Error, ValidationPhase2Warning};
-use crate::validators::phase_2::eval_redeemer::{eval_redeemer, slot_config_network};
-use crate::validators::validation_result::{EvalRedeemerResult, ValidationResult};
+use crate::validators::phase_2::eval_redeemer::{eval_redeemer, eval_redeemer_result, slot_config_network};
+use crate::common::{ExUnitPrices, ExUnits};
+use crate::validators::validation_result::{EvalRedeemerResult, TxExecutionBudget, ValidationResult};
 use pallas_primitives::conway::{MintedTx, Redeemer};
-use pallas_traverse::{Era, MultiEraTx};
 use std::collections::HashSet;
-use uplc::machine::cost_model::ExBudget;
 use uplc::tx::{ResolvedInput, SlotConfig};
 use uplc::tx::{iter_redeemers, DataLookupTable};
 use crate::validators::validation_result::RedeemerTag as ValidatorRedeemerTag;
@@ -17,25 +17,46 @@ pub fn phase_2_validation(
     validation_input_context: &ValidationInputContext,
 ) -> Result<ValidationResult, JsError> {
     let tx_bytes = hex::decode(tx_hex).map_err(|e| JsError::new(&e.to_string()))?;
-    let mtx = MultiEraTx::decode_for_era(Era::Conway, &tx_bytes)
-        .map_err(|e| JsError::new(&e.to_string()))?;
-    let tx = match mtx {
-        MultiEraTx::Conway(tx) => tx.into_owned(),
-        _ => return Err(JsError::new("Invalid transaction type")),
+    let tx = match decode_conway_tx(&tx_bytes)? {
+        Some(tx) => tx,
+        None => return Ok(ValidationResult::new_empty()),
     };
 
     // Gather all input identifiers from the transaction.
     let request_utxos = collect_inputs(&tx);
 
-    // Deserialize UTxO data and convert to the internal representation.
-    let utxos = to_pallas_utxos(&validation_input_context.utxo_set)?;
-
-    check_missed_utxos(&request_utxos, &utxos)?;
+    check_missed_utxos(&request_utxos, &validation_input_context.utxo_set)?;
+
+    // Deserialize UTxO data and convert to the internal representation. A UTxO of the
+    // transaction that can't be converted fails its redeemers, not the whole validation.
+    let mut utxos = Vec::new();
+    let mut invalid_utxos = Vec::new();
+    for utxo in &validation_input_context.utxo_set {
+        match to_pallas_utxo(utxo) {
+            Ok(resolved_input) => utxos.push(resolved_input),
+            Err(error) => {
+                let utxo_key = format!("{}#{}", utxo.utxo.input.tx_hash, utxo.utxo.input.output_index);
+                if request_utxos.contains(&utxo_key) {
+                    invalid_utxos.push(format!("{}: {}", utxo_key, error));
+                }
+            }
+        }
+    }
 
     let slot_config = slot_config_network(&validation_input_context.network_type);
 
     let cost_models = to_pallas_cost_modesl(&validation_input_context.protocol_parameters.cost_models);
-    let exec_result = eval_all_redeemers(&tx, &utxos, Some(&cost_models), &slot_config);
+    let exec_result = eval_all_redeemers(
+        &tx,
+        &utxos,
+        Some(&cost_models),
+        &slot_config,
+        &validation_input_context.protocol_parameters.max_tx_execution_units,
+        validation_input_context
+            .profile_scripts
+            .then_some(&validation_input_context.protocol_parameters.execution_prices),
+        &invalid_utxos,
+    );
 
     Ok(exec_result)
 }
@@ -60,10 +81,10 @@ fn collect_inputs(tx: &MintedTx) -> Vec<String> {
 }
 
 /// Checks whether the UTXOs requested in the transaction are present in the API response.
-fn check_missed_utxos(request_utxos: &[String], utxos: &[ResolvedInput]) -> Result<(), JsError> {
+fn check_missed_utxos(request_utxos: &[String], utxos: &[UtxoInputContext]) -> Result<(), JsError> {
     let utxo_keys: HashSet<String> = utxos
         .iter()
-        .map(|u| format!("{}#{}", hex::encode(u.input.transaction_id), u.input.index))
+        .map(|u| format!("{}#{}", u.utxo.input.tx_hash, u.utxo.input.output_index))
         .collect();
     let missed_utxos: Vec<String> = request_utxos
         .iter()
@@ -91,6 +112,9 @@ fn eval_all_redeemers(
     utxos: &[ResolvedInput],
     cost_mdls: Option<&pallas_primitives::conway::CostModels>,
     slot_config: &SlotConfig,
+    max_tx_execution_units: &ExUnits,
+    profile_prices: Option<&ExUnitPrices>,
+    invalid_utxos: &[String],
 ) -> ValidationResult {
     let mut phase_2_errors = vec![];
     let mut phase_2_warnings = vec![];
@@ -98,11 +122,13 @@ fn eval_all_redeemers(
 
     let lookup_table = DataLookupTable::from_transaction(tx, utxos);
 
+    let mut tx_budget = TxExecutionBudget {
+        declared: ExUnits { mem: 0, steps: 0 },
+        required: ExUnits { mem: 0, steps: 0 },
+        max: max_tx_execution_units.clone(),
+    };
+
     if let Some(redeemers) = tx.transaction_witness_set.redeemer.as_ref() {
-        let remaining_budget = ExBudget {
-            mem: i64::MAX,
-            cpu: i64::MAX,
-        };
         for (redeemer_index, (r_key, r_value, r_ex_units)) in iter_redeemers(redeemers).enumerate() {
             let redeemer = Redeemer {
                 tag: r_key.tag,
@@ -110,15 +136,23 @@ fn eval_all_redeemers(
                 data: r_value.clone(),
                 ex_units: r_ex_units,
             };
-            let (eval_redeemer_result, error) = eval_redeemer(
-                tx,
-                utxos,
-                slot_config,
-                &redeemer,
-                &lookup_table,
-                cost_mdls,
-                &remaining_budget,
-            );
+            let (eval_redeemer_result, error) = if invalid_utxos.is_empty() {
+                eval_redeemer(
+                    tx,
+                    utxos,
+                    slot_config,
+                    &redeemer,
+                    &lookup_table,
+                    cost_mdls,
+                    max_tx_execution_units,
+                    profile_prices,
+                )
+            } else {
+                // The transaction info of every script holds all the UTxOs of the transaction
+                eval_redeemer_result(&redeemer, Phase2Error::BuildTxContextError {
+                    error: format!("Invalid UTxO {}", invalid_utxos.join(", ")),
+                })
+            };
 
             if let Some(error) = error {
                 phase_2_errors.push(ValidationPhase2Error::new_with_locations(error, &redeemer_to_tx_locations(&eval_redeemer_result, redeemer_index)));
@@ -128,13 +162,13 @@ fn eval_all_redeemers(
 
             let estimated_budget = &eval_redeemer_result.calculated_ex_units;
             let redeemer_budget = &eval_redeemer_result.provided_ex_units;
+            tx_budget.declared.mem += redeemer_budget.mem;
+            tx_budget.declared.steps += redeemer_budget.steps;
+            tx_budget.required.mem += estimated_budget.mem;
+            tx_budget.required.steps += estimated_budget.steps;
 
-            if estimated_budget.mem > redeemer_budget.mem || estimated_budget.steps > redeemer_budget.steps {
-                phase_2_errors.push(ValidationPhase2Error::new_with_locations(Phase2Error::NoEnoughBudget {
-                    expected_budget: estimated_budget.clone(),
-                    actual_budget: redeemer_budget.clone(),
-                }, &redeemer_to_tx_locations(&eval_redeemer_result, redeemer_index)));
-            } else if estimated_budget.mem < redeemer_budget.mem || estimated_budget.steps < redeemer_budget.steps {
+            // Exceeding the declared budget is reported by the evaluation itself
+            if eval_redeemer_result.success && (estimated_budget.mem < redeemer_budget.mem || estimated_budget.steps < redeemer_budget.steps) {
                 phase_2_warnings.push(ValidationPhase2Warning::new_with_locations(Phase2Warning::BudgetIsBiggerThanExpected {
                     expected_budget: estimated_budget.clone(),
                     actual_budget: redeemer_budget.clone(),
@@ -142,7 +176,20 @@ fn eval_all_redeemers(
             }
         }
     }
-    ValidationResult::new_phase_2(phase_2_errors, phase_2_warnings, eval_results)
+
+    if tx_budget.required.mem > tx_budget.max.mem || tx_budget.required.steps > tx_budget.max.steps {
+        phase_2_errors.push(ValidationPhase2Error::new(
+            Phase2Error::TxExecutionBudgetTooBig {
+                required_budget: tx_budget.required.clone(),
+                max_budget: tx_budget.max.clone(),
+            },
+            "transaction.witness_set.redeemers".to_string(),
+        ));
+    }
+
+    let mut result = ValidationResult::new_phase_2(phase_2_errors, phase_2_warnings, eval_results);
+    result.tx_execution_budget = Some(tx_budget);
+    result
 }
 
 fn redeemer_to_tx_locations(redeemer: &EvalRedeemerResult, redeemer_index: usize) -> Vec<String> {
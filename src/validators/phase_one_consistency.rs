@@ -0,0 +1,268 @@
+//! Diagnostic comparing the script related phase 1 checks of cquisitor with the ones uplc runs
+//! before evaluating scripts (`eval_phase_one`). Disagreements point at a rule that changed
+//! on one side, which is useful after a dependency upgrade or when adding rules.
+
+use crate::bingen::wasm_bindgen;
+use crate::js_error::JsError;
+use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::phase_1::errors::Phase1Error;
+use crate::validators::phase_2::data_mapper::{decode_conway_tx, to_pallas_utxos};
+use crate::validators::validator::validate_transaction;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use uplc::tx::error::Error as UplcError;
+use uplc::tx::{eval_phase_one, DataLookupTable};
+
+#[derive(
+    Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum PhaseOneCheck {
+    MissingScripts,
+    ExtraneousScripts,
+    MissingRedeemers,
+    ExtraneousRedeemers,
+    /// uplc rejected the transaction for a reason cquisitor has no counterpart for
+    Other,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PhaseOneReporter {
+    Cquisitor,
+    Uplc,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct PhaseOneDisagreement {
+    pub check: PhaseOneCheck,
+    /// Script hash, redeemer as `Tag[index]` or the uplc error message
+    pub subject: String,
+    /// The side that reported the failure the other side didn't
+    pub reported_by: PhaseOneReporter,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct PhaseOneConsistencyReport {
+    /// The error returned by uplc's phase one checks, if any
+    pub uplc_error: Option<String>,
+    /// Checks uplc didn't reach because it stops at the first failing one
+    pub not_compared: Vec<PhaseOneCheck>,
+    pub disagreements: Vec<PhaseOneDisagreement>,
+}
+
+/// Failures found by one side, per check. Checks that weren't run are `None`.
+#[derive(Default)]
+struct Findings {
+    missing_scripts: Option<BTreeSet<String>>,
+    extraneous_scripts: Option<BTreeSet<String>>,
+    missing_redeemers: Option<BTreeSet<String>>,
+    extraneous_redeemers: Option<BTreeSet<String>>,
+}
+
+impl Findings {
+    fn by_check(&self) -> [(PhaseOneCheck, &Option<BTreeSet<String>>); 4] {
+        [
+            (PhaseOneCheck::MissingScripts, &self.missing_scripts),
+            (PhaseOneCheck::ExtraneousScripts, &self.extraneous_scripts),
+            (PhaseOneCheck::MissingRedeemers, &self.missing_redeemers),
+            (
+                PhaseOneCheck::ExtraneousRedeemers,
+                &self.extraneous_redeemers,
+            ),
+        ]
+    }
+}
+
+#[wasm_bindgen]
+pub fn check_phase_one_consistency_js(
+    tx_hex: &str,
+    validation_context: &str,
+) -> Result<String, JsError> {
+    let validation_context =
+        serde_json::from_str(validation_context).map_err(|e| JsError::new(&e.to_string()))?;
+    let report = check_phase_one_consistency(tx_hex, validation_context)?;
+    serde_json::to_string(&report).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Runs the transaction through `validate_transaction` and uplc's `eval_phase_one` and
+/// reports the script and redeemer failures only one of them found.
+pub fn check_phase_one_consistency(
+    tx_hex: &str,
+    validation_context: ValidationInputContext,
+) -> Result<PhaseOneConsistencyReport, JsError> {
+    let tx_bytes = hex::decode(tx_hex).map_err(|e| JsError::new(&e.to_string()))?;
+    let tx = match decode_conway_tx(&tx_bytes)? {
+        Some(tx) => tx,
+        // uplc can't decode the transaction, nothing is compared
+        None => {
+            return Ok(PhaseOneConsistencyReport {
+                uplc_error: None,
+                not_compared: Findings::default()
+                    .by_check()
+                    .iter()
+                    .map(|(check, _)| *check)
+                    .collect(),
+                disagreements: vec![],
+            })
+        }
+    };
+    let utxos = to_pallas_utxos(&validation_context.utxo_set)?;
+    let lookup_table = DataLookupTable::from_transaction(&tx, &utxos);
+    let uplc_result = eval_phase_one(&tx, &utxos, &lookup_table);
+
+    let validation_result = validate_transaction(tx_hex, validation_context)?;
+    let cquisitor_findings =
+        cquisitor_findings(validation_result.errors.iter().map(|error| &error.error));
+
+    let uplc_error = uplc_result.as_ref().err().map(|e| e.to_string());
+    let uplc_findings = match &uplc_result {
+        Ok(()) => Some(uplc_findings(&[], &[], true)),
+        Err(UplcError::RequiredRedeemersMismatch { missing, extra }) => {
+            // The scripts check reports hashes, the redeemers check `Tag[index]`
+            let redeemers_check = missing.iter().chain(extra).any(|s| s.contains('['));
+            Some(uplc_findings(missing, extra, redeemers_check))
+        }
+        Err(_) => None,
+    };
+
+    let mut disagreements = Vec::new();
+    let mut not_compared = Vec::new();
+    match uplc_findings {
+        Some(uplc_findings) => {
+            for ((check, cquisitor), (_, uplc)) in cquisitor_findings
+                .by_check()
+                .iter()
+                .zip(uplc_findings.by_check().iter())
+            {
+                let (cquisitor, uplc) = match (cquisitor, uplc) {
+                    (Some(cquisitor), Some(uplc)) => (cquisitor, uplc),
+                    _ => {
+                        not_compared.push(*check);
+                        continue;
+                    }
+                };
+                for subject in cquisitor.difference(uplc) {
+                    disagreements.push(PhaseOneDisagreement {
+                        check: *check,
+                        subject: subject.clone(),
+                        reported_by: PhaseOneReporter::Cquisitor,
+                    });
+                }
+                for subject in uplc.difference(cquisitor) {
+                    disagreements.push(PhaseOneDisagreement {
+                        check: *check,
+                        subject: subject.clone(),
+                        reported_by: PhaseOneReporter::Uplc,
+                    });
+                }
+            }
+        }
+        None => {
+            not_compared.extend(
+                cquisitor_findings
+                    .by_check()
+                    .iter()
+                    .map(|(check, _)| *check),
+            );
+            if validation_result.errors.is_empty() {
+                disagreements.push(PhaseOneDisagreement {
+                    check: PhaseOneCheck::Other,
+                    subject: uplc_error.clone().unwrap_or_default(),
+                    reported_by: PhaseOneReporter::Uplc,
+                });
+            }
+        }
+    }
+
+    Ok(PhaseOneConsistencyReport {
+        uplc_error,
+        not_compared,
+        disagreements,
+    })
+}
+
+fn cquisitor_findings<'a>(errors: impl Iterator<Item = &'a Phase1Error>) -> Findings {
+    let mut findings = Findings {
+        missing_scripts: Some(BTreeSet::new()),
+        extraneous_scripts: Some(BTreeSet::new()),
+        missing_redeemers: Some(BTreeSet::new()),
+        extraneous_redeemers: Some(BTreeSet::new()),
+    };
+    for error in errors {
+        let (set, subject) = match error {
+            Phase1Error::MissingScriptWitnesses {
+                missing_script_hash,
+            } => (&mut findings.missing_scripts, missing_script_hash.clone()),
+            Phase1Error::ExtraneousScriptWitnesses { extraneous_script } => {
+                (&mut findings.extraneous_scripts, extraneous_script.clone())
+            }
+            Phase1Error::MissingRedeemer { tag, index } => (
+                &mut findings.missing_redeemers,
+                redeemer_subject(tag, *index),
+            ),
+            _ => continue,
+        };
+        if let Some(set) = set {
+            set.insert(subject);
+        }
+    }
+    findings
+}
+
+/// uplc stops at its scripts check when it fails, the redeemers check is then not run
+fn uplc_findings(missing: &[String], extra: &[String], redeemers_check: bool) -> Findings {
+    let scripts = |hashes: &[String]| hashes.iter().cloned().collect::<BTreeSet<_>>();
+    // Missing redeemers are reported as `Tag[index] -> script hash`
+    let redeemers = |redeemers: &[String]| {
+        redeemers
+            .iter()
+            .filter_map(|redeemer| {
+                let key = redeemer.split(" -> ").next()?;
+                let (tag, index) = key.trim_end_matches(']').split_once('[')?;
+                Some(redeemer_subject(
+                    tag,
+                    u32::try_from(index.parse::<u64>().ok()?).ok()?,
+                ))
+            })
+            .collect::<BTreeSet<_>>()
+    };
+    if redeemers_check {
+        Findings {
+            missing_scripts: Some(BTreeSet::new()),
+            extraneous_scripts: Some(BTreeSet::new()),
+            missing_redeemers: Some(redeemers(missing)),
+            extraneous_redeemers: Some(redeemers(extra)),
+        }
+    } else {
+        Findings {
+            missing_scripts: Some(scripts(missing)),
+            extraneous_scripts: Some(scripts(extra)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Common `Tag[index]` form of the CSL (`Spend`, `Reward`, `VotingProposal`...) and uplc
+/// (`Spend`, `Withdraw`, `Publish`...) redeemer tag names
+fn redeemer_subject(tag: &str, index: u32) -> String {
+    let tag = tag.to_lowercase();
+    let tag = if tag.contains("spend") {
+        "Spend"
+    } else if tag.contains("mint") {
+        "Mint"
+    } else if tag.contains("reward") || tag.contains("withdraw") {
+        "Reward"
+    } else if tag.contains("cert") || tag.contains("publish") {
+        "Cert"
+    } else if tag.contains("propos") {
+        "Propose"
+    } else if tag.contains("vot") {
+        "Vote"
+    } else {
+        return format!("{}[{}]", tag, index);
+    };
+    format!("{}[{}]", tag, index)
+}
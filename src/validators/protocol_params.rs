@@ -3,6 +3,11 @@ use schemars::JsonSchema;
 use std::convert::TryFrom;
 use crate::common::{CostModels, ExUnitPrices, ExUnits, SubCoin};
 
+/// First protocol version of the Conway era, the governance bootstrap phase
+pub const CONWAY_PROTOCOL_VERSION: u32 = 9;
+/// First protocol version after the Conway bootstrap phase
+pub const POST_BOOTSTRAP_PROTOCOL_VERSION: u32 = 10;
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProtocolParameters {
@@ -67,3 +72,9 @@ pub struct ProtocolParameters {
     /// Coins per byte for reference scripts
     pub reference_script_cost_per_byte: SubCoin,
 }
+
+impl ProtocolParameters {
+    pub fn protocol_major_version(&self) -> u32 {
+        self.protocol_version.0
+    }
+}
@@ -0,0 +1,518 @@
+use crate::bingen::wasm_bindgen;
+use crate::common::SubCoin;
+use crate::js_error::JsError;
+use crate::validators::common::{LocalCredential, Voter};
+use crate::validators::helpers::{normalize_drep_id, normalize_pool_id};
+use crate::validators::protocol_params::POST_BOOTSTRAP_PROTOCOL_VERSION;
+use cardano_serialization_lib as csl;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum VoteChoice {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CastVote {
+    pub voter: Voter,
+    pub vote: VoteChoice,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DrepStake {
+    /// DRep id, bech32 (CIP-129 or legacy)
+    pub drep: String,
+    pub stake: u64,
+    /// The DRep is inactive once the current epoch is past its expiry
+    pub expiry_epoch: Option<u64>,
+}
+
+/// Predefined DRep the reward account of a pool delegates to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PredefinedDrep {
+    AlwaysAbstain,
+    AlwaysNoConfidence,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStake {
+    /// Pool id, bech32 or hex
+    pub pool_id: String,
+    pub stake: u64,
+    /// Used as the default vote of a pool that did not vote
+    #[serde(default)]
+    pub reward_account_drep: Option<PredefinedDrep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitteeMemberState {
+    pub cold_credential: LocalCredential,
+    pub hot_credential: Option<LocalCredential>,
+    pub expiry_epoch: u64,
+    #[serde(default)]
+    pub is_resigned: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitteeState {
+    pub members: Vec<CommitteeMemberState>,
+    pub threshold: SubCoin,
+    pub min_size: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RatificationInput {
+    /// Hex encoded `GovernanceAction`
+    pub governance_action: String,
+    pub votes: Vec<CastVote>,
+    pub drep_stake: Vec<DrepStake>,
+    #[serde(default)]
+    pub always_abstain_stake: u64,
+    #[serde(default)]
+    pub always_no_confidence_stake: u64,
+    pub pool_stake: Vec<PoolStake>,
+    /// `None` when the ledger is in a state of no confidence
+    pub committee: Option<CommitteeState>,
+    /// Hex encoded `DRepVotingThresholds`
+    pub drep_voting_thresholds: String,
+    /// Hex encoded `PoolVotingThresholds`
+    pub pool_voting_thresholds: String,
+    pub current_epoch: u64,
+    pub protocol_major_version: u64,
+}
+
+/// Tally of one voting body. Yes and no percentages are relative to the non abstaining total,
+/// which is the ratio compared with the threshold, abstain is relative to the whole total.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyVotes {
+    /// The approval of this body is needed for the action
+    pub required: bool,
+    pub threshold: Option<SubCoin>,
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+    pub yes_percentage: f64,
+    pub no_percentage: f64,
+    pub abstain_percentage: f64,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RatificationResult {
+    pub ratified: bool,
+    pub committee: BodyVotes,
+    pub dreps: BodyVotes,
+    pub spos: BodyVotes,
+}
+
+/// Threshold of a body for an action: not voting on it, a ratio, or never reachable
+enum Threshold {
+    NotRequired,
+    Ratio(SubCoin),
+    Unreachable,
+}
+
+#[wasm_bindgen]
+pub fn simulate_ratification_js(input: &str) -> Result<String, JsError> {
+    let input: RatificationInput =
+        serde_json::from_str(input).map_err(|e| JsError::new(&e.to_string()))?;
+    let result = simulate_ratification(&input)?;
+    serde_json::to_string(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Computes whether a governance action would be ratified with the given votes and stake
+/// distributions under the Conway rules. Only the vote tallies are checked, not the
+/// previous action ids, the guardrails script or the enactment order.
+pub fn simulate_ratification(input: &RatificationInput) -> Result<RatificationResult, JsError> {
+    let gov_action = csl::GovernanceAction::from_hex(&input.governance_action)
+        .map_err(|e| JsError::new(&format!("Failed to parse governance action: {:?}", e)))?;
+    let drep_thresholds = csl::DRepVotingThresholds::from_hex(&input.drep_voting_thresholds)
+        .map_err(|e| JsError::new(&format!("Failed to parse DRep voting thresholds: {:?}", e)))?;
+    let pool_thresholds = csl::PoolVotingThresholds::from_hex(&input.pool_voting_thresholds)
+        .map_err(|e| JsError::new(&format!("Failed to parse pool voting thresholds: {:?}", e)))?;
+    // DReps do not vote during the bootstrap phase
+    let is_bootstrap = input.protocol_major_version < POST_BOOTSTRAP_PROTOCOL_VERSION as u64;
+
+    let committee_threshold = committee_threshold(&gov_action, input, is_bootstrap);
+    let drep_threshold = if is_bootstrap {
+        Threshold::NotRequired
+    } else {
+        drep_threshold(&gov_action, &drep_thresholds, input.committee.is_some())
+    };
+    let spo_threshold = spo_threshold(&gov_action, &pool_thresholds, input.committee.is_some());
+
+    let committee = tally(committee_threshold, committee_votes(input));
+    let dreps = tally(drep_threshold, drep_votes(input, &gov_action));
+    let spos = tally(spo_threshold, spo_votes(input, &gov_action, is_bootstrap));
+
+    Ok(RatificationResult {
+        ratified: committee.passed && dreps.passed && spos.passed,
+        committee,
+        dreps,
+        spos,
+    })
+}
+
+fn to_sub_coin(interval: &csl::UnitInterval) -> SubCoin {
+    SubCoin {
+        numerator: u64::from(interval.numerator()),
+        denominator: u64::from(interval.denominator()),
+    }
+}
+
+fn max_ratio(a: SubCoin, b: SubCoin) -> SubCoin {
+    if a.numerator as u128 * b.denominator as u128 >= b.numerator as u128 * a.denominator as u128 {
+        a
+    } else {
+        b
+    }
+}
+
+/// Outside bootstrap a committee with fewer active members than its minimum size can't
+/// approve anything
+fn committee_threshold(
+    gov_action: &csl::GovernanceAction,
+    input: &RatificationInput,
+    is_bootstrap: bool,
+) -> Threshold {
+    match gov_action.kind() {
+        csl::GovernanceActionKind::NoConfidenceAction
+        | csl::GovernanceActionKind::UpdateCommitteeAction => Threshold::NotRequired,
+        csl::GovernanceActionKind::InfoAction => Threshold::Unreachable,
+        _ => match &input.committee {
+            Some(committee) => {
+                let active_members = committee
+                    .members
+                    .iter()
+                    .filter(|member| is_active_member(member, input.current_epoch))
+                    .count();
+                if !is_bootstrap && (active_members as u32) < committee.min_size {
+                    Threshold::Unreachable
+                } else {
+                    Threshold::Ratio(committee.threshold.clone())
+                }
+            }
+            None => Threshold::Unreachable,
+        },
+    }
+}
+
+fn is_active_member(member: &CommitteeMemberState, current_epoch: u64) -> bool {
+    !member.is_resigned && member.hot_credential.is_some() && member.expiry_epoch >= current_epoch
+}
+
+fn drep_threshold(
+    gov_action: &csl::GovernanceAction,
+    thresholds: &csl::DRepVotingThresholds,
+    has_committee: bool,
+) -> Threshold {
+    let ratio = match gov_action.kind() {
+        csl::GovernanceActionKind::NoConfidenceAction => thresholds.motion_no_confidence(),
+        csl::GovernanceActionKind::UpdateCommitteeAction if has_committee => {
+            thresholds.committee_normal()
+        }
+        csl::GovernanceActionKind::UpdateCommitteeAction => thresholds.committee_no_confidence(),
+        csl::GovernanceActionKind::NewConstitutionAction => thresholds.update_constitution(),
+        csl::GovernanceActionKind::HardForkInitiationAction => thresholds.hard_fork_initiation(),
+        csl::GovernanceActionKind::TreasuryWithdrawalsAction => thresholds.treasury_withdrawal(),
+        csl::GovernanceActionKind::ParameterChangeAction => {
+            let update = match gov_action.as_parameter_change_action() {
+                Some(action) => action.protocol_param_updates(),
+                None => return Threshold::Unreachable,
+            };
+            let groups = ParameterGroups::of(&update);
+            let mut threshold = SubCoin {
+                numerator: 0,
+                denominator: 1,
+            };
+            if groups.network {
+                threshold = max_ratio(threshold, to_sub_coin(&thresholds.pp_network_group()));
+            }
+            if groups.economic {
+                threshold = max_ratio(threshold, to_sub_coin(&thresholds.pp_economic_group()));
+            }
+            if groups.technical {
+                threshold = max_ratio(threshold, to_sub_coin(&thresholds.pp_technical_group()));
+            }
+            if groups.governance {
+                threshold = max_ratio(threshold, to_sub_coin(&thresholds.pp_governance_group()));
+            }
+            return Threshold::Ratio(threshold);
+        }
+        csl::GovernanceActionKind::InfoAction => return Threshold::Unreachable,
+    };
+    Threshold::Ratio(to_sub_coin(&ratio))
+}
+
+fn spo_threshold(
+    gov_action: &csl::GovernanceAction,
+    thresholds: &csl::PoolVotingThresholds,
+    has_committee: bool,
+) -> Threshold {
+    let ratio = match gov_action.kind() {
+        csl::GovernanceActionKind::NoConfidenceAction => thresholds.motion_no_confidence(),
+        csl::GovernanceActionKind::UpdateCommitteeAction if has_committee => {
+            thresholds.committee_normal()
+        }
+        csl::GovernanceActionKind::UpdateCommitteeAction => thresholds.committee_no_confidence(),
+        csl::GovernanceActionKind::HardForkInitiationAction => thresholds.hard_fork_initiation(),
+        csl::GovernanceActionKind::ParameterChangeAction => {
+            match gov_action.as_parameter_change_action() {
+                Some(action) if ParameterGroups::of(&action.protocol_param_updates()).security => {
+                    thresholds.security_relevant_threshold()
+                }
+                _ => return Threshold::NotRequired,
+            }
+        }
+        csl::GovernanceActionKind::NewConstitutionAction
+        | csl::GovernanceActionKind::TreasuryWithdrawalsAction => return Threshold::NotRequired,
+        csl::GovernanceActionKind::InfoAction => return Threshold::Unreachable,
+    };
+    Threshold::Ratio(to_sub_coin(&ratio))
+}
+
+/// Parameter groups touched by a protocol parameter update
+struct ParameterGroups {
+    network: bool,
+    economic: bool,
+    technical: bool,
+    governance: bool,
+    security: bool,
+}
+
+impl ParameterGroups {
+    fn of(update: &csl::ProtocolParamUpdate) -> Self {
+        Self {
+            network: update.max_block_body_size().is_some()
+                || update.max_tx_size().is_some()
+                || update.max_block_header_size().is_some()
+                || update.max_value_size().is_some()
+                || update.max_tx_ex_units().is_some()
+                || update.max_block_ex_units().is_some()
+                || update.max_collateral_inputs().is_some(),
+            economic: update.minfee_a().is_some()
+                || update.minfee_b().is_some()
+                || update.key_deposit().is_some()
+                || update.pool_deposit().is_some()
+                || update.expansion_rate().is_some()
+                || update.treasury_growth_rate().is_some()
+                || update.min_pool_cost().is_some()
+                || update.ada_per_utxo_byte().is_some()
+                || update.execution_costs().is_some()
+                || update.ref_script_coins_per_byte().is_some(),
+            technical: update.max_epoch().is_some()
+                || update.n_opt().is_some()
+                || update.pool_pledge_influence().is_some()
+                || update.cost_models().is_some()
+                || update.collateral_percentage().is_some(),
+            governance: update.pool_voting_thresholds().is_some()
+                || update.drep_voting_thresholds().is_some()
+                || update.min_committee_size().is_some()
+                || update.committee_term_limit().is_some()
+                || update.governance_action_validity_period().is_some()
+                || update.governance_action_deposit().is_some()
+                || update.drep_deposit().is_some()
+                || update.drep_inactivity_period().is_some(),
+            security: update.max_block_body_size().is_some()
+                || update.max_tx_size().is_some()
+                || update.max_block_header_size().is_some()
+                || update.max_value_size().is_some()
+                || update.max_block_ex_units().is_some()
+                || update.minfee_a().is_some()
+                || update.minfee_b().is_some()
+                || update.ada_per_utxo_byte().is_some()
+                || update.governance_action_deposit().is_some()
+                || update.ref_script_coins_per_byte().is_some(),
+        }
+    }
+}
+
+/// Yes, no and abstain totals of a body
+struct Votes {
+    yes: u64,
+    no: u64,
+    abstain: u64,
+}
+
+impl Votes {
+    fn add(&mut self, vote: VoteChoice, weight: u64) {
+        match vote {
+            VoteChoice::Yes => self.yes += weight,
+            VoteChoice::No => self.no += weight,
+            VoteChoice::Abstain => self.abstain += weight,
+        }
+    }
+}
+
+fn find_vote<F: Fn(&Voter) -> bool>(votes: &[CastVote], matches: F) -> Option<VoteChoice> {
+    votes
+        .iter()
+        .find(|cast| matches(&cast.voter))
+        .map(|cast| cast.vote)
+}
+
+/// Active members that did not vote count as no, members that are expired, resigned or
+/// without a hot key count as abstain
+fn committee_votes(input: &RatificationInput) -> Votes {
+    let mut votes = Votes {
+        yes: 0,
+        no: 0,
+        abstain: 0,
+    };
+    let committee = match &input.committee {
+        Some(committee) => committee,
+        None => return votes,
+    };
+    for member in committee.members.iter() {
+        let vote = match &member.hot_credential {
+            Some(hot_credential) if is_active_member(member, input.current_epoch) => {
+                find_vote(&input.votes, |voter| match (voter, hot_credential) {
+                    (
+                        Voter::ConstitutionalCommitteeHotKeyHash(hash),
+                        LocalCredential::KeyHash(hot_hash),
+                    )
+                    | (
+                        Voter::ConstitutionalCommitteeHotScriptHash(hash),
+                        LocalCredential::ScriptHash(hot_hash),
+                    ) => hash == hot_hash,
+                    _ => false,
+                })
+                .unwrap_or(VoteChoice::No)
+            }
+            _ => VoteChoice::Abstain,
+        };
+        votes.add(vote, 1);
+    }
+    votes
+}
+
+/// Active DReps that did not vote count as no, inactive ones are left out. The predefined
+/// always no confidence DRep votes yes on no confidence actions and no on the others.
+fn drep_votes(input: &RatificationInput, gov_action: &csl::GovernanceAction) -> Votes {
+    let mut votes = Votes {
+        yes: 0,
+        no: 0,
+        abstain: input.always_abstain_stake,
+    };
+    if gov_action.kind() == csl::GovernanceActionKind::NoConfidenceAction {
+        votes.yes += input.always_no_confidence_stake;
+    } else {
+        votes.no += input.always_no_confidence_stake;
+    }
+
+    for drep in input.drep_stake.iter() {
+        if drep
+            .expiry_epoch
+            .is_some_and(|expiry| expiry < input.current_epoch)
+        {
+            continue;
+        }
+        let drep_id = normalize_drep_id(&drep.drep);
+        let vote = find_vote(&input.votes, |voter| {
+            let credential = match voter {
+                Voter::DRepKeyHash(hash) => csl::Ed25519KeyHash::from_bytes(hash.clone())
+                    .map(|hash| csl::Credential::from_keyhash(&hash)),
+                Voter::DRepScriptHash(hash) => csl::ScriptHash::from_bytes(hash.clone())
+                    .map(|hash| csl::Credential::from_scripthash(&hash)),
+                _ => return false,
+            };
+            credential
+                .ok()
+                .and_then(|credential| {
+                    csl::DRep::new_from_credential(&credential)
+                        .to_bech32(true)
+                        .ok()
+                })
+                .is_some_and(|voter_id| voter_id == drep_id)
+        })
+        .unwrap_or(VoteChoice::No);
+        votes.add(vote, drep.stake);
+    }
+    votes
+}
+
+/// Pools that did not vote count as no on hard forks. Otherwise they abstain during bootstrap
+/// and afterwards follow the predefined DRep of their reward account, defaulting to no.
+fn spo_votes(
+    input: &RatificationInput,
+    gov_action: &csl::GovernanceAction,
+    is_bootstrap: bool,
+) -> Votes {
+    let mut votes = Votes {
+        yes: 0,
+        no: 0,
+        abstain: 0,
+    };
+    let kind = gov_action.kind();
+    for pool in input.pool_stake.iter() {
+        let pool_id = normalize_pool_id(&pool.pool_id);
+        let vote = find_vote(&input.votes, |voter| match voter {
+            Voter::StakingPoolKeyHash(hash) => hex::encode(hash) == pool_id,
+            _ => false,
+        });
+        let vote = match vote {
+            Some(vote) => vote,
+            None if kind == csl::GovernanceActionKind::HardForkInitiationAction => VoteChoice::No,
+            None if is_bootstrap => VoteChoice::Abstain,
+            None => match pool.reward_account_drep {
+                Some(PredefinedDrep::AlwaysAbstain) => VoteChoice::Abstain,
+                Some(PredefinedDrep::AlwaysNoConfidence)
+                    if kind == csl::GovernanceActionKind::NoConfidenceAction =>
+                {
+                    VoteChoice::Yes
+                }
+                _ => VoteChoice::No,
+            },
+        };
+        votes.add(vote, pool.stake);
+    }
+    votes
+}
+
+fn percentage(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+fn tally(threshold: Threshold, votes: Votes) -> BodyVotes {
+    let voting = votes.yes + votes.no;
+    let (required, threshold, passed) = match threshold {
+        Threshold::NotRequired => (false, None, true),
+        Threshold::Unreachable => (true, None, false),
+        Threshold::Ratio(ratio) => {
+            // yes / (yes + no) >= numerator / denominator, a zero total gives a zero ratio
+            let passed = votes.yes as u128 * ratio.denominator as u128
+                >= ratio.numerator as u128 * voting as u128
+                && (voting > 0 || ratio.numerator == 0);
+            (true, Some(ratio), passed)
+        }
+    };
+    BodyVotes {
+        required,
+        threshold,
+        yes: votes.yes,
+        no: votes.no,
+        abstain: votes.abstain,
+        yes_percentage: percentage(votes.yes, voting),
+        no_percentage: percentage(votes.no, voting),
+        abstain_percentage: percentage(votes.abstain, voting + votes.abstain),
+        passed,
+    }
+}
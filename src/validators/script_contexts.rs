@@ -0,0 +1,113 @@
+//! Script contexts the scripts of a transaction are evaluated with, so that a validator can be
+//! replayed with exactly the same arguments in unit tests.
+
+use crate::bingen::wasm_bindgen;
+use crate::js_error::JsError;
+use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::phase_2::data_mapper::{decode_conway_tx, to_pallas_utxos};
+use crate::validators::phase_2::eval_redeemer::{
+    language_to_string, map_tag_to_redeemer_tag, script_arguments, slot_config_network,
+};
+use crate::validators::validation_result::RedeemerTag;
+use cardano_serialization_lib as csl;
+use pallas_primitives::conway::{PlutusData, Redeemer};
+use pallas_primitives::Fragment;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use uplc::tx::{iter_redeemers, DataLookupTable};
+
+/// Plutus data as CBOR hex and as JSON in the detailed schema
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct ScriptArgument {
+    pub cbor: String,
+    pub json: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct RedeemerScriptContext {
+    pub tag: RedeemerTag,
+    pub index: u64,
+    pub language: Option<String>,
+    /// Datum of the spent output. V1 and V2 scripts get it as their first argument.
+    pub datum: Option<ScriptArgument>,
+    pub redeemer: Option<ScriptArgument>,
+    /// Last argument of V1 and V2 scripts, the only one of V3 scripts
+    pub script_context: Option<ScriptArgument>,
+    /// Why the script context couldn't be built or an argument couldn't be converted
+    pub error: Option<String>,
+}
+
+#[wasm_bindgen]
+pub fn get_script_contexts_js(tx_hex: &str, validation_context: &str) -> Result<String, JsError> {
+    let validation_context: ValidationInputContext =
+        serde_json::from_str(validation_context).map_err(|e| JsError::new(&e.to_string()))?;
+    let script_contexts = get_script_contexts(tx_hex, &validation_context)?;
+    serde_json::to_string(&script_contexts).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Builds, for each redeemer, the datum, redeemer and script context its script is applied to
+pub fn get_script_contexts(
+    tx_hex: &str,
+    validation_context: &ValidationInputContext,
+) -> Result<Vec<RedeemerScriptContext>, JsError> {
+    let tx_bytes = hex::decode(tx_hex).map_err(|e| JsError::new(&e.to_string()))?;
+    let tx = match decode_conway_tx(&tx_bytes)? {
+        Some(tx) => tx,
+        None => return Ok(vec![]),
+    };
+    let utxos = to_pallas_utxos(&validation_context.utxo_set)?;
+    let slot_config = slot_config_network(&validation_context.network_type);
+    let lookup_table = DataLookupTable::from_transaction(&tx, &utxos);
+
+    let redeemers = match tx.transaction_witness_set.redeemer.as_ref() {
+        Some(redeemers) => redeemers,
+        None => return Ok(vec![]),
+    };
+    Ok(iter_redeemers(redeemers)
+        .map(|(key, data, ex_units)| {
+            let redeemer = Redeemer {
+                tag: key.tag,
+                index: key.index,
+                data: data.clone(),
+                ex_units,
+            };
+            let mut script_context = RedeemerScriptContext {
+                tag: map_tag_to_redeemer_tag(&redeemer.tag),
+                index: redeemer.index as u64,
+                language: None,
+                datum: None,
+                redeemer: None,
+                script_context: None,
+                error: None,
+            };
+            // A redeemer that can't be exported doesn't fail the others
+            let result = (|| -> Result<(), String> {
+                script_context.redeemer = Some(to_script_argument(&redeemer.data)?);
+                let (language, datum, context) =
+                    script_arguments(&tx, &utxos, &slot_config, &redeemer, &lookup_table)
+                        .map_err(|error| error.to_string())?;
+                script_context.language = Some(language_to_string(&language));
+                script_context.datum = datum.as_ref().map(to_script_argument).transpose()?;
+                script_context.script_context = Some(to_script_argument(&context)?);
+                Ok(())
+            })();
+            script_context.error = result.err();
+            script_context
+        })
+        .collect())
+}
+
+fn to_script_argument(data: &PlutusData) -> Result<ScriptArgument, String> {
+    let cbor = data
+        .encode_fragment()
+        .map_err(|e| format!("Failed to encode plutus data: {}", e))?;
+    let json = csl::PlutusData::from_bytes(cbor.clone())
+        .map_err(|e| format!("Failed to decode plutus data: {:?}", e))?
+        .to_json(csl::PlutusDatumSchema::DetailedSchema)
+        .map_err(|e| format!("Failed to convert plutus data to JSON: {:?}", e))?;
+    Ok(ScriptArgument {
+        cbor: hex::encode(cbor),
+        json: serde_json::from_str(&json).map_err(|e| e.to_string())?,
+    })
+}
@@ -0,0 +1,140 @@
+use crate::validators::anchor_documents::{verify_anchor_documents, AnchorDocumentsInput};
+use crate::validators::phase_1::errors::{Phase1Error, Phase1Warning};
+use cardano_serialization_lib as csl;
+use cryptoxide::hashing::blake2b_256;
+use std::collections::HashMap;
+
+const URL: &str = "https://example.com/drep.jsonld";
+
+fn drep_registration_hex(document: &[u8]) -> String {
+    let anchor = csl::Anchor::new(
+        &csl::URL::new(URL.to_string()).unwrap(),
+        &csl::AnchorDataHash::from_bytes(blake2b_256(document).to_vec()).unwrap(),
+    );
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![7; 28]).unwrap());
+    let cert = csl::DRepRegistration::new_with_anchor(
+        &credential,
+        &csl::BigNum::from(500_000_000u64),
+        &anchor,
+    );
+    csl::Certificate::new_drep_registration(&cert).to_hex()
+}
+
+#[test]
+fn test_verify_anchor_documents() {
+    let document = br#"{
+        "@context": {},
+        "hashAlgorithm": "blake2b-256",
+        "authors": [],
+        "body": { "givenName": "Alice" }
+    }"#;
+    let cert_hex = drep_registration_hex(document);
+    let mut documents = HashMap::new();
+    documents.insert(URL.to_string(), hex::encode(&document[..]));
+    let input = AnchorDocumentsInput {
+        documents,
+        check_structure: true,
+    };
+
+    let result = verify_anchor_documents(&cert_hex, &input).unwrap();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    // Same URL, different document bytes
+    let mut documents = HashMap::new();
+    documents.insert(URL.to_string(), hex::encode(br#"{"body": {}}"#));
+    let input = AnchorDocumentsInput {
+        documents,
+        check_structure: true,
+    };
+    let result = verify_anchor_documents(&cert_hex, &input).unwrap();
+    assert_eq!(result.errors.len(), 1);
+    assert!(matches!(
+        result.errors[0].error,
+        Phase1Error::AnchorDocumentHashMismatch { .. }
+    ));
+    assert!(result.warnings.iter().any(|warning| matches!(
+        &warning.warning,
+        Phase1Warning::AnchorDocumentInvalid { message, .. } if message == "Missing \"body.givenName\""
+    )));
+
+    let input = AnchorDocumentsInput {
+        documents: HashMap::new(),
+        check_structure: false,
+    };
+    let result = verify_anchor_documents(&cert_hex, &input).unwrap();
+    assert!(matches!(
+        result.warnings[0].warning,
+        Phase1Warning::AnchorDocumentMissing { .. }
+    ));
+    assert_eq!(
+        result.warnings[0].locations,
+        vec!["certificate".to_string()]
+    );
+}
+
+#[test]
+fn test_constitution_anchor_is_only_hash_checked() {
+    let constitution_url = "https://example.com/constitution.txt";
+    let proposal_url = "https://example.com/proposal.jsonld";
+    let constitution = b"Cardano Blockchain Ecosystem Constitution\n\nPreamble...";
+    let proposal = br#"{ "@context": {}, "hashAlgorithm": "blake2b-256", "authors": [], "body": {} }"#;
+    let anchor = |url: &str, document: &[u8]| {
+        csl::Anchor::new(
+            &csl::URL::new(url.to_string()).unwrap(),
+            &csl::AnchorDataHash::from_bytes(blake2b_256(document).to_vec()).unwrap(),
+        )
+    };
+    let action = csl::GovernanceAction::new_new_constitution_action(
+        &csl::NewConstitutionAction::new(&csl::Constitution::new(&anchor(
+            constitution_url,
+            constitution,
+        ))),
+    );
+    let reward_account = csl::RewardAddress::new(
+        1,
+        &csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![7; 28]).unwrap()),
+    );
+    let proposal_hex = csl::VotingProposal::new(
+        &action,
+        &anchor(proposal_url, proposal),
+        &reward_account,
+        &csl::BigNum::from(100_000_000_000u64),
+    )
+    .to_hex();
+    let mut documents = HashMap::new();
+    documents.insert(constitution_url.to_string(), hex::encode(&constitution[..]));
+    documents.insert(proposal_url.to_string(), hex::encode(&proposal[..]));
+    let input = AnchorDocumentsInput {
+        documents,
+        check_structure: true,
+    };
+
+    let result = verify_anchor_documents(&proposal_hex, &input).unwrap();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    // Only the CIP-108 proposal anchor is structure checked
+    assert!(!result.warnings.is_empty());
+    assert!(result.warnings.iter().all(|warning| matches!(
+        &warning.warning,
+        Phase1Warning::AnchorDocumentInvalid { url, standard, .. }
+            if url == proposal_url && standard == "CIP-108"
+    )));
+
+    // A different constitution text is still a hash mismatch
+    let mut input = input;
+    input.documents.insert(
+        constitution_url.to_string(),
+        hex::encode(b"Another constitution"),
+    );
+    let result = verify_anchor_documents(&proposal_hex, &input).unwrap();
+    assert_eq!(result.errors.len(), 1);
+    assert!(matches!(
+        &result.errors[0].error,
+        Phase1Error::AnchorDocumentHashMismatch { url, .. } if url == constitution_url
+    ));
+    assert_eq!(
+        result.errors[0].locations,
+        vec!["voting_proposal.governance_action.constitution".to_string()]
+    );
+}
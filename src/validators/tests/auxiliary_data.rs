@@ -0,0 +1,106 @@
+use crate::validators::phase_1::validation::AuxiliaryDataValidator;
+
+#[test]
+fn test_invalid_metadata_violations() {
+    // auxiliary data: { 674: "a" * 65, 1: [h'00' * 65, 7] }
+    let tx_hex = format!(
+        "84a0a0f5a2 1902a2 7841{text} 01 82 5841{bytes} 07",
+        text = "61".repeat(65),
+        bytes = "00".repeat(65)
+    )
+    .replace(' ', "");
+    let tx_bytes = hex::decode(tx_hex).unwrap();
+
+    let violations = AuxiliaryDataValidator::collect_raw_violations(&tx_bytes);
+
+    assert_eq!(violations.len(), 2, "{:?}", violations);
+    assert!(violations.iter().any(|(message, location)| message
+        .starts_with("Text metadatum is 65 bytes")
+        && location == "transaction.auxiliary_data.metadata.674"));
+    assert!(violations.iter().any(|(message, location)| message
+        .starts_with("Bytes metadatum is 65 bytes")
+        && location == "transaction.auxiliary_data.metadata.1.0"));
+}
+
+#[test]
+fn test_chunked_metadata_length() {
+    // auxiliary data: { 674: (_ "aaaaaaaa", ...) } with `chunks` 8-byte chunks
+    let chunked_text = |chunks: usize| {
+        let tx_hex = format!(
+            "84a0a0f5a1 1902a2 7f{chunks}ff",
+            chunks = format!("68{}", "61".repeat(8)).repeat(chunks)
+        )
+        .replace(' ', "");
+        AuxiliaryDataValidator::collect_raw_violations(&hex::decode(tx_hex).unwrap())
+    };
+
+    let violations = chunked_text(10);
+    assert_eq!(violations.len(), 1, "{:?}", violations);
+    assert!(violations[0].0.starts_with("Text metadatum is 80 bytes"));
+    assert_eq!(violations[0].1, "transaction.auxiliary_data.metadata.674");
+
+    assert!(chunked_text(8).is_empty());
+}
+
+#[test]
+fn test_cip25_metadata_warnings() {
+    use crate::validators::phase_1::{
+        errors::Phase1Warning, validation::token_metadata::collect_token_metadata_warnings,
+    };
+    use cardano_serialization_lib as csl;
+
+    let policy_id =
+        csl::ScriptHash::from_hex("1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209")
+            .unwrap();
+    let minted_name = csl::AssetName::new(b"Token1".to_vec()).unwrap();
+    let mut assets = csl::MintAssets::new();
+    assets.insert(&minted_name, &csl::Int::new_i32(1)).unwrap();
+    let mut mint = csl::Mint::new();
+    mint.insert(&policy_id, &assets);
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::zero(),
+    );
+    tx_body.set_mint(&mint);
+
+    // { 721: { "<policy>": { h'546f6b656e31': { "name": "Token1" } } } } without "version": 2
+    let mut details = csl::MetadataMap::new();
+    details
+        .insert_str(
+            "name",
+            &csl::TransactionMetadatum::new_text("Token1".to_string()).unwrap(),
+        )
+        .unwrap();
+    let mut policy_assets = csl::MetadataMap::new();
+    policy_assets.insert(
+        &csl::TransactionMetadatum::new_bytes(b"Token1".to_vec()).unwrap(),
+        &csl::TransactionMetadatum::new_map(&details),
+    );
+    let mut policies = csl::MetadataMap::new();
+    policies
+        .insert_str(
+            &policy_id.to_hex(),
+            &csl::TransactionMetadatum::new_map(&policy_assets),
+        )
+        .unwrap();
+    let mut metadata = csl::GeneralTransactionMetadata::new();
+    metadata.insert(
+        &csl::BigNum::from(721u64),
+        &csl::TransactionMetadatum::new_map(&policies),
+    );
+    let mut auxiliary_data = csl::AuxiliaryData::new();
+    auxiliary_data.set_metadata(&metadata);
+
+    let warnings = collect_token_metadata_warnings(&tx_body, Some(&auxiliary_data));
+
+    let messages: Vec<String> = warnings
+        .iter()
+        .filter_map(|(warning, _)| match warning {
+            Phase1Warning::Cip25MetadataMismatch { message, .. } => Some(message.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(messages.len(), 1, "{:?}", warnings);
+    assert!(messages[0].starts_with("Asset name key is raw bytes"));
+}
@@ -0,0 +1,191 @@
+use crate::validators::common::NetworkType;
+use crate::validators::helpers::credential_to_bech32_reward_address;
+use crate::validators::input_contexts::{
+    AccountInputContext, DepositParametersInputContext, DrepInputContext, ValidationInputContext,
+};
+use crate::validators::phase_1::errors::{Phase1Error, Phase1Warning};
+use crate::validators::phase_1::validation::BalanceValidator;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+
+fn unregistration_tx_body(credential: &csl::Credential, refund: u64) -> csl::TransactionBody {
+    let mut certs = csl::Certificates::new();
+    certs.add(&csl::Certificate::new_stake_deregistration(
+        &csl::StakeDeregistration::new_with_explicit_refund(credential, &csl::BigNum::from(refund)),
+    ));
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::zero(),
+    );
+    tx_body.set_certs(&certs);
+    tx_body
+}
+
+fn context_with_history(
+    credential: &csl::Credential,
+    history: Vec<DepositParametersInputContext>,
+) -> ValidationInputContext {
+    let mut context = ValidationInputContext::new(
+        vec![],
+        get_test_protocol_parameters(),
+        0,
+        vec![AccountInputContext {
+            bech32_address: credential_to_bech32_reward_address(credential, &NetworkType::Mainnet),
+            is_registered: true,
+            payed_deposit: None,
+            delegated_to_drep: None,
+            delegated_to_pool: None,
+            balance: Some(0),
+            registration_epoch: Some(300),
+        }],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    );
+    context.deposit_history = history;
+    context
+}
+
+#[test]
+fn test_stake_deregistration_refund_inference() {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![3; 28]).unwrap());
+    let tx_body = unregistration_tx_body(&credential, 2_000_000);
+
+    // No history: the refund can't be checked
+    let context = context_with_history(&credential, vec![]);
+    let result = BalanceValidator::new(&tx_body, &context).validate();
+    assert!(result.warnings.iter().any(|warning| matches!(
+        warning.warning,
+        Phase1Warning::CannotCheckStakeDeregistrationRefund {}
+    )));
+
+    // The deposit was 3 ADA when the account was registered in epoch 300
+    let context = context_with_history(
+        &credential,
+        vec![
+            DepositParametersInputContext {
+                epoch: 0,
+                stake_key_deposit: 2_000_000,
+                drep_deposit: 500_000_000,
+            },
+            DepositParametersInputContext {
+                epoch: 250,
+                stake_key_deposit: 3_000_000,
+                drep_deposit: 500_000_000,
+            },
+        ],
+    );
+    let result = BalanceValidator::new(&tx_body, &context).validate();
+    assert!(!result.warnings.iter().any(|warning| matches!(
+        warning.warning,
+        Phase1Warning::CannotCheckStakeDeregistrationRefund {}
+    )));
+    assert!(result.errors.iter().any(|error| matches!(
+        error.error,
+        Phase1Error::StakeDeregistrationWrongRefund {
+            supplied_refund: 2_000_000,
+            required_refund: 3_000_000,
+        }
+    )));
+}
+
+#[test]
+fn test_withdrawal_drep_delegation_by_protocol_version() {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![4; 28]).unwrap());
+    let mut withdrawals = csl::Withdrawals::new();
+    withdrawals.insert(&csl::RewardAddress::new(1, &credential), &csl::Coin::zero());
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::zero(),
+    );
+    tx_body.set_withdrawals(&withdrawals);
+    let is_not_delegated = |error: &Phase1Error| {
+        matches!(
+            error,
+            Phase1Error::WithdrawalNotAllowedBecauseNotDelegatedToDRep { .. }
+        )
+    };
+
+    let mut context = context_with_history(&credential, vec![]);
+    let result = BalanceValidator::new(&tx_body, &context).validate();
+    assert!(!result
+        .errors
+        .iter()
+        .any(|error| is_not_delegated(&error.error)));
+
+    context.protocol_parameters.protocol_version = (10, 0);
+    let result = BalanceValidator::new(&tx_body, &context).validate();
+    let error = result
+        .errors
+        .iter()
+        .find(|error| is_not_delegated(&error.error))
+        .unwrap();
+    assert_eq!(error.rule_version, Some(10));
+}
+
+#[test]
+fn test_withdrawal_drep_delegation_will_be_dropped() {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![5; 28]).unwrap());
+    let drep = csl::DRep::new_key_hash(&csl::Ed25519KeyHash::from_bytes(vec![6; 28]).unwrap());
+    let drep_id = drep.to_bech32(true).unwrap();
+    let mut withdrawals = csl::Withdrawals::new();
+    withdrawals.insert(&csl::RewardAddress::new(1, &credential), &csl::Coin::zero());
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::zero(),
+    );
+    tx_body.set_withdrawals(&withdrawals);
+    let will_be_dropped = |warning: &Phase1Warning| {
+        matches!(
+            warning,
+            Phase1Warning::WithdrawalDRepDelegationWillBeDropped { .. }
+        )
+    };
+
+    let mut context = context_with_history(&credential, vec![]);
+    context.protocol_parameters.protocol_version = (10, 0);
+    context.account_contexts[0].delegated_to_drep = Some(drep_id.clone());
+    context.drep_contexts = vec![DrepInputContext {
+        bech32_drep: drep_id,
+        is_registered: false,
+        payed_deposit: None,
+        expiry_epoch: None,
+        registration_epoch: None,
+    }];
+    let result = BalanceValidator::new(&tx_body, &context).validate();
+    let warning = result
+        .warnings
+        .iter()
+        .find(|warning| will_be_dropped(&warning.warning))
+        .unwrap();
+    assert_eq!(warning.rule_version, Some(10));
+
+    // Before protocol version 10 withdrawals don't depend on the DRep delegation
+    context.protocol_parameters.protocol_version = (9, 1);
+    let result = BalanceValidator::new(&tx_body, &context).validate();
+    assert!(!result
+        .warnings
+        .iter()
+        .any(|warning| will_be_dropped(&warning.warning)));
+
+    // A registered DRep keeps the delegation
+    context.protocol_parameters.protocol_version = (10, 0);
+    context.drep_contexts[0].is_registered = true;
+    let result = BalanceValidator::new(&tx_body, &context).validate();
+    assert!(!result
+        .warnings
+        .iter()
+        .any(|warning| will_be_dropped(&warning.warning)));
+}
@@ -0,0 +1,151 @@
+use crate::validators::block_validator::{validate_block, BlockValidationError};
+use crate::validators::context_update::apply_transaction_to_context;
+use crate::validators::common::NetworkType;
+use crate::validators::helpers::credential_to_bech32_reward_address;
+use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+use cryptoxide::hashing::blake2b_256;
+use pallas_codec::minicbor;
+use pallas_codec::utils::Bytes;
+use pallas_primitives::babbage::{Header, HeaderBody, OperationalCert};
+use pallas_primitives::VrfCert;
+
+fn test_context() -> ValidationInputContext {
+    ValidationInputContext::new(
+        vec![],
+        get_test_protocol_parameters(),
+        0,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+fn block_hex(body_hash: [u8; 32], body_size: u64, invalid_transactions: &[u8]) -> String {
+    let header = Header {
+        header_body: HeaderBody {
+            block_number: 1,
+            slot: 1000,
+            prev_hash: None,
+            issuer_vkey: Bytes::from(vec![0; 32]),
+            vrf_vkey: Bytes::from(vec![0; 32]),
+            vrf_result: VrfCert(Bytes::from(vec![0; 64]), Bytes::from(vec![0; 80])),
+            block_body_size: body_size,
+            block_body_hash: body_hash.into(),
+            operational_cert: OperationalCert {
+                operational_cert_hot_vkey: Bytes::from(vec![0; 32]),
+                operational_cert_sequence_number: 0,
+                operational_cert_kes_period: 0,
+                operational_cert_sigma: Bytes::from(vec![0; 64]),
+            },
+            protocol_version: (10, 0),
+        },
+        body_signature: Bytes::from(vec![0; 448]),
+    };
+    let mut block = vec![0x82, 0x07, 0x85];
+    block.extend(minicbor::to_vec(&header).unwrap());
+    // no transactions, no witness sets, no auxiliary data
+    block.extend([0x80, 0x80, 0xa0]);
+    block.extend(invalid_transactions);
+    hex::encode(block)
+}
+
+fn empty_body_hash(invalid_transactions: &[u8]) -> [u8; 32] {
+    let mut hashes = Vec::new();
+    hashes.extend(blake2b_256(&[0x80]));
+    hashes.extend(blake2b_256(&[0x80]));
+    hashes.extend(blake2b_256(&[0xa0]));
+    hashes.extend(blake2b_256(invalid_transactions));
+    blake2b_256(&hashes)
+}
+
+#[test]
+fn block_body_checks() {
+    let invalid_transactions = [0x81, 0x03];
+    let body_size = 3 + invalid_transactions.len() as u64;
+    let block = block_hex(
+        empty_body_hash(&invalid_transactions),
+        body_size,
+        &invalid_transactions,
+    );
+    let result = validate_block(&block, test_context()).unwrap();
+    assert_eq!(result.slot, 1000);
+    assert!(result.transactions.is_empty());
+    assert_eq!(result.block_errors.len(), 1);
+    assert!(matches!(
+        result.block_errors[0],
+        BlockValidationError::InvalidTransactionIndexOutOfRange {
+            index: 3,
+            tx_count: 0
+        }
+    ));
+
+    let block = block_hex([0; 32], body_size + 1, &invalid_transactions);
+    let result = validate_block(&block, test_context()).unwrap();
+    assert!(result
+        .block_errors
+        .iter()
+        .any(|error| matches!(error, BlockValidationError::BlockBodyHashMismatch { .. })));
+    assert!(result
+        .block_errors
+        .iter()
+        .any(|error| matches!(error, BlockValidationError::WrongBlockBodySize { .. })));
+}
+
+#[test]
+fn transaction_effects_are_applied_to_context() {
+    let credential = csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from([1; 28]));
+    let input = csl::TransactionInput::new(&csl::TransactionHash::from([2; 32]), 0);
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(&input);
+    let mut outputs = csl::TransactionOutputs::new();
+    outputs.add(&csl::TransactionOutput::new(
+        &csl::RewardAddress::new(1, &credential).to_address(),
+        &csl::Value::new(&csl::BigNum::from(5_000_000u64)),
+    ));
+    let mut certs = csl::Certificates::new();
+    certs.add(&csl::Certificate::new_stake_registration(
+        &csl::StakeRegistration::new_with_explicit_deposit(
+            &credential,
+            &csl::BigNum::from(2_000_000u64),
+        ),
+    ));
+    let mut tx_body = csl::TransactionBody::new_tx_body(&inputs, &outputs, &csl::Coin::zero());
+    tx_body.set_certs(&certs);
+    let tx = csl::Transaction::new(&tx_body, &csl::TransactionWitnessSet::new(), None);
+    let tx = csl::FixedTransaction::from_bytes(tx.to_bytes()).unwrap();
+
+    let mut context = test_context();
+    apply_transaction_to_context(&mut context, &tx, true);
+
+    assert_eq!(context.utxo_set.len(), 1);
+    assert_eq!(
+        context.utxo_set[0].utxo.input.tx_hash,
+        tx.transaction_hash().to_hex()
+    );
+    assert_eq!(
+        context.utxo_set[0]
+            .utxo
+            .output
+            .find_ada_asset()
+            .unwrap()
+            .quantity,
+        "5000000"
+    );
+    let account = context
+        .find_account_context(&credential_to_bech32_reward_address(
+            &credential,
+            &NetworkType::Mainnet,
+        ))
+        .unwrap();
+    assert!(account.is_registered);
+    assert_eq!(account.payed_deposit, Some(2_000_000));
+}
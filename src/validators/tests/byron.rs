@@ -0,0 +1,237 @@
+use crate::byron;
+use crate::check_signatures::check_block_or_tx_signatures;
+use crate::csl_decoders::universal_decoder::decode_specific_type;
+use crate::js_value::empty_js_value;
+use cardano_serialization_lib as csl;
+use cryptoxide::ed25519;
+use cryptoxide::hashing::blake2b_256;
+use pallas_codec::minicbor::{self, bytes::ByteVec, Encoder};
+use pallas_codec::utils::{CborWrap, EmptyMap, MaybeIndefArray, TagWrap};
+use pallas_crypto::hash::Hash;
+use pallas_primitives::byron::{
+    Block, BlockBody, BlockCons, BlockHead, BlockHeadEx, BlockProof, BlockSig, EbBlock, EbbCons,
+    EbbHead, SlotId, Ssc, SscProof, Twit, Tx, TxIn, TxOut, TxPayload,
+};
+
+const MAINNET_PROTOCOL_MAGIC: u32 = 764824073;
+
+/// Builds a signed mainnet Byron transaction spending one input to an Icarus address
+fn signed_byron_tx(signature_seed: u8) -> (String, [u8; 32]) {
+    let root_key = csl::Bip32PrivateKey::from_bip39_entropy(&[7; 16], &[]);
+    let icarus_address =
+        csl::ByronAddress::icarus_from_key(&root_key.to_public(), MAINNET_PROTOCOL_MAGIC);
+    let tx = Tx {
+        inputs: MaybeIndefArray::Def(vec![TxIn::Variant0(CborWrap((Hash::new([1; 32]), 0)))]),
+        outputs: MaybeIndefArray::Def(vec![TxOut {
+            address: minicbor::decode(&icarus_address.to_bytes()).unwrap(),
+            amount: 1_000_000,
+        }]),
+        attributes: EmptyMap,
+    };
+    let tx_hash = blake2b_256(&minicbor::to_vec(&tx).unwrap());
+
+    let mut message = Encoder::new(vec![0x01]);
+    message
+        .u32(MAINNET_PROTOCOL_MAGIC)
+        .unwrap()
+        .bytes(&tx_hash)
+        .unwrap();
+    let (secret_key, public_key) = ed25519::keypair(&[signature_seed; 32]);
+    let signing_key = if signature_seed == 1 {
+        secret_key
+    } else {
+        ed25519::keypair(&[1; 32]).0
+    };
+    let signature = ed25519::signature(&message.into_writer(), &signing_key);
+    // Extended public key: the key followed by a chain code
+    let extended_public_key = [&public_key[..], &[0; 32]].concat();
+
+    let payload = TxPayload {
+        transaction: tx,
+        witness: MaybeIndefArray::Def(vec![Twit::PkWitness(CborWrap((
+            ByteVec::from(extended_public_key),
+            ByteVec::from(signature.to_vec()),
+        )))]),
+    };
+    (hex::encode(minicbor::to_vec(&payload).unwrap()), tx_hash)
+}
+
+#[test]
+fn byron_transaction_decoding_and_witnesses() {
+    let (tx_hex, tx_hash) = signed_byron_tx(1);
+
+    let decoded = decode_specific_type(&tx_hex, "ByronTransaction", empty_js_value()).unwrap();
+    let decoded: serde_json::Value = serde_json::from_str(&decoded.as_string().unwrap()).unwrap();
+    assert_eq!(decoded["transaction_hash"], hex::encode(tx_hash));
+    assert_eq!(decoded["transaction"]["inputs"][0]["index"], 0);
+    assert_eq!(decoded["transaction"]["outputs"][0]["amount"], "1000000");
+    assert!(decoded["transaction"]["outputs"][0]["address"]["address_base58"].is_string());
+    assert_eq!(decoded["witnesses"][0]["type"], "PkWitness");
+
+    // CSL can't decode the transaction, "Transaction" falls back to the Byron decoder
+    assert!(decode_specific_type(&tx_hex, "Transaction", empty_js_value()).is_ok());
+
+    let payload_bytes = hex::decode(&tx_hex).unwrap();
+    let payload = byron::decode_tx_payload(&payload_bytes).unwrap();
+    assert!(byron::verify_witness(
+        &payload.witness[0],
+        &tx_hash,
+        MAINNET_PROTOCOL_MAGIC
+    ));
+    assert!(!byron::verify_witness(&payload.witness[0], &tx_hash, 1));
+
+    let check = check_block_or_tx_signatures(&tx_hex).unwrap();
+    let check: serde_json::Value = serde_json::from_str(&check.as_string().unwrap()).unwrap();
+    assert_eq!(check["valid"], true);
+
+    // Signed with a key other than the one in the witness
+    let (tx_hex, _) = signed_byron_tx(2);
+    let check = check_block_or_tx_signatures(&tx_hex).unwrap();
+    let check: serde_json::Value = serde_json::from_str(&check.as_string().unwrap()).unwrap();
+    assert_eq!(check["valid"], false);
+    assert_eq!(check["invalidVkeyWitnesses"].as_array().unwrap().len(), 1);
+}
+
+/// Hash of the mainnet Byron genesis, the previous block of the first epoch boundary block
+const MAINNET_GENESIS_HASH: &str =
+    "5f20df933584822601f9e3f8c024eb5eb252fe8cefb24d1317dc3d432e940ebb";
+
+/// Encodes the block in the `[era, block]` wrapper used by the node
+fn wrap_block<T: minicbor::Encode<()>>(era: u8, block: &T) -> Vec<u8> {
+    let mut encoder = Encoder::new(Vec::new());
+    encoder.array(2).unwrap().u8(era).unwrap();
+    encoder.encode(block).unwrap();
+    encoder.into_writer()
+}
+
+/// Independently computed block hash: blake2b-256 of `[era, header]`
+fn expected_block_hash<T: minicbor::Encode<()>>(era: u8, header: &T) -> [u8; 32] {
+    blake2b_256(&wrap_block(era, header))
+}
+
+fn epoch_boundary_block() -> EbBlock {
+    EbBlock {
+        header: EbbHead {
+            protocol_magic: MAINNET_PROTOCOL_MAGIC,
+            prev_block: MAINNET_GENESIS_HASH.parse().unwrap(),
+            body_proof: Hash::new([2; 32]),
+            consensus_data: EbbCons {
+                epoch_id: 0,
+                difficulty: MaybeIndefArray::Def(vec![0]),
+            },
+            extra_data: (EmptyMap,),
+        },
+        body: MaybeIndefArray::Def(vec![Hash::new([3; 28]), Hash::new([4; 28])]),
+        extra: MaybeIndefArray::Def(vec![]),
+    }
+}
+
+/// A main block of epoch 10 holding the given transaction
+fn main_block(tx_hex: &str) -> Block {
+    let payload: TxPayload = minicbor::decode(&hex::decode(tx_hex).unwrap()).unwrap();
+    Block {
+        header: BlockHead {
+            protocol_magic: MAINNET_PROTOCOL_MAGIC,
+            prev_block: Hash::new([5; 32]),
+            body_proof: BlockProof {
+                tx_proof: (1, Hash::new([6; 32]), Hash::new([7; 32])),
+                ssc_proof: SscProof::Variant3(Hash::new([8; 32])),
+                dlg_proof: Hash::new([9; 32]),
+                upd_proof: Hash::new([10; 32]),
+            },
+            consensus_data: BlockCons(
+                SlotId {
+                    epoch: 10,
+                    slot: 42,
+                },
+                ByteVec::from(vec![11; 64]),
+                MaybeIndefArray::Def(vec![216_042]),
+                BlockSig::Signature(ByteVec::from(vec![12; 64])),
+            ),
+            extra_data: BlockHeadEx {
+                block_version: (0, 2, 0),
+                software_version: ("cardano-sl".to_string(), 1),
+                attributes: Some(EmptyMap),
+                extra_proof: Hash::new([13; 32]),
+            },
+        },
+        body: BlockBody {
+            tx_payload: MaybeIndefArray::Def(vec![payload]),
+            ssc_payload: Ssc::Variant3(TagWrap::new(MaybeIndefArray::Def(vec![]))),
+            dlg_payload: MaybeIndefArray::Def(vec![]),
+            // No update proposal and no votes
+            upd_payload: minicbor::decode(&[0x82, 0x80, 0x80]).unwrap(),
+        },
+        extra: MaybeIndefArray::Def(vec![EmptyMap]),
+    }
+}
+
+#[test]
+fn byron_epoch_boundary_block() {
+    let block = epoch_boundary_block();
+    let expected_hash = expected_block_hash(0, &block.header);
+    let wrapped = wrap_block(0, &block);
+    let unwrapped = minicbor::to_vec(&block).unwrap();
+
+    for bytes in [&wrapped, &unwrapped] {
+        let decoded = byron::ByronBlock::decode(bytes).unwrap();
+        assert!(matches!(decoded, byron::ByronBlock::EpochBoundary(_)));
+        assert_eq!(decoded.hash(), expected_hash);
+        assert_eq!(decoded.protocol_magic(), MAINNET_PROTOCOL_MAGIC);
+        assert!(decoded.transactions().is_empty());
+    }
+
+    // An epoch boundary block in the main block wrapper
+    assert!(byron::ByronBlock::decode(&wrap_block(1, &block)).is_err());
+
+    let decoded =
+        decode_specific_type(&hex::encode(&wrapped), "ByronBlock", empty_js_value()).unwrap();
+    let decoded: serde_json::Value = serde_json::from_str(&decoded.as_string().unwrap()).unwrap();
+    assert_eq!(decoded["block_type"], "EpochBoundary");
+    assert_eq!(decoded["block_hash"], hex::encode(expected_hash));
+    assert_eq!(decoded["prev_block"], MAINNET_GENESIS_HASH);
+    assert_eq!(decoded["stakeholders"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn byron_main_block() {
+    let (tx_hex, tx_hash) = signed_byron_tx(1);
+    let block = main_block(&tx_hex);
+    let expected_hash = expected_block_hash(1, &block.header);
+    let wrapped = wrap_block(1, &block);
+
+    for bytes in [&wrapped, &minicbor::to_vec(&block).unwrap()] {
+        let decoded = byron::ByronBlock::decode(bytes).unwrap();
+        assert!(matches!(decoded, byron::ByronBlock::Main(_)));
+        assert_eq!(decoded.hash(), expected_hash);
+        assert_eq!(decoded.transactions().len(), 1);
+        assert_eq!(byron::tx_hash(&decoded.transactions()[0]), tx_hash);
+    }
+    // The main and epoch boundary hashes differ in the era prefix only
+    assert_ne!(expected_hash, expected_block_hash(0, &block.header));
+
+    let decoded =
+        decode_specific_type(&hex::encode(&wrapped), "ByronBlock", empty_js_value()).unwrap();
+    let decoded: serde_json::Value = serde_json::from_str(&decoded.as_string().unwrap()).unwrap();
+    assert_eq!(decoded["block_type"], "Main");
+    assert_eq!(decoded["block_hash"], hex::encode(expected_hash));
+    assert_eq!(
+        (decoded["epoch"].clone(), decoded["slot"].clone()),
+        (10.into(), 42.into())
+    );
+    assert_eq!(
+        decoded["transactions"][0]["transaction_hash"],
+        hex::encode(tx_hash)
+    );
+
+    let check = check_block_or_tx_signatures(&hex::encode(&wrapped)).unwrap();
+    let check: serde_json::Value = serde_json::from_str(&check.as_string().unwrap()).unwrap();
+    assert_eq!(check["valid"], true);
+
+    // A block holding a transaction with a bad witness is invalid
+    let (bad_tx_hex, _) = signed_byron_tx(2);
+    let check = check_block_or_tx_signatures(&hex::encode(wrap_block(1, &main_block(&bad_tx_hex))))
+        .unwrap();
+    let check: serde_json::Value = serde_json::from_str(&check.as_string().unwrap()).unwrap();
+    assert_eq!(check["valid"], false);
+}
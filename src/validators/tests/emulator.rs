@@ -0,0 +1,215 @@
+use crate::common::{Asset, TxInput, TxOutput, UTxO};
+use crate::validators::common::{GovernanceActionId, NetworkType};
+use crate::validators::emulator::{EmulatorParameters, EmulatorPool, LedgerEmulator};
+use crate::validators::input_contexts::{
+    AccountInputContext, PoolInputContext, UtxoInputContext, ValidationInputContext,
+};
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+
+const GOV_ACTION_DEPOSIT: u64 = 100_000_000_000;
+const POOL_DEPOSIT: u64 = 500_000_000;
+// A real mainnet slot of epoch 521 and the first slot of epoch 522
+const MAINNET_SLOT: u64 = 140_000_000;
+const MAINNET_EPOCH: u64 = 521;
+const MAINNET_NEXT_EPOCH_SLOT: u64 = 140_140_800;
+
+fn signed_tx(tx_body: &csl::TransactionBody, keys: &[&csl::PrivateKey]) -> String {
+    let tx_hash = csl::FixedTransaction::new_from_body_bytes(&tx_body.to_bytes())
+        .unwrap()
+        .transaction_hash();
+    let mut vkey_witnesses = csl::Vkeywitnesses::new();
+    for key in keys {
+        vkey_witnesses.add(&csl::make_vkey_witness(&tx_hash, key));
+    }
+    let mut witness_set = csl::TransactionWitnessSet::new();
+    witness_set.set_vkeys(&vkey_witnesses);
+    csl::Transaction::new(tx_body, &witness_set, None).to_hex()
+}
+
+#[test]
+fn epoch_boundary_processing() {
+    let payment_key = csl::PrivateKey::generate_ed25519().unwrap();
+    let pool_key = csl::PrivateKey::generate_ed25519().unwrap();
+    let credential = csl::Credential::from_keyhash(&payment_key.to_public().hash());
+    let address = csl::EnterpriseAddress::new(1, &credential).to_address();
+    let reward_address = csl::RewardAddress::new(1, &credential);
+    let reward_bech32 = reward_address.to_address().to_bech32(None).unwrap();
+    let pool_id = pool_key.to_public().hash();
+    let input = csl::TransactionInput::new(&csl::TransactionHash::from([7; 32]), 0);
+
+    let context = ValidationInputContext::new(
+        vec![UtxoInputContext {
+            utxo: UTxO {
+                input: TxInput {
+                    tx_hash: input.transaction_id().to_hex(),
+                    output_index: 0,
+                },
+                output: TxOutput {
+                    address: address.to_bech32(None).unwrap(),
+                    amount: vec![Asset {
+                        unit: "lovelace".to_string(),
+                        quantity: (GOV_ACTION_DEPOSIT + 10_000_000).to_string(),
+                    }],
+                    data_hash: None,
+                    plutus_data: None,
+                    script_ref: None,
+                    script_hash: None,
+                },
+            },
+            is_spent: false,
+        }],
+        get_test_protocol_parameters(),
+        MAINNET_SLOT,
+        vec![AccountInputContext {
+            bech32_address: reward_bech32.clone(),
+            is_registered: true,
+            payed_deposit: Some(2_000_000),
+            delegated_to_drep: None,
+            delegated_to_pool: Some(pool_id.to_hex()),
+            balance: Some(0),
+            registration_epoch: Some(0),
+        }],
+        vec![],
+        vec![PoolInputContext {
+            pool_id: pool_id.to_hex(),
+            is_registered: true,
+            retirement_epoch: None,
+            reward_account: None,
+        }],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    );
+    let mut emulator = LedgerEmulator::new(context, EmulatorParameters::default());
+    assert_eq!(emulator.epoch(), MAINNET_EPOCH);
+    emulator.pools.push(EmulatorPool {
+        pool_id: pool_id.to_hex(),
+        reward_address: reward_bech32.clone(),
+        deposit: POOL_DEPOSIT,
+    });
+
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(&input);
+    let mut outputs = csl::TransactionOutputs::new();
+    outputs.add(&csl::TransactionOutput::new(
+        &address,
+        &csl::Value::new(&csl::BigNum::from(8_000_000u64)),
+    ));
+    let mut tx_body =
+        csl::TransactionBody::new_tx_body(&inputs, &outputs, &csl::Coin::from(2_000_000u64));
+    let mut certs = csl::Certificates::new();
+    certs.add(&csl::Certificate::new_pool_retirement(
+        &csl::PoolRetirement::new(&pool_id, MAINNET_EPOCH as u32 + 1),
+    ));
+    tx_body.set_certs(&certs);
+    let mut update = csl::ProtocolParamUpdate::new();
+    update.set_max_tx_size(20_000);
+    let mut proposals = csl::VotingProposals::new();
+    proposals.add(&csl::VotingProposal::new(
+        &csl::GovernanceAction::new_parameter_change_action(&csl::ParameterChangeAction::new(
+            &update,
+        )),
+        &csl::Anchor::new(
+            &csl::URL::new("https://example.com".to_string()).unwrap(),
+            &csl::AnchorDataHash::from([0; 32]),
+        ),
+        &reward_address,
+        &csl::Coin::from(GOV_ACTION_DEPOSIT),
+    ));
+    tx_body.set_voting_proposals(&proposals);
+    let tx = signed_tx(&tx_body, &[&payment_key, &pool_key]);
+
+    let submitted = emulator.submit_transaction(&tx).unwrap();
+    assert!(submitted.accepted, "{:?}", submitted.result.errors);
+    assert_eq!(emulator.proposals.len(), 1);
+    let action_id: GovernanceActionId = emulator.proposals[0].action_id.clone();
+    assert!(emulator.ratify_gov_action(&action_id));
+
+    // Submitting the same transaction again spends an already spent input
+    assert!(!emulator.submit_transaction(&tx).unwrap().accepted);
+
+    let boundaries = emulator.advance_to_slot(MAINNET_NEXT_EPOCH_SLOT + 1);
+    assert_eq!(boundaries.len(), 1);
+    assert_eq!(boundaries[0].epoch, MAINNET_EPOCH + 1);
+    assert_eq!(emulator.slot(), MAINNET_NEXT_EPOCH_SLOT + 1);
+    assert_eq!(boundaries[0].enacted_gov_actions, vec![action_id]);
+    assert_eq!(boundaries[0].retired_pools, vec![pool_id.to_hex()]);
+    assert_eq!(
+        emulator.context.protocol_parameters.max_transaction_size,
+        20_000
+    );
+    assert!(emulator.context.gov_action_contexts.is_empty());
+    assert!(!emulator.context.pool_contexts[0].is_registered);
+    let account = &emulator.context.account_contexts[0];
+    assert_eq!(account.balance, Some(GOV_ACTION_DEPOSIT + POOL_DEPOSIT));
+    assert_eq!(account.delegated_to_pool, None);
+
+    let restored = LedgerEmulator::from_snapshot(&emulator.snapshot().unwrap()).unwrap();
+    assert_eq!(restored.slot(), emulator.slot());
+    assert_eq!(
+        restored.context.account_contexts,
+        emulator.context.account_contexts
+    );
+}
+
+#[test]
+fn initial_pool_retirement_on_preview() {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![3; 28]).unwrap());
+    let reward_bech32 = csl::RewardAddress::new(0, &credential)
+        .to_address()
+        .to_bech32(None)
+        .unwrap();
+    let pool = |byte: u8, reward_account: Option<String>| PoolInputContext {
+        pool_id: hex::encode([byte; 28]),
+        is_registered: true,
+        retirement_epoch: Some(1),
+        reward_account,
+    };
+    let context = ValidationInputContext::new(
+        vec![],
+        get_test_protocol_parameters(),
+        1000,
+        vec![AccountInputContext {
+            bech32_address: reward_bech32.clone(),
+            is_registered: true,
+            payed_deposit: Some(2_000_000),
+            delegated_to_drep: None,
+            delegated_to_pool: None,
+            balance: Some(0),
+            registration_epoch: Some(0),
+        }],
+        vec![],
+        vec![pool(1, Some(reward_bech32.clone())), pool(2, None)],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Preview,
+        vec![],
+        vec![],
+        vec![],
+    );
+    let mut emulator = LedgerEmulator::new(context, EmulatorParameters::default());
+
+    // Preview epochs are one day long
+    let boundaries = emulator.advance_slots(86_400);
+    assert_eq!(boundaries.len(), 1);
+    assert_eq!(boundaries[0].epoch, 1);
+    assert_eq!(boundaries[0].retired_pools.len(), 2);
+
+    // Pools registered before the emulator started refund the current pool deposit
+    assert_eq!(
+        emulator.context.account_contexts[0].balance,
+        Some(POOL_DEPOSIT)
+    );
+    assert_eq!(boundaries[0].refunds[0].reward_address, reward_bech32);
+    assert!(!boundaries[0].refunds[0].to_treasury);
+    // Without a reward account the deposit goes to the treasury
+    assert!(boundaries[0].refunds[1].to_treasury);
+    assert_eq!(emulator.context.treasury_value, POOL_DEPOSIT);
+}
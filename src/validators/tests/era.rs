@@ -0,0 +1,222 @@
+use crate::common::{Asset, SubCoin, TxInput, TxOutput, UTxO};
+use crate::validators::common::{NetworkType, TransactionEra};
+use crate::validators::input_contexts::{UtxoInputContext, ValidationInputContext};
+use crate::validators::phase_1::errors::Phase1Error;
+use crate::validators::phase_1::validation::fee::FeeValidator;
+use crate::validators::phase_1::validation::{CollateralValidator, EraValidator};
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+
+fn context(protocol_major: u32, era: Option<TransactionEra>) -> ValidationInputContext {
+    let mut protocol_parameters = get_test_protocol_parameters();
+    protocol_parameters.protocol_version = (protocol_major, 0);
+    let mut context = ValidationInputContext::new(
+        vec![],
+        protocol_parameters,
+        0,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    );
+    context.era = era;
+    context
+}
+
+fn era_errors(tx: &csl::Transaction, context: &ValidationInputContext) -> Vec<String> {
+    EraValidator::new(&tx.to_bytes(), &tx.body(), &tx.witness_set(), context)
+        .validate()
+        .errors
+        .iter()
+        .map(|error| match &error.error {
+            Phase1Error::FeatureNotSupportedInEra { feature, .. } => feature.clone(),
+            Phase1Error::TransactionNotDecodableInEra { .. } => "not decodable".to_string(),
+            other => panic!("unexpected error {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn era_feature_availability() {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![5; 28]).unwrap());
+    let mut certs = csl::Certificates::new();
+    certs.add(&csl::Certificate::new_stake_registration(
+        &csl::StakeRegistration::new_with_explicit_deposit(
+            &credential,
+            &csl::BigNum::from(2_000_000u64),
+        ),
+    ));
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::from(200_000u64),
+    );
+    tx_body.set_validity_start_interval_bignum(&csl::BigNum::from(10u64));
+    tx_body.set_certs(&certs);
+    let tx = csl::Transaction::new(&tx_body, &csl::TransactionWitnessSet::new(), None);
+
+    // Protocol version 8 is Babbage: the deposit carrying registration is a Conway certificate
+    let babbage = context(8, None);
+    assert_eq!(babbage.transaction_era(), TransactionEra::Babbage);
+    assert_eq!(
+        era_errors(&tx, &babbage),
+        vec!["Registration certificate with deposit".to_string()]
+    );
+
+    // An explicit era takes precedence over the protocol version
+    assert_eq!(
+        era_errors(&tx, &context(8, Some(TransactionEra::Shelley))),
+        vec![
+            "Validity interval start".to_string(),
+            "Registration certificate with deposit".to_string(),
+        ]
+    );
+    assert!(era_errors(&tx, &context(9, None)).is_empty());
+
+    // Conway no longer accepts MIR certificates
+    let mut mir_certs = csl::Certificates::new();
+    mir_certs.add(&csl::Certificate::new_move_instantaneous_rewards_cert(
+        &csl::MoveInstantaneousRewardsCert::new(&csl::MoveInstantaneousReward::new_to_other_pot(
+            csl::MIRPot::Reserves,
+            &csl::BigNum::from(1u64),
+        )),
+    ));
+    tx_body.set_certs(&mir_certs);
+    let tx = csl::Transaction::new(&tx_body, &csl::TransactionWitnessSet::new(), None);
+    // CSL tags the inputs set with 258, which only Conway decoders accept
+    assert_eq!(era_errors(&tx, &babbage), vec!["not decodable".to_string()]);
+    assert_eq!(
+        era_errors(&tx, &context(9, None)),
+        vec!["Move instantaneous rewards".to_string()]
+    );
+}
+
+#[test]
+fn alonzo_multi_asset_collateral() {
+    let collateral_input = csl::TransactionInput::new(&csl::TransactionHash::from([9; 32]), 0);
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![5; 28]).unwrap());
+    let address = csl::EnterpriseAddress::new(1, &credential).to_address();
+    let policy_id = "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209";
+    let utxo: UTxO = serde_json::from_str(&format!(
+        "{{\"input\":{{\"outputIndex\":0,\"txHash\":\"{}\"}},\"output\":{{\"address\":\"{}\",\"amount\":[{{\"unit\":\"lovelace\",\"quantity\":\"5000000\"}},{{\"unit\":\"{}\",\"quantity\":\"1\"}}]}}}}",
+        collateral_input.transaction_id().to_hex(),
+        address.to_bech32(None).unwrap(),
+        policy_id
+    ))
+    .unwrap();
+
+    // The collateral return takes the token back, so only ADA is collected
+    let mut collateral = csl::TransactionInputs::new();
+    collateral.add(&collateral_input);
+    let mut assets = csl::Assets::new();
+    assets.insert(
+        &csl::AssetName::new(vec![]).unwrap(),
+        &csl::BigNum::from(1u64),
+    );
+    let mut multiasset = csl::MultiAsset::new();
+    multiasset.insert(&csl::ScriptHash::from_hex(policy_id).unwrap(), &assets);
+    let mut return_value = csl::Value::new(&csl::Coin::from(4_700_000u64));
+    return_value.set_multiasset(&multiasset);
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::from(200_000u64),
+    );
+    tx_body.set_collateral(&collateral);
+    tx_body.set_collateral_return(&csl::TransactionOutput::new(&address, &return_value));
+    tx_body.set_total_collateral(&csl::Coin::from(300_000u64));
+    let mut redeemers = csl::Redeemers::new();
+    redeemers.add(&csl::Redeemer::new(
+        &csl::RedeemerTag::new_spend(),
+        &csl::BigNum::zero(),
+        &csl::PlutusData::new_integer(&csl::BigInt::from(0)),
+        &csl::ExUnits::new(&csl::BigNum::from(1u64), &csl::BigNum::from(1u64)),
+    ));
+    let mut witness_set = csl::TransactionWitnessSet::new();
+    witness_set.set_redeemers(&redeemers);
+
+    let collateral_errors = |era: TransactionEra| {
+        let mut context = context(9, Some(era));
+        context.utxo_set = vec![UtxoInputContext {
+            utxo: utxo.clone(),
+            is_spent: false,
+        }];
+        CollateralValidator::new(&tx_body, &witness_set, &context)
+            .validate()
+            .errors
+            .into_iter()
+            .map(|error| error.error)
+            .collect::<Vec<_>>()
+    };
+
+    let errors = collateral_errors(TransactionEra::Alonzo);
+    assert!(
+        errors.iter().any(|error| matches!(
+            error,
+            Phase1Error::CollateralInputContainsNonAdaAssets { .. }
+        )),
+        "{:?}",
+        errors
+    );
+    let errors = collateral_errors(TransactionEra::Babbage);
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn reference_script_fee_from_conway() {
+    let input = csl::TransactionInput::new(&csl::TransactionHash::from([4; 32]), 0);
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![5; 28]).unwrap());
+    let address = csl::EnterpriseAddress::new(1, &credential).to_address();
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(&input);
+    let tx_body = csl::TransactionBody::new_tx_body(
+        &inputs,
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::from(200_000u64),
+    );
+
+    let reference_scripts_fee = |protocol_major: u32| {
+        let mut context = context(protocol_major, None);
+        context.protocol_parameters.reference_script_cost_per_byte = SubCoin {
+            numerator: 15,
+            denominator: 1,
+        };
+        context.utxo_set = vec![UtxoInputContext {
+            utxo: UTxO {
+                input: TxInput {
+                    tx_hash: input.transaction_id().to_hex(),
+                    output_index: 0,
+                },
+                output: TxOutput {
+                    address: address.to_bech32(None).unwrap(),
+                    amount: vec![Asset {
+                        unit: "lovelace".to_string(),
+                        quantity: "5000000".to_string(),
+                    }],
+                    data_hash: None,
+                    plutus_data: None,
+                    // 1000 bytes of script reference
+                    script_ref: Some("82".repeat(1000)),
+                    script_hash: None,
+                },
+            },
+            is_spent: false,
+        }];
+        FeeValidator::new(200, &tx_body, &csl::TransactionWitnessSet::new(), &context)
+            .unwrap()
+            .fee_decomposition
+            .reference_scripts_fee
+    };
+
+    assert_eq!(reference_scripts_fee(8), 0);
+    assert_eq!(reference_scripts_fee(9), 15_000);
+}
@@ -0,0 +1,114 @@
+use crate::check_signatures::{check_header_signatures, HeaderCheckParams};
+use crate::header_crypto::{verify_sum_kes, vrf_proof_to_hash, vrf_verify, CARDANO_KES_DEPTH};
+use cryptoxide::ed25519;
+use cryptoxide::hashing::blake2b_256;
+use pallas_codec::minicbor;
+use pallas_codec::utils::Bytes;
+use pallas_primitives::babbage::{HeaderBody, OperationalCert, PseudoHeader};
+use pallas_primitives::VrfCert;
+
+/// Builds a sum composition KES tree from 2^depth Ed25519 leaves, returns the root key and a
+/// signer producing the signature for a given period.
+fn kes_tree(depth: u32) -> (Vec<u8>, impl Fn(u64, &[u8]) -> Vec<u8>) {
+    let leaves: Vec<([u8; 64], [u8; 32])> = (0..1u64 << depth)
+        .map(|i| ed25519::keypair(&[i as u8 + 1; 32]))
+        .collect();
+    let mut levels = vec![leaves.iter().map(|(_, pk)| pk.to_vec()).collect::<Vec<_>>()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| blake2b_256(&[pair[0].clone(), pair[1].clone()].concat()).to_vec())
+            .collect();
+        levels.push(next);
+    }
+    let root = levels.last().unwrap()[0].clone();
+    let sign = move |period: u64, message: &[u8]| {
+        let mut signature = ed25519::signature(message, &leaves[period as usize].0).to_vec();
+        for (level, keys) in levels.iter().take(depth as usize).enumerate() {
+            let index = (period >> level) as usize & !1;
+            signature.extend_from_slice(&keys[index]);
+            signature.extend_from_slice(&keys[index + 1]);
+        }
+        signature
+    };
+    (root, sign)
+}
+
+#[test]
+fn vrf_draft03_test_vector() {
+    // draft-irtf-cfrg-vrf-03, ECVRF-ED25519-SHA512-Elligator2, empty alpha
+    let public_key =
+        hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a").unwrap();
+    let mut proof = hex::decode("b6b4699f87d56126c9117a7da55bd0085246f4c56dbc95d20172612e9d38e8d7ca65e573a126ed88d4e30a46f80a666854d675cf3ba81de0de043c3774f061560f55edc256a787afe701677c0f602900").unwrap();
+    let output = hex::decode("5b49b554d05c0cd5a5325376b3387de59d924fd1e13ded44648ab33c21349a603f25b84ec5ed887995b33da5e3bfcb87cd2f64521c4c62cf825cffabbe5d31cc").unwrap();
+
+    assert_eq!(vrf_proof_to_hash(&proof).unwrap().to_vec(), output);
+    assert_eq!(
+        vrf_verify(&public_key, &[], &proof).unwrap().to_vec(),
+        output
+    );
+    assert!(vrf_verify(&public_key, &[0x72], &proof).is_none());
+    proof[40] ^= 1;
+    assert!(vrf_verify(&public_key, &[], &proof).is_none());
+}
+
+#[test]
+fn sum_kes_signature() {
+    let (root, sign) = kes_tree(3);
+    let signature = sign(5, b"message");
+    assert!(verify_sum_kes(3, &root, 5, b"message", &signature));
+    assert!(!verify_sum_kes(3, &root, 4, b"message", &signature));
+    assert!(!verify_sum_kes(3, &root, 5, b"other message", &signature));
+}
+
+#[test]
+fn block_header_signatures() {
+    let (hot_vkey, sign) = kes_tree(CARDANO_KES_DEPTH);
+    let cold_key = ed25519::keypair(&[42; 32]);
+    let (sequence_number, opcert_kes_period) = (3u64, 100u64);
+    let mut opcert_message = hot_vkey.clone();
+    opcert_message.extend_from_slice(&sequence_number.to_be_bytes());
+    opcert_message.extend_from_slice(&opcert_kes_period.to_be_bytes());
+
+    let slot = 129600 * 102 + 10;
+    let header_body = HeaderBody {
+        block_number: 1,
+        slot,
+        prev_hash: None,
+        issuer_vkey: Bytes::from(cold_key.1.to_vec()),
+        vrf_vkey: Bytes::from(vec![0; 32]),
+        vrf_result: VrfCert(Bytes::from(vec![0; 64]), Bytes::from(vec![0; 80])),
+        block_body_size: 0,
+        block_body_hash: [0; 32].into(),
+        operational_cert: OperationalCert {
+            operational_cert_hot_vkey: Bytes::from(hot_vkey),
+            operational_cert_sequence_number: sequence_number,
+            operational_cert_kes_period: opcert_kes_period,
+            operational_cert_sigma: Bytes::from(
+                ed25519::signature(&opcert_message, &cold_key.0).to_vec(),
+            ),
+        },
+        protocol_version: (10, 0),
+    };
+    let body_bytes = minicbor::to_vec(&header_body).unwrap();
+    let header = PseudoHeader {
+        header_body,
+        body_signature: Bytes::from(sign(2, &body_bytes)),
+    };
+    let header_hex = hex::encode(minicbor::to_vec(&header).unwrap());
+
+    let result = check_header_signatures(&header_hex, &HeaderCheckParams::default()).unwrap();
+    assert!(result.valid, "{:?}", result.errors);
+    assert_eq!(result.kes_period, 102);
+    assert!(result.vrf_valid.is_none());
+
+    let params = HeaderCheckParams {
+        slots_per_kes_period: 86400,
+        ..HeaderCheckParams::default()
+    };
+    let result = check_header_signatures(&header_hex, &params).unwrap();
+    assert!(!result.kes_signature_valid);
+    assert!(result.opcert_signature_valid);
+}
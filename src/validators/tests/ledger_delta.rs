@@ -0,0 +1,90 @@
+use crate::common::{Asset, TxInput, TxOutput, UTxO};
+use crate::validators::common::NetworkType;
+use crate::validators::context_update::apply_transaction;
+use crate::validators::input_contexts::{UtxoInputContext, ValidationInputContext};
+use crate::validators::phase_1::validation::balance::DepositType;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+
+#[test]
+fn transaction_delta() {
+    let credential = csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from([1; 28]));
+    let address = csl::EnterpriseAddress::new(1, &credential).to_address();
+    let input = csl::TransactionInput::new(&csl::TransactionHash::from([7; 32]), 0);
+    let context = ValidationInputContext::new(
+        vec![UtxoInputContext {
+            utxo: UTxO {
+                input: TxInput {
+                    tx_hash: input.transaction_id().to_hex(),
+                    output_index: 0,
+                },
+                output: TxOutput {
+                    address: address.to_bech32(None).unwrap(),
+                    amount: vec![Asset {
+                        unit: "lovelace".to_string(),
+                        quantity: "10000000".to_string(),
+                    }],
+                    data_hash: None,
+                    plutus_data: None,
+                    script_ref: None,
+                    script_hash: None,
+                },
+            },
+            is_spent: false,
+        }],
+        get_test_protocol_parameters(),
+        // A mainnet slot of epoch 521
+        140_000_000,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    );
+
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(&input);
+    let mut outputs = csl::TransactionOutputs::new();
+    outputs.add(&csl::TransactionOutput::new(
+        &address,
+        &csl::Value::new(&csl::BigNum::from(6_000_000u64)),
+    ));
+    let mut tx_body =
+        csl::TransactionBody::new_tx_body(&inputs, &outputs, &csl::Coin::from(1_000_000u64));
+    let mut certs = csl::Certificates::new();
+    certs.add(
+        &csl::Certificate::new_reg_cert(&csl::StakeRegistration::new_with_explicit_deposit(
+            &credential,
+            &csl::Coin::from(2_000_000u64),
+        ))
+        .unwrap(),
+    );
+    tx_body.set_certs(&certs);
+    tx_body.set_donation(&csl::Coin::from(1_000_000u64));
+    let tx = csl::Transaction::new(&tx_body, &csl::TransactionWitnessSet::new(), None).to_hex();
+
+    let (_, delta) = apply_transaction(&tx, context).unwrap();
+    assert!(delta.is_valid);
+    assert_eq!(delta.consumed_utxos.len(), 1);
+    assert!(delta.unresolved_inputs.is_empty());
+    assert_eq!(delta.produced_utxos.len(), 1);
+    assert_eq!(delta.produced_utxos[0].input.output_index, 0);
+    assert_eq!(
+        delta.deposits,
+        vec![DepositType::StakeRegistration {
+            amount: 2_000_000,
+            index: 0
+        }]
+    );
+    assert_eq!(delta.fee, 1_000_000);
+    assert_eq!(delta.treasury_donation, 1_000_000);
+    assert_eq!(delta.accounts.len(), 1);
+    assert!(delta.accounts[0].is_registered);
+    assert_eq!(delta.accounts[0].payed_deposit, Some(2_000_000));
+    assert_eq!(delta.accounts[0].registration_epoch, Some(521));
+}
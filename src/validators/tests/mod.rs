@@ -1 +1,24 @@
-pub mod validator;
\ No newline at end of file
+pub mod validator;
+pub mod strict_decoding;
+pub mod auxiliary_data;
+pub mod voting_registration;
+pub mod anchor_documents;
+pub mod balance;
+pub mod block_validator;
+pub mod header_signatures;
+pub mod transaction_chain;
+pub mod ledger_delta;
+pub mod emulator;
+pub mod ratification;
+pub mod era;
+pub mod byron;
+pub mod node_errors;
+pub mod phase_one_consistency;
+pub mod phase_2_budget;
+pub mod phase_2_profile;
+pub mod script_contexts;
+pub mod phase_2_context_errors;
+pub mod transaction_limits;
+pub mod registration;
+pub mod output_lint;
+pub mod token_metadata;
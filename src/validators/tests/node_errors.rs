@@ -0,0 +1,337 @@
+use crate::validators::common::{LocalCredential, NetworkType};
+use crate::validators::node_errors::translate_node_error;
+use crate::validators::phase_1::errors::Phase1Error;
+use crate::validators::phase_2::errors::Phase2Error;
+use pallas_codec::minicbor::{data::Tag, Encoder};
+
+#[test]
+fn node_error_translation() {
+    // MsgRejectTx [2, [6 (Conway), [failures]]]
+    let mut encoder = Encoder::new(Vec::new());
+    encoder.array(2).unwrap().u8(2).unwrap();
+    encoder.array(2).unwrap().u8(6).unwrap();
+    encoder.array(3).unwrap();
+    // UTXOW / UTXO / BadInputsUTxO with a tagged set of inputs
+    encoder.array(2).unwrap().u8(1).unwrap();
+    encoder.array(2).unwrap().u8(0).unwrap();
+    encoder.array(2).unwrap().u8(1).unwrap();
+    encoder.tag(Tag::new(258)).unwrap();
+    encoder.array(1).unwrap();
+    encoder
+        .array(2)
+        .unwrap()
+        .bytes(&[1; 32])
+        .unwrap()
+        .u8(3)
+        .unwrap();
+    // CERTS / CERT / DELEG / StakeKeyNotRegisteredDELEG
+    encoder.array(2).unwrap().u8(2).unwrap();
+    encoder.array(2).unwrap().u8(1).unwrap();
+    encoder.array(2).unwrap().u8(1).unwrap();
+    encoder.array(2).unwrap().u8(3).unwrap();
+    encoder
+        .array(2)
+        .unwrap()
+        .u8(0)
+        .unwrap()
+        .bytes(&[5; 28])
+        .unwrap();
+    // A UTXOW failure with an unknown tag
+    encoder.array(2).unwrap().u8(1).unwrap();
+    encoder.array(1).unwrap().u8(99).unwrap();
+    let error_hex = hex::encode(encoder.into_writer());
+
+    let translation = translate_node_error(&error_hex, &NetworkType::Mainnet).unwrap();
+    assert_eq!(translation.era, Some("Conway".to_string()));
+    assert_eq!(translation.errors.len(), 2);
+    match &translation.errors[0].error {
+        Phase1Error::BadInputsUTxO { invalid_input } => {
+            assert_eq!(invalid_input.tx_hash, hex::encode([1; 32]));
+            assert_eq!(invalid_input.output_index, 3);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert_eq!(translation.errors[0].locations, vec!["LEDGER/UTXOW/UTXO"]);
+    assert!(translation.errors[0].hint.is_some());
+    assert!(matches!(
+        &translation.errors[1].error,
+        Phase1Error::StakeNotRegistered { reward_address } if reward_address.starts_with("stake1")
+    ));
+    assert_eq!(translation.untranslated_failures.len(), 1);
+    assert_eq!(translation.untranslated_failures[0].rule, "LEDGER/UTXOW");
+
+    // cardano-cli prints the Haskell text of the failures
+    let cli_error = r#"{"error": "ApplyTxError (ConwayUtxowFailure (UtxoFailure (FeeTooSmallUTxO (Mismatch {mismatchSupplied = Coin 100, mismatchExpected = Coin 170000}))) :| [ConwayUtxowFailure (UtxoFailure InputSetEmptyUTxO)])"}"#;
+    let translation = translate_node_error(cli_error, &NetworkType::Mainnet).unwrap();
+    assert_eq!(translation.errors.len(), 2);
+    assert!(matches!(
+        translation.errors[0].error,
+        Phase1Error::FeeTooSmallUTxO {
+            actual_fee: 100,
+            min_fee: 170000,
+            ..
+        }
+    ));
+    assert!(matches!(
+        translation.errors[1].error,
+        Phase1Error::InputSetEmptyUTxO
+    ));
+
+    let ogmios_error = r#"{"jsonrpc": "2.0", "error": {"code": 3122, "message": "Insufficient fee", "data": {"minimumRequiredFee": {"ada": {"lovelace": 170000}}, "providedFee": {"ada": {"lovelace": 100}}}}}"#;
+    let translation = translate_node_error(ogmios_error, &NetworkType::Mainnet).unwrap();
+    assert!(matches!(
+        translation.errors[0].error,
+        Phase1Error::FeeTooSmallUTxO {
+            actual_fee: 100,
+            min_fee: 170000,
+            ..
+        }
+    ));
+}
+
+type CborEncoder = Encoder<Vec<u8>>;
+
+/// CBOR hex of a Conway `MsgRejectTx` with one failure per encoder: `[2, [[6, [failures]]]]`
+fn conway_rejection(failures: &[&dyn Fn(&mut CborEncoder)]) -> String {
+    let mut encoder = Encoder::new(Vec::new());
+    encoder.array(2).unwrap().u8(2).unwrap();
+    encoder.array(1).unwrap();
+    encoder.array(2).unwrap().u8(6).unwrap();
+    encoder.array(failures.len() as u64).unwrap();
+    for failure in failures {
+        failure(&mut encoder);
+    }
+    hex::encode(encoder.into_writer())
+}
+
+/// Opens the `[tag, failure]` wrappers of the given rules down to the leaf failure
+fn wrap(encoder: &mut CborEncoder, tags: &[u8]) {
+    for tag in tags {
+        encoder.array(2).unwrap().u8(*tag).unwrap();
+    }
+}
+
+#[test]
+fn pool_failures() {
+    let error_hex = conway_rejection(&[
+        // CERTS / CERT / POOL / StakePoolNotRegisteredOnKeyPOOL
+        &|encoder| {
+            wrap(encoder, &[2, 1, 2]);
+            encoder.array(2).unwrap().u8(0).unwrap();
+            encoder.bytes(&[4; 28]).unwrap();
+        },
+        // CERTS / CERT / POOL / StakePoolCostTooLowPOOL
+        &|encoder| {
+            wrap(encoder, &[2, 1, 2]);
+            encoder.array(3).unwrap().u8(3).unwrap();
+            encoder.u64(100_000_000).unwrap().u64(170_000_000).unwrap();
+        },
+    ]);
+
+    let translation = translate_node_error(&error_hex, &NetworkType::Mainnet).unwrap();
+    assert_eq!(translation.errors.len(), 2);
+    assert!(matches!(
+        &translation.errors[0].error,
+        Phase1Error::StakePoolNotRegistered { pool_id } if pool_id.starts_with("pool1")
+    ));
+    assert!(matches!(
+        translation.errors[1].error,
+        Phase1Error::StakePoolCostTooLow {
+            specified_cost: 100_000_000,
+            min_cost: 170_000_000,
+        }
+    ));
+    for error in translation.errors.iter() {
+        assert_eq!(error.locations, vec!["LEDGER/CERTS/CERT/POOL"]);
+    }
+}
+
+#[test]
+fn gov_cert_failures() {
+    let error_hex = conway_rejection(&[
+        // CERTS / CERT / GOVCERT / ConwayDRepIncorrectDeposit
+        &|encoder| {
+            wrap(encoder, &[2, 1, 3]);
+            encoder.array(3).unwrap().u8(2).unwrap();
+            encoder.u64(2_000_000).unwrap().u64(500_000_000).unwrap();
+        },
+        // CERTS / CERT / GOVCERT / ConwayCommitteeIsUnknown
+        &|encoder| {
+            wrap(encoder, &[2, 1, 3]);
+            encoder.array(2).unwrap().u8(5).unwrap();
+            encoder.array(2).unwrap().u8(0).unwrap();
+            encoder.bytes(&[6; 28]).unwrap();
+        },
+    ]);
+
+    let translation = translate_node_error(&error_hex, &NetworkType::Mainnet).unwrap();
+    assert_eq!(translation.errors.len(), 2);
+    assert!(matches!(
+        translation.errors[0].error,
+        Phase1Error::DRepIncorrectDeposit {
+            supplied_deposit: 2_000_000,
+            required_deposit: 500_000_000,
+        }
+    ));
+    assert!(matches!(
+        &translation.errors[1].error,
+        Phase1Error::CommitteeIsUnknown {
+            committee_key_hash: LocalCredential::KeyHash(hash),
+        } if hash == &vec![6; 28]
+    ));
+    assert_eq!(
+        translation.errors[1].locations,
+        vec!["LEDGER/CERTS/CERT/GOVCERT"]
+    );
+}
+
+#[test]
+fn gov_failures() {
+    let mut reward_account = vec![0xe1];
+    reward_account.extend_from_slice(&[7; 28]);
+    let error_hex = conway_rejection(&[
+        // GOV / GovActionsDoNotExist
+        &|encoder| {
+            wrap(encoder, &[3]);
+            encoder.array(2).unwrap().u8(0).unwrap();
+            encoder.array(1).unwrap();
+            encoder
+                .array(2)
+                .unwrap()
+                .bytes(&[8; 32])
+                .unwrap()
+                .u8(1)
+                .unwrap();
+        },
+        // GOV / VotersDoNotExist with a DRep key hash voter
+        &|encoder| {
+            wrap(encoder, &[3]);
+            encoder.array(2).unwrap().u8(14).unwrap();
+            encoder.array(1).unwrap();
+            encoder
+                .array(2)
+                .unwrap()
+                .u8(2)
+                .unwrap()
+                .bytes(&[9; 28])
+                .unwrap();
+        },
+        // GOV / ProposalReturnAccountDoesNotExist
+        &|encoder| {
+            wrap(encoder, &[3]);
+            encoder.array(2).unwrap().u8(16).unwrap();
+            encoder.bytes(&reward_account).unwrap();
+        },
+    ]);
+
+    let translation = translate_node_error(&error_hex, &NetworkType::Mainnet).unwrap();
+    assert!(translation.untranslated_failures.is_empty());
+    assert_eq!(translation.errors.len(), 3);
+    match &translation.errors[0].error {
+        Phase1Error::GovActionsDoNotExist { invalid_action_ids } => {
+            assert_eq!(invalid_action_ids.len(), 1);
+            assert_eq!(invalid_action_ids[0].tx_hash, vec![8; 32]);
+            assert_eq!(invalid_action_ids[0].index, 1);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert!(matches!(
+        translation.errors[1].error,
+        Phase1Error::VoterDoNotExist { .. }
+    ));
+    assert!(matches!(
+        &translation.errors[2].error,
+        Phase1Error::ProposalReturnAccountDoesNotExist { return_account }
+            if return_account.starts_with("stake1")
+    ));
+    for error in translation.errors.iter() {
+        assert_eq!(error.locations, vec!["LEDGER/GOV"]);
+    }
+}
+
+#[test]
+fn script_failure_is_a_phase_2_error() {
+    // UTXOW / UTXO / UTXOS / ValidationTagMismatch (IsValid True) (FailedUnexpectedly [..])
+    let error_hex = conway_rejection(&[&|encoder| {
+        wrap(encoder, &[1, 0, 0]);
+        encoder.array(3).unwrap().u8(0).unwrap().bool(true).unwrap();
+        encoder.array(2).unwrap().u8(1).unwrap();
+        encoder.array(1).unwrap();
+        encoder
+            .array(3)
+            .unwrap()
+            .u8(0)
+            .unwrap()
+            .str("The machine terminated because of an error")
+            .unwrap()
+            .bytes(&[0; 4])
+            .unwrap();
+    }]);
+
+    let translation = translate_node_error(&error_hex, &NetworkType::Mainnet).unwrap();
+    assert!(translation.errors.is_empty());
+    assert_eq!(translation.phase2_errors.len(), 1);
+    assert!(matches!(
+        &translation.phase2_errors[0].error,
+        Phase2Error::MachineError { error } if error.starts_with("The machine terminated")
+    ));
+    assert_eq!(
+        translation.phase2_errors[0].locations,
+        vec!["LEDGER/UTXOW/UTXO/UTXOS"]
+    );
+}
+
+#[test]
+fn cardano_cli_submit_error() {
+    // As printed by `cardano-cli conway transaction submit`
+    let cli_error = r#"Command failed: transaction submit  Error: Error while submitting tx: ShelleyTxValidationError ShelleyBasedEraConway (ApplyTxError (ConwayUtxowFailure (MissingVKeyWitnessesUTXOW (fromList [KeyHash {unKeyHash = "9b8f4f3d1ac9e6da8a0c6c4fbe3c1ed5a1b0f9e6d7c8b9a0f1e2d3c4"}])) :| [ConwayUtxowFailure (UtxoFailure (BadInputsUTxO (fromList [TxIn (TxId {unTxId = SafeHash "0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0"}) (TxIx {unTxIx = 1})]))),ConwayUtxowFailure (UtxoFailure (ValueNotConservedUTxO (Mismatch {mismatchSupplied = MaryValue (Coin 4998831727) (MultiAsset (fromList [])), mismatchExpected = MaryValue (Coin 5000000000) (MultiAsset (fromList []))})))]))"#;
+
+    let translation = translate_node_error(cli_error, &NetworkType::Mainnet).unwrap();
+    assert!(translation.untranslated_failures.is_empty());
+    assert_eq!(translation.errors.len(), 3);
+    assert!(matches!(
+        &translation.errors[0].error,
+        Phase1Error::MissingVKeyWitnesses { missing_key_hash }
+            if missing_key_hash == "9b8f4f3d1ac9e6da8a0c6c4fbe3c1ed5a1b0f9e6d7c8b9a0f1e2d3c4"
+    ));
+    assert_eq!(translation.errors[0].locations, vec!["LEDGER/UTXOW"]);
+    match &translation.errors[1].error {
+        Phase1Error::BadInputsUTxO { invalid_input } => {
+            assert_eq!(
+                invalid_input.tx_hash,
+                "0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0"
+            );
+            assert_eq!(invalid_input.output_index, 1);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert!(matches!(
+        translation.errors[2].error,
+        Phase1Error::ValueNotConservedUTxO { .. }
+    ));
+    assert_eq!(translation.errors[2].locations, vec!["LEDGER/UTXOW/UTXO"]);
+}
+
+#[test]
+fn node_rejection_bytes() {
+    // `MsgRejectTx` as cardano-node sends it over local tx submission: the error of the
+    // current era in a one element array, the era index and an indefinite list of failures,
+    // here LEDGER/UTXOW MissingVKeyWitnessesUTXOW and LEDGER/UTXOW/UTXO BadInputsUTxO
+    let error_hex = "82028182069f82018202d9010281581c9b8f4f3d1ac9e6da8a0c6c4fbe3c1ed5a1b0f9e6d7c8b9a0f1e2d3c4820182008201d90102818258200f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f000ff";
+
+    let translation = translate_node_error(error_hex, &NetworkType::Mainnet).unwrap();
+    assert_eq!(translation.era, Some("Conway".to_string()));
+    assert!(translation.untranslated_failures.is_empty());
+    assert_eq!(translation.errors.len(), 2);
+    assert!(matches!(
+        &translation.errors[0].error,
+        Phase1Error::MissingVKeyWitnesses { missing_key_hash }
+            if missing_key_hash == "9b8f4f3d1ac9e6da8a0c6c4fbe3c1ed5a1b0f9e6d7c8b9a0f1e2d3c4"
+    ));
+    assert_eq!(translation.errors[0].locations, vec!["LEDGER/UTXOW"]);
+    assert!(matches!(
+        &translation.errors[1].error,
+        Phase1Error::BadInputsUTxO { invalid_input } if invalid_input.output_index == 0
+    ));
+    assert_eq!(translation.errors[1].locations, vec!["LEDGER/UTXOW/UTXO"]);
+}
@@ -0,0 +1,263 @@
+use crate::validators::common::NetworkType;
+use crate::validators::input_contexts::{UtxoInputContext, ValidationInputContext};
+use crate::validators::phase_1::errors::Phase1Warning;
+use crate::validators::phase_1::validation::OutputLintValidator;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+
+const INPUT_TX_HASH: &str = "16b6ee8c812f8b1c9c643ee3828f50fdcf0f174625bbd6e947ba77b12374094a";
+
+fn test_context() -> ValidationInputContext {
+    ValidationInputContext::new(
+        vec![],
+        get_test_protocol_parameters(),
+        1_000,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+fn key_address() -> csl::Address {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![1; 28]).unwrap());
+    csl::EnterpriseAddress::new(1, &credential).to_address()
+}
+
+fn script_address(script: &csl::PlutusScript) -> csl::Address {
+    csl::EnterpriseAddress::new(1, &csl::Credential::from_scripthash(&script.hash())).to_address()
+}
+
+fn plutus_v2_script() -> csl::PlutusScript {
+    csl::PlutusScript::new_v2(vec![
+        0x4e, 0x4d, 0x01, 0x00, 0x00, 0x33, 0x22, 0x22, 0x20, 0x05, 0x12, 0x00, 0x12, 0x00, 0x11,
+    ])
+}
+
+fn datum() -> csl::PlutusData {
+    csl::PlutusData::new_integer(&csl::BigInt::from(42))
+}
+
+fn lint(
+    outputs: Vec<csl::TransactionOutput>,
+    witness_set: &csl::TransactionWitnessSet,
+    context: &ValidationInputContext,
+) -> Vec<Phase1Warning> {
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(&csl::TransactionInput::new(
+        &csl::TransactionHash::from_hex(INPUT_TX_HASH).unwrap(),
+        0,
+    ));
+    let mut tx_outputs = csl::TransactionOutputs::new();
+    for output in outputs {
+        tx_outputs.add(&output);
+    }
+    let tx_body =
+        csl::TransactionBody::new_tx_body(&inputs, &tx_outputs, &csl::Coin::from(200_000u64));
+    OutputLintValidator::new(&tx_body, witness_set, context)
+        .warnings
+        .into_iter()
+        .map(|(warning, _)| warning)
+        .collect()
+}
+
+fn script_witness_set() -> csl::TransactionWitnessSet {
+    let mut scripts = csl::PlutusScripts::new();
+    scripts.add(&plutus_v2_script());
+    let mut witness_set = csl::TransactionWitnessSet::new();
+    witness_set.set_plutus_scripts(&scripts);
+    witness_set
+}
+
+#[test]
+fn script_output_without_datum() {
+    let output = csl::TransactionOutput::new(
+        &script_address(&plutus_v2_script()),
+        &csl::Value::new(&csl::Coin::from(2_000_000u64)),
+    );
+    let is_lint = |warning: &Phase1Warning| match warning {
+        Phase1Warning::ScriptOutputWithoutDatum { language, .. } => language == "PlutusV2",
+        _ => false,
+    };
+
+    let mut context = test_context();
+    let warnings = lint(vec![output.clone()], &script_witness_set(), &context);
+    assert!(warnings.iter().any(is_lint));
+
+    // The script version is unknown without the script
+    let warnings = lint(
+        vec![output.clone()],
+        &csl::TransactionWitnessSet::new(),
+        &context,
+    );
+    assert!(!warnings.iter().any(is_lint));
+
+    let mut with_datum = output.clone();
+    with_datum.set_plutus_data(&datum());
+    let warnings = lint(vec![with_datum], &script_witness_set(), &context);
+    assert!(!warnings.iter().any(is_lint));
+
+    context.output_lint_options.script_output_without_datum = false;
+    let warnings = lint(vec![output], &script_witness_set(), &context);
+    assert!(!warnings.iter().any(is_lint));
+}
+
+#[test]
+fn datum_hash_not_published() {
+    let mut output = csl::TransactionOutput::new(
+        &script_address(&plutus_v2_script()),
+        &csl::Value::new(&csl::Coin::from(2_000_000u64)),
+    );
+    output.set_data_hash(&csl::hash_plutus_data(&datum()));
+    let is_lint =
+        |warning: &Phase1Warning| matches!(warning, Phase1Warning::DatumHashNotPublished { .. });
+
+    let mut context = test_context();
+    let warnings = lint(
+        vec![output.clone()],
+        &csl::TransactionWitnessSet::new(),
+        &context,
+    );
+    assert!(warnings.iter().any(is_lint));
+
+    let mut plutus_list = csl::PlutusList::new();
+    plutus_list.add(&datum());
+    let mut witness_set = csl::TransactionWitnessSet::new();
+    witness_set.set_plutus_data(&plutus_list);
+    let warnings = lint(vec![output.clone()], &witness_set, &context);
+    assert!(!warnings.iter().any(is_lint));
+
+    context.output_lint_options.datum_hash_not_published = false;
+    let warnings = lint(vec![output], &csl::TransactionWitnessSet::new(), &context);
+    assert!(!warnings.iter().any(is_lint));
+}
+
+#[test]
+fn tokens_sent_to_pointer_address() {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![2; 28]).unwrap());
+    let pointer = csl::Pointer::new_pointer(
+        &csl::BigNum::from(1u64),
+        &csl::BigNum::from(2u64),
+        &csl::BigNum::from(3u64),
+    );
+    let address = csl::PointerAddress::new(1, &credential, &pointer).to_address();
+    let mut assets = csl::Assets::new();
+    assets.insert(
+        &csl::AssetName::new(b"token".to_vec()).unwrap(),
+        &csl::BigNum::from(1u64),
+    );
+    let mut multiasset = csl::MultiAsset::new();
+    multiasset.insert(&plutus_v2_script().hash(), &assets);
+    let mut value = csl::Value::new(&csl::Coin::from(2_000_000u64));
+    value.set_multiasset(&multiasset);
+    let output = csl::TransactionOutput::new(&address, &value);
+    let is_lint = |warning: &Phase1Warning| match warning {
+        Phase1Warning::TokensSentToUnusualAddress { address_kind, .. } => address_kind == "Pointer",
+        _ => false,
+    };
+
+    let mut context = test_context();
+    let warnings = lint(
+        vec![output.clone()],
+        &csl::TransactionWitnessSet::new(),
+        &context,
+    );
+    assert!(warnings.iter().any(is_lint));
+
+    // Only ADA can go to a pointer address without a warning
+    let ada_only = csl::TransactionOutput::new(&address, &csl::Value::new(&value.coin()));
+    let warnings = lint(vec![ada_only], &csl::TransactionWitnessSet::new(), &context);
+    assert!(!warnings.iter().any(is_lint));
+
+    context.output_lint_options.tokens_to_unusual_address = false;
+    let warnings = lint(vec![output], &csl::TransactionWitnessSet::new(), &context);
+    assert!(!warnings.iter().any(is_lint));
+}
+
+#[test]
+fn output_repeats_input() {
+    let utxo = serde_json::from_str(&format!(
+        "{{\"input\":{{\"outputIndex\":0,\"txHash\":\"{}\"}},\"output\":{{\"address\":\"{}\",\"amount\":[{{\"unit\":\"lovelace\",\"quantity\":\"5000000\"}}]}}}}",
+        INPUT_TX_HASH,
+        key_address().to_bech32(None).unwrap()
+    ))
+    .unwrap();
+    let mut context = test_context();
+    context.utxo_set = vec![UtxoInputContext {
+        utxo,
+        is_spent: false,
+    }];
+    let repeated = csl::TransactionOutput::new(
+        &key_address(),
+        &csl::Value::new(&csl::Coin::from(5_000_000u64)),
+    );
+    let is_lint = |warning: &Phase1Warning| match warning {
+        Phase1Warning::OutputRepeatsInput { input } => input == &format!("{}#0", INPUT_TX_HASH),
+        _ => false,
+    };
+
+    let warnings = lint(
+        vec![repeated.clone()],
+        &csl::TransactionWitnessSet::new(),
+        &context,
+    );
+    assert!(warnings.iter().any(is_lint));
+
+    let changed = csl::TransactionOutput::new(
+        &key_address(),
+        &csl::Value::new(&csl::Coin::from(4_800_000u64)),
+    );
+    let warnings = lint(vec![changed], &csl::TransactionWitnessSet::new(), &context);
+    assert!(!warnings.iter().any(is_lint));
+
+    context.output_lint_options.output_repeats_input = false;
+    let warnings = lint(vec![repeated], &csl::TransactionWitnessSet::new(), &context);
+    assert!(!warnings.iter().any(is_lint));
+}
+
+#[test]
+fn large_inline_datum() {
+    let mut output = csl::TransactionOutput::new(
+        &key_address(),
+        &csl::Value::new(&csl::Coin::from(2_000_000u64)),
+    );
+    output.set_plutus_data(&csl::PlutusData::new_bytes(vec![0; 5_000]));
+    let is_lint = |warning: &Phase1Warning| {
+        matches!(
+            warning,
+            Phase1Warning::LargeInlineDatum { max_size: 4096, .. }
+        )
+    };
+
+    let mut context = test_context();
+    let warnings = lint(
+        vec![output.clone()],
+        &csl::TransactionWitnessSet::new(),
+        &context,
+    );
+    assert!(warnings.iter().any(is_lint));
+
+    context.output_lint_options.max_inline_datum_size = Some(8192);
+    let warnings = lint(
+        vec![output.clone()],
+        &csl::TransactionWitnessSet::new(),
+        &context,
+    );
+    assert!(!warnings
+        .iter()
+        .any(|warning| matches!(warning, Phase1Warning::LargeInlineDatum { .. })));
+
+    context.output_lint_options.max_inline_datum_size = None;
+    let warnings = lint(vec![output], &csl::TransactionWitnessSet::new(), &context);
+    assert!(!warnings
+        .iter()
+        .any(|warning| matches!(warning, Phase1Warning::LargeInlineDatum { .. })));
+}
@@ -0,0 +1,112 @@
+use crate::common::ExUnits;
+use crate::validators::phase_2::errors::Phase2Error;
+use crate::validators::tests::validator::get_test_plutus_transaction;
+use crate::validators::validator::validate_transaction;
+use cardano_serialization_lib as csl;
+
+/// The test transaction with the ex units of the mint redeemer, which needs exactly what it
+/// declares, halved
+pub fn halved_mint_budget_tx(tx_hex: &str) -> String {
+    let tx = csl::Transaction::from_hex(tx_hex).unwrap();
+    let mut witness_set = tx.witness_set();
+    let redeemers = witness_set.redeemers().unwrap();
+    let mut lowered_redeemers = csl::Redeemers::new();
+    for i in 0..redeemers.len() {
+        let redeemer = redeemers.get(i);
+        let ex_units = if redeemer.tag().kind() == csl::RedeemerTagKind::Mint {
+            csl::ExUnits::new(
+                &redeemer
+                    .ex_units()
+                    .mem()
+                    .div_floor(&csl::BigNum::from(2u64)),
+                &redeemer
+                    .ex_units()
+                    .steps()
+                    .div_floor(&csl::BigNum::from(2u64)),
+            )
+        } else {
+            redeemer.ex_units()
+        };
+        lowered_redeemers.add(&csl::Redeemer::new(
+            &redeemer.tag(),
+            &redeemer.index(),
+            &redeemer.data(),
+            &ex_units,
+        ));
+    }
+    witness_set.set_redeemers(&lowered_redeemers);
+    csl::Transaction::new(&tx.body(), &witness_set, tx.auxiliary_data()).to_hex()
+}
+
+#[test]
+fn phase_2_budget_enforcement() {
+    let (tx_hex, mut validation_context) = get_test_plutus_transaction();
+    let tx_hex = halved_mint_budget_tx(tx_hex);
+
+    validation_context
+        .protocol_parameters
+        .max_tx_execution_units = ExUnits {
+        mem: 300_000,
+        steps: 10_000_000_000,
+    };
+    let result = validate_transaction(&tx_hex, validation_context).unwrap();
+
+    let required = ExUnits {
+        mem: 102887,
+        steps: 45331444,
+    };
+    let mint_result = result
+        .eval_redeemer_results
+        .iter()
+        .find(|eval_result| eval_result.index == 1)
+        .unwrap();
+    assert!(!mint_result.success);
+    assert_eq!(mint_result.calculated_ex_units.mem, required.mem);
+    assert_eq!(mint_result.calculated_ex_units.steps, required.steps);
+    assert!(result.phase2_errors.iter().any(|error| matches!(
+        &error.error,
+        Phase2Error::NoEnoughBudget { expected_budget, actual_budget }
+            if expected_budget.steps == required.steps && actual_budget.mem == required.mem / 2
+    )));
+
+    let tx_budget = result.tx_execution_budget.unwrap();
+    assert_eq!(tx_budget.required.mem, 251612 + required.mem);
+    assert!(result
+        .phase2_errors
+        .iter()
+        .any(|error| matches!(error.error, Phase2Error::TxExecutionBudgetTooBig { .. })));
+}
+
+#[test]
+fn phase_2_budget_rerun_capped_at_tx_maximum() {
+    let (tx_hex, mut validation_context) = get_test_plutus_transaction();
+    let tx_hex = halved_mint_budget_tx(tx_hex);
+
+    // Above the declared 51443 memory of the mint redeemer, below the 102887 it needs
+    let max_budget = ExUnits {
+        mem: 80_000,
+        steps: 10_000_000_000,
+    };
+    validation_context
+        .protocol_parameters
+        .max_tx_execution_units = max_budget.clone();
+    let result = validate_transaction(&tx_hex, validation_context).unwrap();
+
+    let mint_result = result
+        .eval_redeemer_results
+        .iter()
+        .find(|eval_result| eval_result.index == 1)
+        .unwrap();
+    assert!(!mint_result.success);
+    // The evaluation stops at the transaction maximum
+    assert!(mint_result.calculated_ex_units.mem < 102887);
+    assert!(result.phase2_errors.iter().any(|error| matches!(
+        &error.error,
+        Phase2Error::ScriptExceedsTxMaxBudget { actual_budget, max_budget: max }
+            if actual_budget.mem == 102887 / 2 && max.mem == max_budget.mem
+    )));
+    assert!(!result
+        .phase2_errors
+        .iter()
+        .any(|error| matches!(error.error, Phase2Error::NoEnoughBudget { .. })));
+}
@@ -0,0 +1,52 @@
+use crate::common::{Asset, TxInput};
+use crate::validators::phase_2::errors::Phase2Error;
+use crate::validators::phase_2::validation::phase_2_validation;
+use crate::validators::tests::validator::get_test_plutus_transaction;
+use crate::validators::validator::validate_transaction;
+
+#[test]
+fn phase_2_invalid_utxos() {
+    let (tx_hex, mut validation_context) = get_test_plutus_transaction();
+
+    // A malformed UTxO the transaction doesn't use is ignored
+    let mut unrelated_utxo = validation_context.utxo_set[0].clone();
+    unrelated_utxo.utxo.input = TxInput {
+        tx_hash: hex::encode([9; 32]),
+        output_index: 0,
+    };
+    unrelated_utxo.utxo.output.amount.push(Asset {
+        unit: "abcd".to_string(),
+        quantity: "1".to_string(),
+    });
+    validation_context.utxo_set.push(unrelated_utxo);
+    let result = validate_transaction(tx_hex, validation_context.clone()).unwrap();
+    let mint_result = result
+        .eval_redeemer_results
+        .iter()
+        .find(|eval_result| eval_result.index == 1)
+        .unwrap();
+    assert!(mint_result.success);
+
+    // A malformed UTxO of the transaction fails every redeemer instead of panicking
+    validation_context.utxo_set[0].utxo.output.amount[0].quantity = "not a number".to_string();
+    let result = phase_2_validation(tx_hex, &validation_context).unwrap();
+    assert!(!result.eval_redeemer_results.is_empty());
+    assert!(result
+        .eval_redeemer_results
+        .iter()
+        .all(|eval_result| !eval_result.success));
+    assert_eq!(
+        result.phase2_errors.len(),
+        result.eval_redeemer_results.len()
+    );
+    for error in &result.phase2_errors {
+        assert!(matches!(
+            &error.error,
+            Phase2Error::BuildTxContextError { error } if error.contains("Invalid quantity")
+        ));
+        assert!(error
+            .locations
+            .iter()
+            .any(|location| location.starts_with("transaction.witness_set.redeemers.")));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::common::ExUnits;
+use crate::validators::tests::phase_2_budget::halved_mint_budget_tx;
+use crate::validators::tests::validator::get_test_plutus_transaction;
+use crate::validators::validation_result::{EvalRedeemerResult, ExecutionProfile};
+use crate::validators::validator::validate_transaction;
+
+/// Memory and steps of all machine steps and builtin calls
+fn profile_total(profile: &ExecutionProfile) -> (u64, u64) {
+    profile
+        .machine_steps
+        .iter()
+        .map(|step| &step.ex_units)
+        .chain(profile.builtins.iter().map(|builtin| &builtin.ex_units))
+        .fold((0, 0), |(mem, steps), ex_units| {
+            (mem + ex_units.mem, steps + ex_units.steps)
+        })
+}
+
+fn mint_result(results: &[EvalRedeemerResult]) -> &EvalRedeemerResult {
+    results
+        .iter()
+        .find(|eval_result| eval_result.index == 1)
+        .unwrap()
+}
+
+#[test]
+fn phase_2_execution_profile() {
+    let (tx_hex, mut validation_context) = get_test_plutus_transaction();
+    assert!(validate_transaction(tx_hex, validation_context.clone())
+        .unwrap()
+        .eval_redeemer_results
+        .iter()
+        .all(|eval_result| eval_result.profile.is_none()));
+
+    validation_context.profile_scripts = true;
+    let result = validate_transaction(tx_hex, validation_context).unwrap();
+    let mint_result = mint_result(&result.eval_redeemer_results);
+    let profile = mint_result.profile.as_ref().unwrap();
+
+    // Steps and builtin calls add up to what uplc charged
+    let calculated = &mint_result.calculated_ex_units;
+    assert_eq!(profile_total(profile), (calculated.mem, calculated.steps));
+    assert!(profile
+        .machine_steps
+        .iter()
+        .any(|step| step.step == "StartUp" && step.count == 1));
+    assert!(profile
+        .builtins
+        .windows(2)
+        .all(|pair| pair[0].ex_units.steps >= pair[1].ex_units.steps));
+
+    assert!(profile.execution_fee > 0);
+    assert!(profile.tx_fee_share > 0.0 && profile.tx_fee_share < 1.0);
+}
+
+#[test]
+fn phase_2_profile_stops_at_tx_maximum() {
+    let (tx_hex, mut validation_context) = get_test_plutus_transaction();
+    let tx_hex = halved_mint_budget_tx(tx_hex);
+    validation_context.profile_scripts = true;
+    // The mint redeemer needs 102887 memory
+    validation_context
+        .protocol_parameters
+        .max_tx_execution_units = ExUnits {
+        mem: 80_000,
+        steps: 10_000_000_000,
+    };
+
+    let result = validate_transaction(&tx_hex, validation_context).unwrap();
+    let mint_result = mint_result(&result.eval_redeemer_results);
+    assert!(!mint_result.success);
+    let calculated = &mint_result.calculated_ex_units;
+    assert_eq!(
+        profile_total(mint_result.profile.as_ref().unwrap()),
+        (calculated.mem, calculated.steps)
+    );
+    assert!(calculated.mem < 102887);
+}
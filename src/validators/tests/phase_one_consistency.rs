@@ -0,0 +1,77 @@
+use crate::common::{Asset, TxInput, TxOutput, UTxO};
+use crate::validators::common::NetworkType;
+use crate::validators::input_contexts::{UtxoInputContext, ValidationInputContext};
+use crate::validators::phase_one_consistency::check_phase_one_consistency;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+
+#[test]
+fn phase_one_consistency_with_uplc() {
+    let credential = csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from([1; 28]));
+    let address = csl::EnterpriseAddress::new(1, &credential).to_address();
+    let input = csl::TransactionInput::new(&csl::TransactionHash::from([7; 32]), 0);
+    let context = || {
+        ValidationInputContext::new(
+            vec![UtxoInputContext {
+                utxo: UTxO {
+                    input: TxInput {
+                        tx_hash: input.transaction_id().to_hex(),
+                        output_index: 0,
+                    },
+                    output: TxOutput {
+                        address: address.to_bech32(None).unwrap(),
+                        amount: vec![Asset {
+                            unit: "lovelace".to_string(),
+                            quantity: "10000000".to_string(),
+                        }],
+                        data_hash: None,
+                        plutus_data: None,
+                        script_ref: None,
+                        script_hash: None,
+                    },
+                },
+                is_spent: false,
+            }],
+            get_test_protocol_parameters(),
+            0,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            0,
+            NetworkType::Mainnet,
+            vec![],
+            vec![],
+            vec![],
+        )
+    };
+
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(&input);
+    let mut outputs = csl::TransactionOutputs::new();
+    outputs.add(&csl::TransactionOutput::new(
+        &address,
+        &csl::Value::new(&csl::BigNum::from(9_000_000u64)),
+    ));
+    let tx_body =
+        csl::TransactionBody::new_tx_body(&inputs, &outputs, &csl::Coin::from(1_000_000u64));
+    let tx = csl::Transaction::new(&tx_body, &csl::TransactionWitnessSet::new(), None);
+
+    let report = check_phase_one_consistency(&tx.to_hex(), context()).unwrap();
+    assert!(report.uplc_error.is_none());
+    assert!(report.not_compared.is_empty());
+    assert!(report.disagreements.is_empty());
+
+    // Both sides report the script nothing requires, uplc then stops before its redeemer check
+    let mut plutus_scripts = csl::PlutusScripts::new();
+    plutus_scripts.add(&csl::PlutusScript::new_v2(vec![0x4d, 0x01, 0x00, 0x00]));
+    let mut witness_set = csl::TransactionWitnessSet::new();
+    witness_set.set_plutus_scripts(&plutus_scripts);
+    let tx = csl::Transaction::new(&tx_body, &witness_set, None);
+
+    let report = check_phase_one_consistency(&tx.to_hex(), context()).unwrap();
+    assert!(report.uplc_error.is_some());
+    assert_eq!(report.not_compared.len(), 2);
+    assert!(report.disagreements.is_empty());
+}
@@ -0,0 +1,211 @@
+use crate::common::SubCoin;
+use crate::validators::common::{LocalCredential, Voter};
+use crate::validators::ratification::{
+    simulate_ratification, CastVote, CommitteeMemberState, CommitteeState, DrepStake, PoolStake,
+    PredefinedDrep, RatificationInput, VoteChoice,
+};
+use cardano_serialization_lib as csl;
+
+fn ratio(numerator: u64, denominator: u64) -> csl::UnitInterval {
+    csl::UnitInterval::new(
+        &csl::BigNum::from(numerator),
+        &csl::BigNum::from(denominator),
+    )
+}
+
+fn drep_id(byte: u8) -> String {
+    let credential = csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from([byte; 28]));
+    csl::DRep::new_from_credential(&credential)
+        .to_bech32(true)
+        .unwrap()
+}
+
+fn input(governance_action: &csl::GovernanceAction, votes: Vec<CastVote>) -> RatificationInput {
+    let drep_thresholds = csl::DRepVotingThresholds::new(
+        &ratio(67, 100),
+        &ratio(67, 100),
+        &ratio(60, 100),
+        &ratio(75, 100),
+        &ratio(60, 100),
+        &ratio(67, 100),
+        &ratio(67, 100),
+        &ratio(67, 100),
+        &ratio(75, 100),
+        &ratio(67, 100),
+    );
+    let pool_thresholds = csl::PoolVotingThresholds::new(
+        &ratio(51, 100),
+        &ratio(51, 100),
+        &ratio(51, 100),
+        &ratio(51, 100),
+        &ratio(51, 100),
+    );
+    RatificationInput {
+        governance_action: governance_action.to_hex(),
+        votes,
+        drep_stake: vec![
+            DrepStake {
+                drep: drep_id(1),
+                stake: 70,
+                expiry_epoch: Some(100),
+            },
+            DrepStake {
+                drep: drep_id(2),
+                stake: 30,
+                expiry_epoch: Some(100),
+            },
+            DrepStake {
+                drep: drep_id(3),
+                stake: 100,
+                expiry_epoch: Some(5),
+            },
+        ],
+        always_abstain_stake: 50,
+        always_no_confidence_stake: 0,
+        pool_stake: vec![
+            PoolStake {
+                pool_id: hex::encode([4; 28]),
+                stake: 60,
+                reward_account_drep: None,
+            },
+            PoolStake {
+                pool_id: hex::encode([5; 28]),
+                stake: 40,
+                reward_account_drep: Some(PredefinedDrep::AlwaysAbstain),
+            },
+        ],
+        committee: Some(CommitteeState {
+            members: (6..9)
+                .map(|byte| CommitteeMemberState {
+                    cold_credential: LocalCredential::KeyHash(vec![byte; 28]),
+                    hot_credential: Some(LocalCredential::KeyHash(vec![byte + 10; 28])),
+                    expiry_epoch: 100,
+                    is_resigned: false,
+                })
+                .collect(),
+            threshold: SubCoin {
+                numerator: 2,
+                denominator: 3,
+            },
+            min_size: 3,
+        }),
+        drep_voting_thresholds: drep_thresholds.to_hex(),
+        pool_voting_thresholds: pool_thresholds.to_hex(),
+        current_epoch: 10,
+        protocol_major_version: 10,
+    }
+}
+
+fn vote(voter: Voter, vote: VoteChoice) -> CastVote {
+    CastVote { voter, vote }
+}
+
+#[test]
+fn parameter_change_ratification() {
+    let mut update = csl::ProtocolParamUpdate::new();
+    update.set_max_tx_size(20_000);
+    let action = csl::GovernanceAction::new_parameter_change_action(
+        &csl::ParameterChangeAction::new(&update),
+    );
+    let mut votes = vec![
+        vote(
+            Voter::ConstitutionalCommitteeHotKeyHash(vec![16; 28]),
+            VoteChoice::Yes,
+        ),
+        vote(
+            Voter::ConstitutionalCommitteeHotKeyHash(vec![17; 28]),
+            VoteChoice::Yes,
+        ),
+        vote(Voter::DRepKeyHash(vec![1; 28]), VoteChoice::Yes),
+        vote(Voter::DRepKeyHash(vec![3; 28]), VoteChoice::No),
+        vote(Voter::StakingPoolKeyHash(vec![4; 28]), VoteChoice::Yes),
+    ];
+
+    // Network group for DReps, security relevant for SPOs
+    let result = simulate_ratification(&input(&action, votes.clone())).unwrap();
+    assert!(result.ratified);
+    assert_eq!((result.committee.yes, result.committee.no), (2, 1));
+    assert_eq!(result.dreps.threshold.as_ref().unwrap().numerator, 67);
+    assert_eq!(
+        (result.dreps.yes, result.dreps.no, result.dreps.abstain),
+        (70, 30, 50)
+    );
+    assert_eq!(result.dreps.yes_percentage, 70.0);
+    assert!(result.spos.required);
+    assert_eq!((result.spos.yes, result.spos.abstain), (60, 40));
+
+    // The governance group raises the DRep threshold to 75%
+    update.set_drep_deposit(&csl::Coin::from(1_000_000u64));
+    let action = csl::GovernanceAction::new_parameter_change_action(
+        &csl::ParameterChangeAction::new(&update),
+    );
+    let result = simulate_ratification(&input(&action, votes.clone())).unwrap();
+    assert!(!result.ratified);
+    assert!(!result.dreps.passed);
+
+    // During bootstrap DReps don't vote and pools that did not vote abstain
+    let mut bootstrap = input(&action, votes.clone());
+    bootstrap.protocol_major_version = 9;
+    let result = simulate_ratification(&bootstrap).unwrap();
+    assert!(result.ratified);
+    assert!(!result.dreps.required);
+
+    // A committee vote below the threshold
+    votes.remove(1);
+    let result = simulate_ratification(&input(&action, votes)).unwrap();
+    assert!(!result.committee.passed);
+}
+
+#[test]
+fn no_confidence_ratification() {
+    let action = csl::GovernanceAction::new_no_confidence_action(&csl::NoConfidenceAction::new());
+    let mut input = input(
+        &action,
+        vec![vote(Voter::DRepKeyHash(vec![2; 28]), VoteChoice::Yes)],
+    );
+    input.always_no_confidence_stake = 150;
+    input.pool_stake[1].reward_account_drep = Some(PredefinedDrep::AlwaysNoConfidence);
+    input.pool_stake[0].stake = 30;
+
+    let result = simulate_ratification(&input).unwrap();
+    assert!(!result.committee.required);
+    // 30 + 150 yes against 70 no
+    assert_eq!((result.dreps.yes, result.dreps.no), (180, 70));
+    assert_eq!((result.spos.yes, result.spos.no), (40, 30));
+    assert!(result.ratified);
+}
+
+#[test]
+fn hard_fork_ratification_during_bootstrap() {
+    let action = csl::GovernanceAction::new_hard_fork_initiation_action(
+        &csl::HardForkInitiationAction::new(&csl::ProtocolVersion::new(10, 0)),
+    );
+    let votes = vec![
+        vote(
+            Voter::ConstitutionalCommitteeHotKeyHash(vec![16; 28]),
+            VoteChoice::Yes,
+        ),
+        vote(
+            Voter::ConstitutionalCommitteeHotKeyHash(vec![17; 28]),
+            VoteChoice::Yes,
+        ),
+        vote(Voter::StakingPoolKeyHash(vec![4; 28]), VoteChoice::Yes),
+    ];
+    let mut bootstrap = input(&action, votes);
+    bootstrap.protocol_major_version = 9;
+
+    // The pool that did not vote counts as no even during bootstrap
+    let result = simulate_ratification(&bootstrap).unwrap();
+    assert!(!result.dreps.required);
+    assert_eq!(
+        (result.spos.yes, result.spos.no, result.spos.abstain),
+        (60, 40, 0)
+    );
+    assert!(result.ratified);
+
+    bootstrap.votes.pop();
+    let result = simulate_ratification(&bootstrap).unwrap();
+    assert_eq!((result.spos.yes, result.spos.no), (0, 100));
+    assert!(!result.spos.passed);
+    assert!(!result.ratified);
+}
@@ -0,0 +1,374 @@
+use crate::validators::common::NetworkType;
+use crate::validators::helpers::credential_to_bech32_reward_address;
+use crate::validators::input_contexts::{
+    AccountInputContext, DrepInputContext, PoolInputContext, ValidationInputContext,
+    VrfKeyHashInputContext,
+};
+use crate::validators::phase_1::errors::{Phase1Error, Phase1Warning, ValidationPhase1Error};
+use crate::validators::phase_1::validation::RegistrationValidator;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use crate::validators::validation_result::ValidationResult;
+use cardano_serialization_lib as csl;
+
+const POOL_COST: u64 = 340_000_000;
+
+fn key_hash(byte: u8) -> csl::Ed25519KeyHash {
+    csl::Ed25519KeyHash::from_bytes(vec![byte; 28]).unwrap()
+}
+
+fn reward_address(network: u8, byte: u8) -> csl::RewardAddress {
+    csl::RewardAddress::new(network, &csl::Credential::from_keyhash(&key_hash(byte)))
+}
+
+fn test_context() -> ValidationInputContext {
+    ValidationInputContext::new(
+        vec![],
+        get_test_protocol_parameters(),
+        1_000,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+struct PoolParamsBuilder {
+    margin: (u64, u64),
+    reward_network: u8,
+    relays: csl::Relays,
+    metadata_url: Option<String>,
+}
+
+impl PoolParamsBuilder {
+    fn new() -> Self {
+        Self {
+            margin: (1, 100),
+            reward_network: csl::NetworkInfo::mainnet().network_id(),
+            relays: csl::Relays::new(),
+            metadata_url: None,
+        }
+    }
+
+    fn build(&self) -> csl::PoolParams {
+        let metadata = self.metadata_url.as_ref().map(|url| {
+            csl::PoolMetadata::new(
+                &csl::URL::new(url.clone()).unwrap(),
+                &csl::PoolMetadataHash::from_bytes(vec![7; 32]).unwrap(),
+            )
+        });
+        csl::PoolParams::new(
+            &key_hash(1),
+            &csl::VRFKeyHash::from_bytes(vec![2; 32]).unwrap(),
+            &csl::Coin::from(1_000_000u64),
+            &csl::Coin::from(POOL_COST),
+            &csl::UnitInterval::new(
+                &csl::BigNum::from(self.margin.0),
+                &csl::BigNum::from(self.margin.1),
+            ),
+            &reward_address(self.reward_network, 3),
+            &csl::Ed25519KeyHashes::new(),
+            &self.relays,
+            metadata,
+        )
+    }
+}
+
+fn validate_certs(
+    certs: &[csl::Certificate],
+    context: &ValidationInputContext,
+) -> ValidationResult {
+    let mut certificates = csl::Certificates::new();
+    for cert in certs {
+        certificates.add(cert);
+    }
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::from(200_000u64),
+    );
+    tx_body.set_certs(&certificates);
+    RegistrationValidator::new(&tx_body, context).validate()
+}
+
+fn pool_registration_errors(pool_params: &PoolParamsBuilder) -> Vec<ValidationPhase1Error> {
+    let cert =
+        csl::Certificate::new_pool_registration(&csl::PoolRegistration::new(&pool_params.build()));
+    validate_certs(&[cert], &test_context()).errors
+}
+
+#[test]
+fn pool_margin_out_of_range() {
+    let mut pool_params = PoolParamsBuilder::new();
+    pool_params.margin = (3, 2);
+    let errors = pool_registration_errors(&pool_params);
+    assert!(errors.iter().any(|error| matches!(
+        error.error,
+        Phase1Error::PoolMarginOutOfRange {
+            numerator: 3,
+            denominator: 2,
+            ..
+        }
+    )));
+
+    pool_params.margin = (1, 1);
+    let errors = pool_registration_errors(&pool_params);
+    assert!(!errors
+        .iter()
+        .any(|error| matches!(error.error, Phase1Error::PoolMarginOutOfRange { .. })));
+}
+
+#[test]
+fn pool_relay_dns_name_too_long() {
+    let mut pool_params = PoolParamsBuilder::new();
+    pool_params.relays.add(&csl::Relay::new_single_host_name(
+        &csl::SingleHostName::new(
+            None,
+            &csl::DNSRecordAorAAAA::new("relay.example.com".to_string()).unwrap(),
+        ),
+    ));
+    pool_params
+        .relays
+        .add(&csl::Relay::new_multi_host_name(&csl::MultiHostName::new(
+            &csl::DNSRecordSRV::new(format!("{}.example.com", "r".repeat(53))).unwrap(),
+        )));
+    let errors = pool_registration_errors(&pool_params);
+    let dns_errors = errors
+        .iter()
+        .filter(|error| matches!(error.error, Phase1Error::PoolRelayDnsNameTooLong { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(dns_errors.len(), 1);
+    assert!(matches!(
+        dns_errors[0].error,
+        Phase1Error::PoolRelayDnsNameTooLong {
+            relay_index: 1,
+            length: 65,
+            max_length: 64,
+            ..
+        }
+    ));
+    assert_eq!(
+        dns_errors[0].locations,
+        vec!["transaction.body.certs.0.relays.1".to_string()]
+    );
+}
+
+#[test]
+fn pool_metadata_url_too_long() {
+    let mut pool_params = PoolParamsBuilder::new();
+    pool_params.metadata_url = Some(format!("https://example.com/{}", "m".repeat(45)));
+    let errors = pool_registration_errors(&pool_params);
+    assert!(errors.iter().any(|error| matches!(
+        error.error,
+        Phase1Error::PoolMetadataUrlTooLong {
+            length: 65,
+            max_length: 64,
+            ..
+        }
+    )));
+
+    pool_params.metadata_url = Some(format!("https://example.com/{}", "m".repeat(44)));
+    let errors = pool_registration_errors(&pool_params);
+    assert!(!errors
+        .iter()
+        .any(|error| matches!(error.error, Phase1Error::PoolMetadataUrlTooLong { .. })));
+}
+
+#[test]
+fn pool_reward_account_on_wrong_network() {
+    let mut pool_params = PoolParamsBuilder::new();
+    pool_params.reward_network = csl::NetworkInfo::testnet_preview().network_id();
+    let errors = pool_registration_errors(&pool_params);
+    assert!(errors.iter().any(|error| matches!(
+        error.error,
+        Phase1Error::WrongNetworkPool {
+            expected_network: 1,
+            ..
+        }
+    )));
+
+    let errors = pool_registration_errors(&PoolParamsBuilder::new());
+    assert!(!errors
+        .iter()
+        .any(|error| matches!(error.error, Phase1Error::WrongNetworkPool { .. })));
+}
+
+#[test]
+fn pool_vrf_key_hash_already_registered() {
+    let cert = csl::Certificate::new_pool_registration(&csl::PoolRegistration::new(
+        &PoolParamsBuilder::new().build(),
+    ));
+    let is_duplicate_vrf = |error: &&ValidationPhase1Error| {
+        matches!(error.error, Phase1Error::VrfKeyHashAlreadyRegistered { .. })
+    };
+    let mut context = test_context();
+    context.vrf_key_hash_contexts = vec![VrfKeyHashInputContext {
+        vrf_key_hash: hex::encode([2; 32]),
+        pool_id: key_hash(9).to_hex(),
+    }];
+
+    // Protocol version 9 doesn't require unique VRF keys
+    let result = validate_certs(&[cert.clone()], &context);
+    assert!(!result.errors.iter().any(|error| is_duplicate_vrf(&error)));
+
+    context.protocol_parameters.protocol_version = (10, 0);
+    let result = validate_certs(&[cert.clone()], &context);
+    let error = result.errors.iter().find(is_duplicate_vrf).unwrap();
+    assert!(matches!(
+        &error.error,
+        Phase1Error::VrfKeyHashAlreadyRegistered { registered_pool_id, .. }
+            if registered_pool_id == &key_hash(9).to_hex()
+    ));
+    assert_eq!(error.rule_version, Some(10));
+
+    // Re-registering the pool that owns the VRF key is fine
+    context.vrf_key_hash_contexts[0].pool_id = key_hash(1).to_hex();
+    let result = validate_certs(&[cert], &context);
+    assert!(!result.errors.iter().any(|error| is_duplicate_vrf(&error)));
+}
+
+/// A mainnet slot of epoch 521: 208 Byron epochs end at slot 4492800, then 432000 slots each
+const MAINNET_SLOT: u64 = 140_000_000;
+const MAINNET_EPOCH: u64 = 521;
+
+fn stake_credential() -> csl::Credential {
+    csl::Credential::from_keyhash(&key_hash(5))
+}
+
+fn delegator_context(
+    delegated_to_pool: Option<String>,
+    delegated_to_drep: Option<String>,
+) -> ValidationInputContext {
+    let mut context = test_context();
+    context.slot = MAINNET_SLOT;
+    context.account_contexts = vec![AccountInputContext {
+        bech32_address: credential_to_bech32_reward_address(
+            &stake_credential(),
+            &NetworkType::Mainnet,
+        ),
+        is_registered: true,
+        payed_deposit: Some(2_000_000),
+        delegated_to_drep,
+        delegated_to_pool,
+        balance: Some(0),
+        registration_epoch: None,
+    }];
+    context
+}
+
+fn pool_delegation_warnings(context: &ValidationInputContext) -> Vec<Phase1Warning> {
+    let cert = csl::Certificate::new_stake_delegation(&csl::StakeDelegation::new(
+        &stake_credential(),
+        &key_hash(1),
+    ));
+    validate_certs(&[cert], context)
+        .warnings
+        .into_iter()
+        .map(|warning| warning.warning)
+        .collect()
+}
+
+fn drep() -> csl::DRep {
+    csl::DRep::new_key_hash(&key_hash(6))
+}
+
+fn drep_delegation_warnings(context: &ValidationInputContext) -> Vec<Phase1Warning> {
+    let cert = csl::Certificate::new_vote_delegation(&csl::VoteDelegation::new(
+        &stake_credential(),
+        &drep(),
+    ));
+    validate_certs(&[cert], context)
+        .warnings
+        .into_iter()
+        .map(|warning| warning.warning)
+        .collect()
+}
+
+fn pool_context(retirement_epoch: Option<u64>) -> PoolInputContext {
+    PoolInputContext {
+        pool_id: key_hash(1).to_hex(),
+        is_registered: true,
+        retirement_epoch,
+        reward_account: None,
+    }
+}
+
+#[test]
+fn delegation_to_retiring_pool() {
+    let mut context = delegator_context(None, None);
+    context.pool_contexts = vec![pool_context(Some(MAINNET_EPOCH + 2))];
+    assert!(pool_delegation_warnings(&context)
+        .iter()
+        .any(|warning| matches!(
+            warning,
+            Phase1Warning::DelegationToRetiringPool {
+                retirement_epoch: 523,
+                ..
+            }
+        )));
+
+    context.pool_contexts = vec![pool_context(None)];
+    assert!(!pool_delegation_warnings(&context)
+        .iter()
+        .any(|warning| matches!(warning, Phase1Warning::DelegationToRetiringPool { .. })));
+}
+
+#[test]
+fn delegation_to_expired_drep() {
+    let mut context = delegator_context(None, None);
+    let drep_context = |expiry_epoch| DrepInputContext {
+        bech32_drep: drep().to_bech32(true).unwrap(),
+        is_registered: true,
+        payed_deposit: Some(500_000_000),
+        expiry_epoch: Some(expiry_epoch),
+        registration_epoch: None,
+    };
+
+    context.drep_contexts = vec![drep_context(MAINNET_EPOCH - 2)];
+    assert!(drep_delegation_warnings(&context)
+        .iter()
+        .any(|warning| matches!(
+            warning,
+            Phase1Warning::DelegationToInactiveDRep {
+                expiry_epoch: 519,
+                current_epoch: 521,
+                ..
+            }
+        )));
+
+    // A DRep expiring in the current epoch is still active
+    context.drep_contexts = vec![drep_context(MAINNET_EPOCH)];
+    assert!(!drep_delegation_warnings(&context)
+        .iter()
+        .any(|warning| matches!(warning, Phase1Warning::DelegationToInactiveDRep { .. })));
+}
+
+#[test]
+fn redundant_delegation() {
+    let is_redundant =
+        |warning: &Phase1Warning| matches!(warning, Phase1Warning::RedundantDelegation { .. });
+
+    // Delegated with the pool id in bech32, delegating again to the same pool
+    let pool_bech32 = key_hash(1).to_bech32("pool").unwrap();
+    let mut context = delegator_context(Some(pool_bech32), None);
+    context.pool_contexts = vec![pool_context(None)];
+    assert_eq!(
+        pool_delegation_warnings(&context)
+            .iter()
+            .filter(|warning| is_redundant(warning))
+            .count(),
+        1
+    );
+
+    let mut context = delegator_context(Some(key_hash(9).to_hex()), None);
+    context.pool_contexts = vec![pool_context(None)];
+    assert!(!pool_delegation_warnings(&context).iter().any(is_redundant));
+
+    let context = delegator_context(None, Some(drep().to_bech32(false).unwrap()));
+    assert!(drep_delegation_warnings(&context).iter().any(is_redundant));
+}
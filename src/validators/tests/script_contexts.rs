@@ -0,0 +1,37 @@
+use crate::validators::script_contexts::get_script_contexts;
+use crate::validators::tests::validator::get_test_plutus_transaction;
+use cardano_serialization_lib as csl;
+
+#[test]
+fn script_contexts_export() {
+    let (tx_hex, validation_context) = get_test_plutus_transaction();
+    let script_contexts = get_script_contexts(tx_hex, &validation_context).unwrap();
+
+    let tx = csl::Transaction::from_hex(tx_hex).unwrap();
+    let redeemers = tx.witness_set().redeemers().unwrap();
+    assert_eq!(script_contexts.len(), redeemers.len());
+
+    for script_context in &script_contexts {
+        assert!(script_context.error.is_none());
+        let redeemer = (0..redeemers.len())
+            .map(|i| redeemers.get(i))
+            .find(|redeemer| u64::from(redeemer.index()) == script_context.index)
+            .unwrap();
+        assert_eq!(
+            script_context.redeemer.as_ref().unwrap().cbor,
+            redeemer.data().to_hex()
+        );
+
+        let context = script_context.script_context.as_ref().unwrap();
+        assert!(csl::PlutusData::from_hex(&context.cbor).is_ok());
+        // ScriptContext is a constructor of the TxInfo and the script purpose
+        assert_eq!(context.json["constructor"], 0);
+        assert_eq!(context.json["fields"].as_array().unwrap().len(), 2);
+    }
+
+    let spend = script_contexts
+        .iter()
+        .find(|script_context| script_context.index == 2)
+        .unwrap();
+    assert!(spend.datum.is_some());
+}
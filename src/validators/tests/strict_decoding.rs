@@ -0,0 +1,164 @@
+use crate::common::{Asset, TxInput, TxOutput, UTxO};
+use crate::validators::common::NetworkType;
+use crate::validators::input_contexts::{UtxoInputContext, ValidationInputContext};
+use crate::validators::phase_1::errors::Phase1Error;
+use crate::validators::phase_1::validation::StrictDecodingValidator;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use crate::validators::validator::validate_transaction;
+use cardano_serialization_lib as csl;
+use pallas_codec::minicbor::{data::Tag, Encoder};
+
+type CborEncoder = Encoder<Vec<u8>>;
+
+/// CBOR of a valid transaction `[body, {}, true, null]` whose body has `fields` entries
+fn transaction(fields: u64, body: impl Fn(&mut CborEncoder)) -> Vec<u8> {
+    let mut encoder = Encoder::new(Vec::new());
+    encoder.array(4).unwrap().map(fields).unwrap();
+    body(&mut encoder);
+    encoder.map(0).unwrap().bool(true).unwrap().null().unwrap();
+    encoder.into_writer()
+}
+
+/// Body field 0: a 258 tagged set with `count` copies of input `[#00..00, 0]`
+fn inputs(encoder: &mut CborEncoder, count: u64) {
+    encoder.u8(0).unwrap().tag(Tag::new(258)).unwrap();
+    encoder.array(count).unwrap();
+    for _ in 0..count {
+        encoder
+            .array(2)
+            .unwrap()
+            .bytes(&[0; 32])
+            .unwrap()
+            .u8(0)
+            .unwrap();
+    }
+}
+
+fn strict_decoding_errors(tx_bytes: &[u8]) -> Vec<Phase1Error> {
+    StrictDecodingValidator::new(tx_bytes)
+        .unwrap()
+        .validate()
+        .errors
+        .into_iter()
+        .map(|e| e.error)
+        .collect()
+}
+
+#[test]
+fn test_strict_decoding_violations() {
+    // body: { 0: 258([input, input]), 2: 0, 4: 258([]), 9: { policy: { "": 0 } } }
+    let tx_bytes = transaction(4, |encoder| {
+        inputs(encoder, 2);
+        encoder.u8(2).unwrap().u8(0).unwrap();
+        encoder.u8(4).unwrap().tag(Tag::new(258)).unwrap();
+        encoder.array(0).unwrap();
+        encoder
+            .u8(9)
+            .unwrap()
+            .map(1)
+            .unwrap()
+            .bytes(&[0x11; 28])
+            .unwrap();
+        encoder.map(1).unwrap().bytes(&[]).unwrap().u8(0).unwrap();
+    });
+
+    let errors = strict_decoding_errors(&tx_bytes);
+
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors.iter().any(
+        |e| matches!(e, Phase1Error::DuplicateSetElement { field, offset }
+        if field == "transaction.body.inputs" && *offset == 43)
+    ));
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, Phase1Error::EmptyNonEmptySet { field, .. }
+        if field == "transaction.body.certs")));
+    assert!(errors.iter().any(
+        |e| matches!(e, Phase1Error::ZeroQuantityAsset { field, asset_name, .. }
+        if field == "transaction.body.mint" && asset_name.is_empty())
+    ));
+}
+
+#[test]
+fn test_strict_decoding_duplicate_withdrawals() {
+    let mut reward_account = vec![0xe1];
+    reward_account.extend_from_slice(&[0x22; 28]);
+    // body: { 0: 258([input]), 2: 0, 5: { reward_account: 1, reward_account: 2 } }
+    let tx_bytes = transaction(3, |encoder| {
+        inputs(encoder, 1);
+        encoder.u8(2).unwrap().u8(0).unwrap();
+        encoder.u8(5).unwrap().map(2).unwrap();
+        encoder.bytes(&reward_account).unwrap().u8(1).unwrap();
+        encoder.bytes(&reward_account).unwrap().u8(2).unwrap();
+    });
+
+    let errors = strict_decoding_errors(&tx_bytes);
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(
+        matches!(&errors[0], Phase1Error::DuplicateMapKey { field, .. }
+        if field == "transaction.body.withdrawals")
+    );
+}
+
+#[test]
+fn test_strict_decoding_only_from_conway() {
+    // body: { 0: 258([input, input]), 1: [], 2: 0 }
+    let tx_hex = hex::encode(transaction(3, |encoder| {
+        inputs(encoder, 2);
+        encoder.u8(1).unwrap().array(0).unwrap();
+        encoder.u8(2).unwrap().u8(0).unwrap();
+    }));
+    let credential = csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from([1; 28]));
+    let address = csl::EnterpriseAddress::new(1, &credential)
+        .to_address()
+        .to_bech32(None)
+        .unwrap();
+    let has_duplicate_input = |protocol_major: u32| {
+        let mut protocol_parameters = get_test_protocol_parameters();
+        protocol_parameters.protocol_version = (protocol_major, 0);
+        let context = ValidationInputContext::new(
+            vec![UtxoInputContext {
+                utxo: UTxO {
+                    input: TxInput {
+                        tx_hash: "00".repeat(32),
+                        output_index: 0,
+                    },
+                    output: TxOutput {
+                        address: address.clone(),
+                        amount: vec![Asset {
+                            unit: "lovelace".to_string(),
+                            quantity: "10000000".to_string(),
+                        }],
+                        data_hash: None,
+                        plutus_data: None,
+                        script_ref: None,
+                        script_hash: None,
+                    },
+                },
+                is_spent: false,
+            }],
+            protocol_parameters,
+            0,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            0,
+            NetworkType::Mainnet,
+            vec![],
+            vec![],
+            vec![],
+        );
+        validate_transaction(&tx_hex, context)
+            .unwrap()
+            .errors
+            .iter()
+            .any(|error| matches!(error.error, Phase1Error::DuplicateSetElement { .. }))
+    };
+
+    // Babbage nodes accept duplicate set elements and drop the repeated ones
+    assert!(!has_duplicate_input(8));
+    assert!(has_duplicate_input(9));
+}
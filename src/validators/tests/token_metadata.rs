@@ -0,0 +1,252 @@
+use crate::validators::phase_1::errors::Phase1Warning;
+use crate::validators::phase_1::validation::token_metadata::collect_token_metadata_warnings;
+use cardano_serialization_lib as csl;
+
+const POLICY_ID: &str = "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209";
+const REFERENCE_PREFIX: [u8; 4] = [0x00, 0x06, 0x43, 0xb0];
+const NFT_PREFIX: [u8; 4] = [0x00, 0x0d, 0xe1, 0x40];
+const FT_PREFIX: [u8; 4] = [0x00, 0x14, 0xdf, 0x10];
+const RFT_PREFIX: [u8; 4] = [0x00, 0x1b, 0xc2, 0x80];
+
+fn policy_id() -> csl::ScriptHash {
+    csl::ScriptHash::from_hex(POLICY_ID).unwrap()
+}
+
+fn address() -> csl::Address {
+    let credential =
+        csl::Credential::from_keyhash(&csl::Ed25519KeyHash::from_bytes(vec![5; 28]).unwrap());
+    csl::EnterpriseAddress::new(1, &credential).to_address()
+}
+
+fn cip68_name(prefix: [u8; 4], name: &[u8]) -> Vec<u8> {
+    let mut asset_name = prefix.to_vec();
+    asset_name.extend_from_slice(name);
+    asset_name
+}
+
+/// Transaction body minting `assets` under `POLICY_ID` and sending them to `outputs`
+fn minting_tx_body(
+    assets: &[(Vec<u8>, i32)],
+    outputs: &csl::TransactionOutputs,
+) -> csl::TransactionBody {
+    let mut mint_assets = csl::MintAssets::new();
+    for (name, quantity) in assets {
+        mint_assets
+            .insert(
+                &csl::AssetName::new(name.clone()).unwrap(),
+                &csl::Int::new_i32(*quantity),
+            )
+            .unwrap();
+    }
+    let mut mint = csl::Mint::new();
+    mint.insert(&policy_id(), &mint_assets);
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        outputs,
+        &csl::Coin::zero(),
+    );
+    tx_body.set_mint(&mint);
+    tx_body
+}
+
+fn text(value: &str) -> csl::TransactionMetadatum {
+    csl::TransactionMetadatum::new_text(value.to_string()).unwrap()
+}
+
+fn text_map(entries: &[(&str, csl::TransactionMetadatum)]) -> csl::TransactionMetadatum {
+    let mut map = csl::MetadataMap::new();
+    for (key, value) in entries {
+        map.insert_str(key, value).unwrap();
+    }
+    csl::TransactionMetadatum::new_map(&map)
+}
+
+fn auxiliary_data(label: u64, metadatum: &csl::TransactionMetadatum) -> csl::AuxiliaryData {
+    let mut metadata = csl::GeneralTransactionMetadata::new();
+    metadata.insert(&csl::BigNum::from(label), metadatum);
+    let mut auxiliary_data = csl::AuxiliaryData::new();
+    auxiliary_data.set_metadata(&metadata);
+    auxiliary_data
+}
+
+fn warnings(
+    tx_body: &csl::TransactionBody,
+    auxiliary_data: Option<&csl::AuxiliaryData>,
+) -> Vec<Phase1Warning> {
+    collect_token_metadata_warnings(tx_body, auxiliary_data)
+        .into_iter()
+        .map(|(warning, _)| warning)
+        .collect()
+}
+
+/// `{ 721: { "<policy>": { "<asset>": { "name": ..., "image": [...] } } } }` in version 1
+fn cip25_metadata(asset_name: &str) -> csl::AuxiliaryData {
+    let mut image = csl::MetadataList::new();
+    image.add(&text(
+        "ipfs://QmYw2VJtFJpqBLgy4rMXEWdqLMxkx3mWjV8wEqKXwrYZfs/",
+    ));
+    image.add(&text("image.png"));
+    let details = text_map(&[
+        ("name", text(asset_name)),
+        ("image", csl::TransactionMetadatum::new_list(&image)),
+    ]);
+    let assets = text_map(&[(asset_name, details)]);
+    auxiliary_data(721, &text_map(&[(POLICY_ID, assets)]))
+}
+
+#[test]
+fn cip25_matching_metadata() {
+    let tx_body = minting_tx_body(&[(b"Token1".to_vec(), 1)], &csl::TransactionOutputs::new());
+    let warnings = warnings(&tx_body, Some(&cip25_metadata("Token1")));
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn cip25_metadata_not_matching_the_mint() {
+    let tx_body = minting_tx_body(&[(b"Token1".to_vec(), 1)], &csl::TransactionOutputs::new());
+    let messages: Vec<String> = warnings(&tx_body, Some(&cip25_metadata("Token2")))
+        .into_iter()
+        .map(|warning| match warning {
+            Phase1Warning::Cip25MetadataMismatch { message, .. } => message,
+            other => panic!("unexpected warning {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Asset is described in the metadata but not minted in this transaction".to_string(),
+            "Minted asset has no CIP-25 metadata".to_string(),
+        ]
+    );
+}
+
+/// Output holding `assets` of `POLICY_ID` with `datum` inline
+fn output_with_datum(assets: &[Vec<u8>], datum: &csl::PlutusData) -> csl::TransactionOutput {
+    let mut multiasset = csl::MultiAsset::new();
+    for name in assets {
+        multiasset.set_asset(
+            &policy_id(),
+            &csl::AssetName::new(name.clone()).unwrap(),
+            &csl::BigNum::one(),
+        );
+    }
+    let mut value = csl::Value::new(&csl::Coin::from(2_000_000u64));
+    value.set_multiasset(&multiasset);
+    let mut output = csl::TransactionOutput::new(&address(), &value);
+    output.set_plutus_data(datum);
+    output
+}
+
+/// `Constr alternative [metadata: {}, version: 1, extra: Constr 0 []]`
+fn cip68_datum(alternative: u64) -> csl::PlutusData {
+    let mut fields = csl::PlutusList::new();
+    fields.add(&csl::PlutusData::new_map(&csl::PlutusMap::new()));
+    fields.add(&csl::PlutusData::new_integer(&csl::BigInt::from(1)));
+    fields.add(&csl::PlutusData::new_empty_constr_plutus_data(
+        &csl::BigNum::zero(),
+    ));
+    csl::PlutusData::new_constr_plutus_data(&csl::ConstrPlutusData::new(
+        &csl::BigNum::from(alternative),
+        &fields,
+    ))
+}
+
+fn cip68_messages(warnings: Vec<Phase1Warning>) -> Vec<String> {
+    warnings
+        .into_iter()
+        .map(|warning| match warning {
+            Phase1Warning::Cip68TokenMismatch { message, .. } => message,
+            other => panic!("unexpected warning {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn cip68_reference_and_user_token_pairs() {
+    let reference_name = cip68_name(REFERENCE_PREFIX, b"Token");
+    for (prefix, quantity) in [(NFT_PREFIX, 1), (FT_PREFIX, 1000), (RFT_PREFIX, 10)] {
+        let user_name = cip68_name(prefix, b"Token");
+        let mut outputs = csl::TransactionOutputs::new();
+        outputs.add(&output_with_datum(
+            &[reference_name.clone()],
+            &cip68_datum(0),
+        ));
+        let tx_body = minting_tx_body(
+            &[(reference_name.clone(), 1), (user_name, quantity)],
+            &outputs,
+        );
+        let warnings = warnings(&tx_body, None);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+}
+
+#[test]
+fn cip68_invalid_pairs_and_reference_datum() {
+    let reference_name = cip68_name(REFERENCE_PREFIX, b"Token");
+
+    // The reference datum must be Constr 0
+    let mut outputs = csl::TransactionOutputs::new();
+    outputs.add(&output_with_datum(
+        &[reference_name.clone()],
+        &cip68_datum(1),
+    ));
+    let tx_body = minting_tx_body(
+        &[
+            (reference_name.clone(), 1),
+            (cip68_name(NFT_PREFIX, b"Token"), 1),
+        ],
+        &outputs,
+    );
+    assert_eq!(
+        cip68_messages(warnings(&tx_body, None)),
+        vec!["Reference token datum must be Constr 0 [metadata map, version int, extra]"]
+    );
+
+    // A user token without its reference token, and an NFT minted twice
+    let tx_body = minting_tx_body(
+        &[(cip68_name(NFT_PREFIX, b"Other"), 2)],
+        &csl::TransactionOutputs::new(),
+    );
+    let messages = cip68_messages(warnings(&tx_body, None));
+    assert_eq!(messages.len(), 2, "{:?}", messages);
+    assert!(messages[0].starts_with("User token (222) is minted without its reference token"));
+    assert_eq!(
+        messages[1],
+        "NFT user token (222) must be minted once, found 2"
+    );
+}
+
+fn cip27_metadata(rate: &str) -> csl::AuxiliaryData {
+    let address = address().to_bech32(None).unwrap();
+    auxiliary_data(
+        777,
+        &text_map(&[("rate", text(rate)), ("addr", text(&address))]),
+    )
+}
+
+#[test]
+fn cip27_royalty_token() {
+    let tx_body = minting_tx_body(&[(vec![], 1)], &csl::TransactionOutputs::new());
+    let warnings = warnings(&tx_body, Some(&cip27_metadata("0.05")));
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn cip27_invalid_rate_without_royalty_token() {
+    let tx_body = minting_tx_body(&[(b"Token1".to_vec(), 1)], &csl::TransactionOutputs::new());
+    let messages: Vec<String> = warnings(&tx_body, Some(&cip27_metadata("1.5")))
+        .into_iter()
+        .map(|warning| match warning {
+            Phase1Warning::Cip27RoyaltiesInvalid { message } => message,
+            other => panic!("unexpected warning {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Royalty \"rate\" must be between 0 and 1".to_string(),
+            "CIP-27 metadata is present, but no royalty token (empty asset name) is minted"
+                .to_string(),
+        ]
+    );
+}
@@ -0,0 +1,173 @@
+use crate::common::{Asset, TxInput, TxOutput, UTxO};
+use crate::validators::common::NetworkType;
+use crate::validators::input_contexts::{UtxoInputContext, ValidationInputContext};
+use crate::validators::phase_1::errors::Phase1Error;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use crate::validators::transaction_chain::validate_transaction_chain;
+use cardano_serialization_lib as csl;
+
+const FEE: u64 = 1_000_000;
+
+fn private_key() -> csl::PrivateKey {
+    csl::PrivateKey::from_normal_bytes(&[1; 32]).unwrap()
+}
+
+fn address() -> csl::Address {
+    let credential = csl::Credential::from_keyhash(&private_key().to_public().hash());
+    csl::EnterpriseAddress::new(1, &credential).to_address()
+}
+
+/// Spends `input` holding `amount` lovelace into a single output, signed unless `signed` is false
+fn spending_tx(input: &csl::TransactionInput, amount: u64, signed: bool) -> String {
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(input);
+    let mut outputs = csl::TransactionOutputs::new();
+    outputs.add(&csl::TransactionOutput::new(
+        &address(),
+        &csl::Value::new(&csl::BigNum::from(amount - FEE)),
+    ));
+    let tx_body = csl::TransactionBody::new_tx_body(&inputs, &outputs, &csl::Coin::from(FEE));
+    let mut tx = csl::FixedTransaction::new_from_body_bytes(&tx_body.to_bytes()).unwrap();
+    if signed {
+        tx.sign_and_add_vkey_signature(&private_key()).unwrap();
+    }
+    tx.to_hex()
+}
+
+fn tx_hash(tx_hex: &str) -> csl::TransactionHash {
+    csl::FixedTransaction::from_hex(tx_hex)
+        .unwrap()
+        .transaction_hash()
+}
+
+fn has_bad_input(errors: &[crate::validators::phase_1::errors::ValidationPhase1Error]) -> bool {
+    errors
+        .iter()
+        .any(|error| matches!(error.error, Phase1Error::BadInputsUTxO { .. }))
+}
+
+fn base_input() -> csl::TransactionInput {
+    csl::TransactionInput::new(&csl::TransactionHash::from([7; 32]), 0)
+}
+
+fn utxo_context(input: &csl::TransactionInput, amount: u64) -> UtxoInputContext {
+    UtxoInputContext {
+        utxo: UTxO {
+            input: TxInput {
+                tx_hash: input.transaction_id().to_hex(),
+                output_index: input.index(),
+            },
+            output: TxOutput {
+                address: address().to_bech32(None).unwrap(),
+                amount: vec![Asset {
+                    unit: "lovelace".to_string(),
+                    quantity: amount.to_string(),
+                }],
+                data_hash: None,
+                plutus_data: None,
+                script_ref: None,
+                script_hash: None,
+            },
+        },
+        is_spent: false,
+    }
+}
+
+fn chain_context() -> ValidationInputContext {
+    ValidationInputContext::new(
+        vec![utxo_context(&base_input(), 10_000_000)],
+        get_test_protocol_parameters(),
+        0,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+#[test]
+fn chained_transactions_and_conflicts() {
+    let first = spending_tx(&base_input(), 10_000_000, true);
+    let second = spending_tx(
+        &csl::TransactionInput::new(&tx_hash(&first), 0),
+        9_000_000,
+        true,
+    );
+    let conflicting = spending_tx(&base_input(), 10_000_000, true);
+
+    let result =
+        validate_transaction_chain(&[first, second, conflicting], chain_context()).unwrap();
+    assert_eq!(result.transactions.len(), 3);
+    assert!(
+        result.transactions[0].result.errors.is_empty(),
+        "{:?}",
+        result.transactions[0].result.errors
+    );
+    assert!(
+        result.transactions[1].result.errors.is_empty(),
+        "{:?}",
+        result.transactions[1].result.errors
+    );
+    assert!(has_bad_input(&result.transactions[2].result.errors));
+
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(
+        result.conflicts[0].input.tx_hash,
+        base_input().transaction_id().to_hex()
+    );
+    assert_eq!(result.conflicts[0].tx_indexes, vec![0, 2]);
+}
+
+#[test]
+fn dependents_of_rejected_transaction_spend_missing_inputs() {
+    // No signature, so the first transaction fails phase 1 and its output is never created
+    let first = spending_tx(&base_input(), 10_000_000, false);
+    let second = spending_tx(
+        &csl::TransactionInput::new(&tx_hash(&first), 0),
+        9_000_000,
+        true,
+    );
+
+    let result = validate_transaction_chain(&[first, second], chain_context()).unwrap();
+    assert!(!result.transactions[0].result.errors.is_empty());
+    assert!(!has_bad_input(&result.transactions[0].result.errors));
+    assert!(has_bad_input(&result.transactions[1].result.errors));
+}
+
+#[test]
+fn collateral_of_failed_transaction_conflicts() {
+    // isValid = false: the base input is only used as collateral, but it is still consumed
+    let script_input = csl::TransactionInput::new(&csl::TransactionHash::from([8; 32]), 0);
+    let mut inputs = csl::TransactionInputs::new();
+    inputs.add(&script_input);
+    let mut collateral = csl::TransactionInputs::new();
+    collateral.add(&base_input());
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &inputs,
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::from(FEE),
+    );
+    tx_body.set_collateral(&collateral);
+    let mut failed = csl::FixedTransaction::new_from_body_bytes(&tx_body.to_bytes()).unwrap();
+    failed.set_is_valid(false);
+    let spending = spending_tx(&base_input(), 10_000_000, true);
+
+    let mut context = chain_context();
+    context
+        .utxo_set
+        .push(utxo_context(&script_input, 10_000_000));
+
+    let result = validate_transaction_chain(&[failed.to_hex(), spending], context).unwrap();
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(
+        result.conflicts[0].input.tx_hash,
+        base_input().transaction_id().to_hex()
+    );
+    assert_eq!(result.conflicts[0].tx_indexes, vec![0, 1]);
+}
@@ -0,0 +1,75 @@
+use crate::validators::common::NetworkType;
+use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::phase_1::errors::Phase1Error;
+use crate::validators::phase_1::validation::TransactionLimitsValidator;
+use crate::validators::tests::validator::get_test_protocol_parameters;
+use cardano_serialization_lib as csl;
+
+const CURRENT_SLOT: u64 = 1_000;
+const MAINNET_STABILITY_WINDOW: u64 = 129_600;
+
+fn forecast_errors(ttl: u64, with_redeemers: bool) -> Vec<Phase1Error> {
+    let context = ValidationInputContext::new(
+        vec![],
+        get_test_protocol_parameters(),
+        CURRENT_SLOT,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        NetworkType::Mainnet,
+        vec![],
+        vec![],
+        vec![],
+    );
+    let mut tx_body = csl::TransactionBody::new_tx_body(
+        &csl::TransactionInputs::new(),
+        &csl::TransactionOutputs::new(),
+        &csl::Coin::from(200_000u64),
+    );
+    tx_body.set_ttl(&csl::BigNum::from(ttl));
+    let mut witness_set = csl::TransactionWitnessSet::new();
+    if with_redeemers {
+        let mut redeemers = csl::Redeemers::new();
+        redeemers.add(&csl::Redeemer::new(
+            &csl::RedeemerTag::new_spend(),
+            &csl::BigNum::zero(),
+            &csl::PlutusData::new_integer(&csl::BigInt::from(0)),
+            &csl::ExUnits::new(&csl::BigNum::from(1u64), &csl::BigNum::from(1u64)),
+        ));
+        witness_set.set_redeemers(&redeemers);
+    }
+    let tx = csl::Transaction::new(&tx_body, &witness_set, None);
+
+    TransactionLimitsValidator::new(tx.to_bytes().len(), &tx_body, &witness_set, &context)
+        .unwrap()
+        .validate()
+        .errors
+        .into_iter()
+        .map(|error| error.error)
+        .filter(|error| matches!(error, Phase1Error::OutsideForecast { .. }))
+        .collect()
+}
+
+#[test]
+fn ttl_outside_forecast() {
+    let horizon = CURRENT_SLOT + MAINNET_STABILITY_WINDOW;
+
+    let errors = forecast_errors(horizon + 1, true);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        Phase1Error::OutsideForecast {
+            slot,
+            current_slot: CURRENT_SLOT,
+            forecast_horizon,
+        } if slot == horizon + 1 && forecast_horizon == horizon
+    ));
+
+    // The horizon itself can still be translated
+    assert!(forecast_errors(horizon, true).is_empty());
+    // Without scripts the validity interval is never translated to POSIX time
+    assert!(forecast_errors(horizon + 1, false).is_empty());
+}
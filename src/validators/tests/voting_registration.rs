@@ -0,0 +1,124 @@
+use crate::validators::phase_1::validation::voting_registration::{
+    VotingRegistration, VotingRegistrationStandard,
+};
+use cardano_serialization_lib as csl;
+use cryptoxide::hashing::blake2b_256;
+
+fn int_key(key: i32) -> csl::TransactionMetadatum {
+    csl::TransactionMetadatum::new_int(&csl::Int::new_i32(key))
+}
+
+#[test]
+fn test_cip36_voting_registration() {
+    let stake_key = csl::PrivateKey::generate_ed25519().unwrap();
+
+    // { 1: [[voting_key, 1]], 2: stake_key, 3: address, 4: nonce, 5: 0 }
+    let mut delegation = csl::MetadataList::new();
+    delegation.add(&csl::TransactionMetadatum::new_bytes(vec![1; 32]).unwrap());
+    delegation.add(&int_key(1));
+    let mut delegations = csl::MetadataList::new();
+    delegations.add(&csl::TransactionMetadatum::new_list(&delegation));
+    let address = csl::EnterpriseAddress::new(
+        1,
+        &csl::Credential::from_keyhash(&stake_key.to_public().hash()),
+    )
+    .to_address();
+
+    let mut registration = csl::MetadataMap::new();
+    registration.insert(
+        &int_key(1),
+        &csl::TransactionMetadatum::new_list(&delegations),
+    );
+    registration.insert(
+        &int_key(2),
+        &csl::TransactionMetadatum::new_bytes(stake_key.to_public().as_bytes()).unwrap(),
+    );
+    registration.insert(
+        &int_key(3),
+        &csl::TransactionMetadatum::new_bytes(address.to_bytes()).unwrap(),
+    );
+    registration.insert(&int_key(4), &int_key(42));
+    registration.insert(&int_key(5), &int_key(0));
+    let registration = csl::TransactionMetadatum::new_map(&registration);
+
+    let mut metadata = csl::GeneralTransactionMetadata::new();
+    metadata.insert(&csl::BigNum::from(61284u64), &registration);
+    let signature = stake_key.sign(&blake2b_256(&metadata.to_bytes()));
+    let mut witness = csl::MetadataMap::new();
+    witness.insert(
+        &int_key(1),
+        &csl::TransactionMetadatum::new_bytes(signature.to_bytes()).unwrap(),
+    );
+    metadata.insert(
+        &csl::BigNum::from(61285u64),
+        &csl::TransactionMetadatum::new_map(&witness),
+    );
+
+    let parsed = VotingRegistration::from_metadata(&metadata).unwrap();
+    assert_eq!(parsed.standard, VotingRegistrationStandard::Cip36);
+    assert!(parsed.violations.is_empty(), "{:?}", parsed.violations);
+    assert_eq!(parsed.delegations, vec![(vec![1; 32], 1)]);
+    assert_eq!(parsed.nonce, Some(42));
+    assert_eq!(parsed.is_signature_valid(), Some(true));
+
+    // Missing witness label
+    let mut unsigned = csl::GeneralTransactionMetadata::new();
+    unsigned.insert(&csl::BigNum::from(61284u64), &registration);
+    let parsed = VotingRegistration::from_metadata(&unsigned).unwrap();
+    assert_eq!(parsed.violations.len(), 1, "{:?}", parsed.violations);
+    assert_eq!(
+        parsed.violations[0].1,
+        "transaction.auxiliary_data.metadata.61285"
+    );
+    assert_eq!(parsed.is_signature_valid(), None);
+}
+
+#[test]
+fn test_cip36_single_key_voting_registration() {
+    let stake_key = csl::PrivateKey::generate_ed25519().unwrap();
+    let address = csl::EnterpriseAddress::new(
+        1,
+        &csl::Credential::from_keyhash(&stake_key.to_public().hash()),
+    )
+    .to_address();
+
+    // { 1: voting_key, 2: stake_key, 3: address, 4: nonce, 5: 0 }
+    let mut registration = csl::MetadataMap::new();
+    registration.insert(
+        &int_key(1),
+        &csl::TransactionMetadatum::new_bytes(vec![1; 32]).unwrap(),
+    );
+    registration.insert(
+        &int_key(2),
+        &csl::TransactionMetadatum::new_bytes(stake_key.to_public().as_bytes()).unwrap(),
+    );
+    registration.insert(
+        &int_key(3),
+        &csl::TransactionMetadatum::new_bytes(address.to_bytes()).unwrap(),
+    );
+    registration.insert(&int_key(4), &int_key(42));
+    registration.insert(&int_key(5), &int_key(0));
+
+    let mut metadata = csl::GeneralTransactionMetadata::new();
+    metadata.insert(
+        &csl::BigNum::from(61284u64),
+        &csl::TransactionMetadatum::new_map(&registration),
+    );
+    let signature = stake_key.sign(&blake2b_256(&metadata.to_bytes()));
+    let mut witness = csl::MetadataMap::new();
+    witness.insert(
+        &int_key(1),
+        &csl::TransactionMetadatum::new_bytes(signature.to_bytes()).unwrap(),
+    );
+    metadata.insert(
+        &csl::BigNum::from(61285u64),
+        &csl::TransactionMetadatum::new_map(&witness),
+    );
+
+    let parsed = VotingRegistration::from_metadata(&metadata).unwrap();
+    assert_eq!(parsed.standard, VotingRegistrationStandard::Cip36);
+    assert!(parsed.violations.is_empty(), "{:?}", parsed.violations);
+    assert_eq!(parsed.delegations, vec![(vec![1; 32], 1)]);
+    assert_eq!(parsed.voting_purpose, 0);
+    assert_eq!(parsed.is_signature_valid(), Some(true));
+}
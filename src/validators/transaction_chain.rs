@@ -0,0 +1,114 @@
+use crate::bingen::wasm_bindgen;
+use crate::common::TxInput;
+use crate::js_error::JsError;
+use crate::validators::context_update::{
+    add_rejected_transaction_outputs, apply_transaction_to_context,
+};
+use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::validation_result::ValidationResult;
+use crate::validators::validator::validate_transaction;
+use cardano_serialization_lib as csl;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// An input spent by more than one transaction of the chain
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InputConflict {
+    pub input: TxInput,
+    pub tx_indexes: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChainTransactionResult {
+    pub index: u64,
+    pub tx_hash: String,
+    pub result: ValidationResult,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionChainResult {
+    pub transactions: Vec<ChainTransactionResult>,
+    pub conflicts: Vec<InputConflict>,
+}
+
+#[wasm_bindgen]
+pub fn validate_transaction_chain_js(
+    tx_hexes: &str,
+    validation_context: &str,
+) -> Result<String, JsError> {
+    let tx_hexes: Vec<String> =
+        serde_json::from_str(tx_hexes).map_err(|e| JsError::new(&e.to_string()))?;
+    let validation_context =
+        serde_json::from_str(validation_context).map_err(|e| JsError::new(&e.to_string()))?;
+    let chain_result = validate_transaction_chain(&tx_hexes, validation_context)?;
+    serde_json::to_string(&chain_result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Validates an ordered list of transactions. Each transaction is validated against the base
+/// context updated with the UTxOs and the certificate state of the transactions before it
+/// that passed phase 1.
+pub fn validate_transaction_chain(
+    tx_hexes: &[String],
+    validation_context: ValidationInputContext,
+) -> Result<TransactionChainResult, JsError> {
+    let mut context = validation_context;
+    let mut transactions = Vec::with_capacity(tx_hexes.len());
+    let mut conflicts: Vec<InputConflict> = Vec::new();
+    let mut spent_inputs: Vec<(TxInput, u64)> = Vec::new();
+
+    for (index, tx_hex) in tx_hexes.iter().enumerate() {
+        let index = index as u64;
+        let tx = csl::FixedTransaction::from_hex(tx_hex).map_err(|e| {
+            JsError::new(&format!("Failed to parse transaction {}: {:?}", index, e))
+        })?;
+
+        // A transaction that fails phase 2 spends its collateral instead of its inputs
+        let tx_body = tx.body();
+        let inputs = if tx.is_valid() {
+            tx_body.inputs()
+        } else {
+            tx_body.collateral().unwrap_or_else(csl::TransactionInputs::new)
+        };
+        for i in 0..inputs.len() {
+            let input = inputs.get(i);
+            let input = TxInput {
+                tx_hash: input.transaction_id().to_hex(),
+                output_index: input.index(),
+            };
+            if let Some((_, first_index)) = spent_inputs.iter().find(|(spent, _)| spent == &input) {
+                match conflicts
+                    .iter_mut()
+                    .find(|conflict| conflict.input == input)
+                {
+                    Some(conflict) => conflict.tx_indexes.push(index),
+                    None => conflicts.push(InputConflict {
+                        input: input.clone(),
+                        tx_indexes: vec![*first_index, index],
+                    }),
+                }
+            } else {
+                spent_inputs.push((input, index));
+            }
+        }
+
+        let result = validate_transaction(tx_hex, context.clone())?;
+        // A transaction rejected in phase 1 never reaches the ledger
+        if result.errors.is_empty() {
+            apply_transaction_to_context(&mut context, &tx, tx.is_valid());
+        } else {
+            add_rejected_transaction_outputs(&mut context, &tx);
+        }
+
+        transactions.push(ChainTransactionResult {
+            index,
+            tx_hash: tx.transaction_hash().to_hex(),
+            result,
+        });
+    }
+
+    Ok(TransactionChainResult {
+        transactions,
+        conflicts,
+    })
+}
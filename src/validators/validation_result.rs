@@ -28,6 +28,47 @@ pub struct EvalRedeemerResult {
     pub logs: Vec<String>,
     pub success: bool,
     pub error: Option<String>,
+    /// Cost breakdown of the evaluation, set when `profile_scripts` is enabled in the context
+    #[serde(default)]
+    pub profile: Option<ExecutionProfile>,
+}
+
+/// Calls to one builtin function during a script evaluation
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct BuiltinProfile {
+    pub builtin: String,
+    pub calls: u64,
+    pub ex_units: ExUnits,
+}
+
+/// CEK machine steps of one kind during a script evaluation
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct MachineStepProfile {
+    pub step: String,
+    pub count: u64,
+    pub ex_units: ExUnits,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct ExecutionProfile {
+    /// Builtin functions called by the script, most expensive in CPU first
+    pub builtins: Vec<BuiltinProfile>,
+    pub machine_steps: Vec<MachineStepProfile>,
+    /// Lovelace paid for the ex units declared by the redeemer, given `execution_prices`
+    pub execution_fee: u64,
+    /// Share of the transaction fee that `execution_fee` represents, between 0 and 1
+    pub tx_fee_share: f64,
+}
+
+/// Execution units of all the redeemers of a transaction
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct TxExecutionBudget {
+    /// Sum of the ex units declared by the redeemers
+    pub declared: ExUnits,
+    /// Sum of the ex units the scripts need, evaluated without a budget limit
+    pub required: ExUnits,
+    /// `max_tx_execution_units` of the protocol parameters
+    pub max: ExUnits,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
@@ -37,6 +78,8 @@ pub struct ValidationResult {
     pub phase2_errors: Vec<ValidationPhase2Error>,
     pub phase2_warnings: Vec<ValidationPhase2Warning>,
     pub eval_redeemer_results: Vec<EvalRedeemerResult>,
+    #[serde(default)]
+    pub tx_execution_budget: Option<TxExecutionBudget>,
 }
 
 impl ValidationResult {
@@ -48,6 +91,7 @@ impl ValidationResult {
             phase2_errors: vec![],
             phase2_warnings: vec![],
             eval_redeemer_results: vec![],
+            tx_execution_budget: None,
         }
     }
 
@@ -61,6 +105,7 @@ impl ValidationResult {
             phase2_errors: vec![],
             phase2_warnings: vec![],
             eval_redeemer_results: vec![],
+            tx_execution_budget: None,
         }
     }
 
@@ -75,6 +120,7 @@ impl ValidationResult {
             phase2_errors: errors,
             phase2_warnings: warnings,
             eval_redeemer_results: eval_redeemer_results,
+            tx_execution_budget: None,
         }
     }
 
@@ -84,5 +130,8 @@ impl ValidationResult {
         self.phase2_errors.extend(other.phase2_errors);
         self.phase2_warnings.extend(other.phase2_warnings);
         self.eval_redeemer_results.extend(other.eval_redeemer_results);
+        if other.tx_execution_budget.is_some() {
+            self.tx_execution_budget = other.tx_execution_budget;
+        }
     }
 }
@@ -1,14 +1,18 @@
 use crate::bingen::wasm_bindgen;
 use crate::common::TxInput;
 use crate::js_error::JsError;
-use crate::validators::common::{GovernanceActionId, GovernanceActionType, NetworkType};
+use crate::validators::common::{
+    GovernanceActionId, GovernanceActionType, NetworkType, TransactionEra,
+};
 use crate::validators::helpers::csl_credential_to_local_credential;
 use crate::validators::input_contexts::NecessaryInputData;
 use crate::validators::input_contexts::ValidationInputContext;
+use crate::validators::phase_1::errors::{Phase1Error, ValidationPhase1Error};
 use crate::validators::phase_1::validation::fee::FeeValidator;
 use crate::validators::phase_1::validation::{
-    AuxiliaryDataValidator, BalanceValidator, CollateralValidator, OutputValidator,
-    RegistrationValidator, TransactionLimitsValidator, WitnessValidator,
+    AuxiliaryDataValidator, BalanceValidator, CollateralValidator, EraValidator,
+    OutputLintValidator, OutputValidator, RegistrationValidator, StrictDecodingValidator,
+    TransactionLimitsValidator, VotingRegistrationValidator, WitnessValidator,
 };
 use crate::validators::phase_2;
 use crate::validators::validation_result::ValidationResult;
@@ -431,8 +435,25 @@ pub fn validate_transaction(
     tx_hex: &str,
     validation_context: ValidationInputContext,
 ) -> Result<ValidationResult, JsError> {
-    let csl_tx = csl::FixedTransaction::from_hex(tx_hex)
-        .map_err(|e| JsError::new(&format!("Failed to parse transaction: {:?}", e)))?;
+    let tx_bytes = hex::decode(tx_hex)
+        .map_err(|e| JsError::new(&format!("Failed to decode transaction hex: {}", e)))?;
+    let csl_tx = match csl::FixedTransaction::from_bytes(tx_bytes.clone()) {
+        Ok(csl_tx) => csl_tx,
+        Err(e) => {
+            // CSL refuses to decode ill-formed metadata, which the ledger reports as InvalidMetadata
+            let metadata_violations = AuxiliaryDataValidator::collect_raw_violations(&tx_bytes);
+            if metadata_violations.is_empty() {
+                return Err(JsError::new(&format!("Failed to parse transaction: {:?}", e)));
+            }
+            let errors = metadata_violations
+                .into_iter()
+                .map(|(message, location)| {
+                    ValidationPhase1Error::new(Phase1Error::InvalidMetadata { message }, location)
+                })
+                .collect();
+            return Ok(ValidationResult::new_phase_1(errors, Vec::new()));
+        }
+    };
     let tx_body = csl_tx.body();
     let tx_witness_set = csl_tx.witness_set();
     let tx_hash = csl_tx.transaction_hash();
@@ -464,7 +485,12 @@ pub fn validate_transaction(
     overall_result.append(collateral_result);
 
     // 5. Auxiliary data validation
-    let auxiliary_context = AuxiliaryDataValidator::new(&tx_body, auxiliary_data);
+    let voting_registration_context =
+        VotingRegistrationValidator::new(auxiliary_data.as_ref(), &validation_context);
+    let voting_registration_result = voting_registration_context.validate();
+    overall_result.append(voting_registration_result);
+
+    let auxiliary_context = AuxiliaryDataValidator::new(&tx_body, auxiliary_data, &tx_bytes);
     let auxiliary_result = auxiliary_context.validate();
     overall_result.append(auxiliary_result);
 
@@ -478,15 +504,33 @@ pub fn validate_transaction(
     let output_result = output_context.validate();
     overall_result.append(output_result);
 
-    // 8. Transaction limits validation
+    // 8. Output safety lints
+    let output_lint_context =
+        OutputLintValidator::new(&tx_body, &tx_witness_set, &validation_context);
+    let output_lint_result = output_lint_context.validate();
+    overall_result.append(output_lint_result);
+
+    // 9. Transaction limits validation
     let transaction_limits_context =
         TransactionLimitsValidator::new(tx_size, &tx_body, &tx_witness_set, &validation_context)?;
     let transaction_limits_result = transaction_limits_context.validate();
     overall_result.append(transaction_limits_result);
 
-    // 9. Votes validation
+    // 10. Votes validation
+
+    // 11. Strict CBOR decoding rules, enforced by the node from Conway on
+    if validation_context.transaction_era() >= TransactionEra::Conway {
+        let strict_decoding_context = StrictDecodingValidator::new(&tx_bytes)?;
+        let strict_decoding_result = strict_decoding_context.validate();
+        overall_result.append(strict_decoding_result);
+    }
+
+    // 12. Era specific rules
+    let era_context = EraValidator::new(&tx_bytes, &tx_body, &tx_witness_set, &validation_context);
+    let era_result = era_context.validate();
+    overall_result.append(era_result);
 
-    // 10. Phase 2 validation
+    // 13. Phase 2 validation
     let phase_2_result = phase_2::validation::phase_2_validation(tx_hex, &validation_context)?;
     overall_result.append(phase_2_result);
 
@@ -9,6 +9,60 @@ export function get_necessary_data_list_js(tx_hex: string): string;
 * @returns {string}
 */
 export function validate_transaction_js(tx_hex: string, validation_context: string): string;
+/**
+* @param {string} hex - transaction, certificate or voting proposal
+* @param {AnchorDocumentsInput} documents
+* @returns {string}
+*/
+export function verify_anchor_documents_js(hex: string, documents: string): string;
+/**
+* @param {string} block_hex
+* @param {ValidationInputContext} validation_context
+* @returns {string}
+*/
+export function validate_block_js(block_hex: string, validation_context: string): string;
+/**
+* @param {string[]} tx_hexes - JSON array of transactions, in submission order
+* @param {ValidationInputContext} validation_context
+* @returns {string}
+*/
+export function validate_transaction_chain_js(tx_hexes: string, validation_context: string): string;
+/**
+* @param {string} tx_hex
+* @param {ValidationInputContext} validation_context
+* @returns {string}
+*/
+export function apply_transaction_js(tx_hex: string, validation_context: string): string;
+/**
+* @param {RatificationInput} input
+* @returns {string}
+*/
+export function simulate_ratification_js(input: string): string;
+/**
+* @param {string} error - CBOR hex of the ApplyTxErr, cardano-cli/submit-api/Ogmios JSON or the Haskell text
+* @param {NetworkType} network_type
+* @returns {string}
+*/
+export function translate_node_error_js(error: string, network_type: NetworkType): string;
+/**
+* @param {string} tx_hex
+* @param {ValidationInputContext} validation_context
+* @returns {string}
+*/
+export function check_phase_one_consistency_js(tx_hex: string, validation_context: string): string;
+/**
+* @param {string} tx_hex
+* @param {ValidationInputContext} validation_context
+* @returns {string} RedeemerScriptContext[]
+*/
+export function get_script_contexts_js(tx_hex: string, validation_context: string): string;
+
+export interface AnchorDocumentsInput {
+    /** URL -> hex encoded document bytes, exactly as served */
+    documents: { [url: string]: string };
+    /** Also check CIP-100/CIP-108/CIP-119 structure and CIP-6 pool metadata limits */
+    checkStructure?: boolean;
+}
 
 /**
  * @returns {(string)[]}
@@ -33,6 +87,12 @@ export function get_possible_types_for_input(input: string): (string)[];
 export function cbor_to_json(cbor_hex: string): CborValue;
 
 export function check_block_or_tx_signatures(hex_str: string): CheckSignaturesResult;
+/**
+ * @param {string} hex_str - block header or whole block
+ * @param {HeaderCheckParams} params_json
+ * @returns {HeaderCheckResult}
+ */
+export function check_block_header_signatures(hex_str: string, params_json: string): HeaderCheckResult;
 
 /**
  * @param {string} tx_hex
@@ -44,9 +104,10 @@ export function get_utxo_list_from_tx(tx_hex: string): string[];
  * @param {string} tx_hex
  * @param {UTxO[]} utxo_json
  * @param {CostModels} cost_models_json
+ * @param {ExUnits} max_tx_ex_units_json
  * @returns {ExecuteTxScriptsResult}
  */
-export function execute_tx_scripts(tx_hex: string, utxo_json: UTxO[], cost_models_json: CostModels): ExecuteTxScriptsResult;
+export function execute_tx_scripts(tx_hex: string, utxo_json: UTxO[], cost_models_json: CostModels, max_tx_ex_units_json: ExUnits): ExecuteTxScriptsResult;
 /**
  * @param {string} hex
  * @returns {ProgramJson}
@@ -157,6 +218,30 @@ export interface CheckSignaturesResult {
     invalidVkeyWitnesses: string[];
 }
 
+export interface HeaderCheckParams {
+    /** Epoch nonce as hex. The VRF proofs are only checked when it is given. */
+    epochNonce?: string | null;
+    /** Defaults to 129600 */
+    slotsPerKesPeriod?: number;
+    /** Defaults to 62 */
+    maxKesEvolutions?: number;
+}
+
+export interface HeaderCheckResult {
+    /** True when no check failed. */
+    valid: boolean;
+    blockHash: string;
+    slot: number;
+    /** KES period of the header slot. */
+    kesPeriod: number;
+    kesSignatureValid: boolean;
+    opcertSignatureValid: boolean;
+    /** Null when no epoch nonce was given. */
+    vrfValid: boolean | null;
+    /** Descriptions of the failed checks. */
+    errors?: string[];
+}
+
 
 // The execution units object contains two numeric fields.
 export type ExUnits = {
@@ -467,6 +552,13 @@ export type Phase1Error =
         interval_end: bigint;
         interval_start: bigint;
     };
+}
+    | {
+    OutsideForecast: {
+        current_slot: bigint;
+        forecast_horizon: bigint;
+        slot: bigint;
+    };
 }
     | {
     MaxTxSizeUTxO: {
@@ -616,6 +708,13 @@ export type Phase1Error =
     InvalidMetadata: {
         message: string;
     };
+}
+    | {
+    AnchorDocumentHashMismatch: {
+        actual_hash: string;
+        expected_hash: string;
+        url: string;
+    };
 }
     | {
     ExtraneousScriptWitnesses: {
@@ -668,6 +767,59 @@ export type Phase1Error =
         min_cost: bigint;
         specified_cost: bigint;
     };
+}
+    | {
+    PoolMarginOutOfRange: {
+        denominator: bigint;
+        numerator: bigint;
+        pool_id: string;
+    };
+}
+    | {
+    PoolAmountOutOfBounds: {
+        amount: bigint;
+        field: string;
+        max_amount: bigint;
+        pool_id: string;
+    };
+}
+    | {
+    WrongNetworkPool: {
+        expected_network: number;
+        pool_id: string;
+        reward_account: string;
+    };
+}
+    | {
+    PoolRelayDnsNameTooLong: {
+        dns_name: string;
+        length: number;
+        max_length: number;
+        pool_id: string;
+        relay_index: number;
+    };
+}
+    | {
+    PoolMetadataUrlTooLong: {
+        length: number;
+        max_length: number;
+        pool_id: string;
+        url: string;
+    };
+}
+    | {
+    PoolMetadataHashTooBig: {
+        hash_size: number;
+        max_size: number;
+        pool_id: string;
+    };
+}
+    | {
+    VrfKeyHashAlreadyRegistered: {
+        pool_id: string;
+        registered_pool_id: string;
+        vrf_key_hash: string;
+    };
 }
     | {
     InsufficientFundsForMir: {
@@ -945,6 +1097,51 @@ export type Phase1Error =
     ReferenceInputOverlapsWithInput: {
         input: TxInput;
     };
+}
+    | {
+    DuplicateSetElement: {
+        field: string;
+        offset: bigint;
+    };
+}
+    | {
+    EmptyNonEmptySet: {
+        field: string;
+        offset: bigint;
+    };
+}
+    | {
+    ZeroQuantityAsset: {
+        asset_name: string;
+        field: string;
+        offset: bigint;
+        policy_id: string;
+    };
+}
+    | {
+    DuplicateMapKey: {
+        field: string;
+        offset: bigint;
+    };
+}
+    | {
+    InvalidSetTag: {
+        field: string;
+        offset: bigint;
+        tag: string;
+    };
+}
+    | {
+    FeatureNotSupportedInEra: {
+        era: TransactionEra;
+        feature: string;
+    };
+}
+    | {
+    TransactionNotDecodableInEra: {
+        era: TransactionEra;
+        message: string;
+    };
 };
 
 /**
@@ -988,6 +1185,18 @@ export type Phase2Error =
     MissingScriptForRedeemer: {
         error: string;
     };
+}
+    | {
+    TxExecutionBudgetTooBig: {
+        max_budget: ExUnits;
+        required_budget: ExUnits;
+    };
+}
+    | {
+    ScriptExceedsTxMaxBudget: {
+        actual_budget: ExUnits;
+        max_budget: ExUnits;
+    };
 };
 export type Phase2Warning = {
     BudgetIsBiggerThanExpected: {
@@ -1020,6 +1229,115 @@ export type Phase1Warning =
     PoolAlreadyRegistered: {
         pool_id: string;
     };
+}
+    | {
+    PoolOwnerStakeKeyNotRegistered: {
+        owner_reward_address: string;
+        pool_id: string;
+    };
+}
+    | {
+    DelegationToRetiringPool: {
+        pool_id: string;
+        retirement_epoch: bigint;
+    };
+}
+    | {
+    DelegationToInactiveDRep: {
+        current_epoch: bigint;
+        drep_id: string;
+        expiry_epoch: bigint;
+    };
+}
+    | {
+    RedundantDelegation: {
+        delegation_target: string;
+        reward_address: string;
+    };
+}
+    | {
+    WithdrawalDRepDelegationWillBeDropped: {
+        drep_id: string;
+        reward_address: string;
+    };
+}
+    | {
+    ScriptOutputWithoutDatum: {
+        address: string;
+        language: string;
+        script_hash: string;
+    };
+}
+    | {
+    DatumHashNotPublished: {
+        address: string;
+        datum_hash: string;
+    };
+}
+    | {
+    TokensSentToUnusualAddress: {
+        address: string;
+        address_kind: string;
+    };
+}
+    | {
+    OutputRepeatsInput: {
+        input: string;
+    };
+}
+    | {
+    LargeInlineDatum: {
+        datum_size: number;
+        max_size: number;
+    };
+}
+    | {
+    Cip25MetadataMismatch: {
+        asset_name: string;
+        message: string;
+        policy_id: string;
+    };
+}
+    | {
+    Cip68TokenMismatch: {
+        asset_name: string;
+        message: string;
+        policy_id: string;
+    };
+}
+    | {
+    Cip27RoyaltiesInvalid: {
+        message: string;
+    };
+}
+    | {
+    InvalidVotingRegistration: {
+        message: string;
+        standard: string;
+    };
+}
+    | {
+    VotingRegistrationInvalidSignature: {
+        stake_public_key: string;
+    };
+}
+    | {
+    VotingRegistrationWrongNetwork: {
+        expected_network: number;
+        payment_address: string;
+    };
+}
+    | {
+    AnchorDocumentMissing: {
+        url: string;
+    };
+}
+    | {
+    AnchorDocumentInvalid: {
+        message: string;
+        standard: string;
+        url: string;
+    };
 }
     | {
     DRepAlreadyRegistered: {
@@ -1061,13 +1379,249 @@ export interface ValidationResult {
     eval_redeemer_results: EvalRedeemerResult[];
     phase2_errors: ValidationPhase2Error[];
     phase2_warnings: ValidationPhase2Warning[];
+    tx_execution_budget?: TxExecutionBudget | null;
     warnings: ValidationPhase1Warning[];
 }
+export interface TxExecutionBudget {
+    /** Sum of the ex units declared by the redeemers */
+    declared: ExUnits;
+    /** max_tx_execution_units of the protocol parameters */
+    max: ExUnits;
+    /** Sum of the ex units the scripts need, evaluated without a budget limit */
+    required: ExUnits;
+}
+export interface NodeErrorTranslation {
+    era: string | null;
+    errors: ValidationPhase1Error[];
+    phase2_errors: ValidationPhase2Error[];
+    untranslated_failures: UntranslatedNodeFailure[];
+}
+export interface UntranslatedNodeFailure {
+    /** Decoded CBOR, Haskell text or the original JSON */
+    failure: unknown;
+    name: string | null;
+    /** Ledger rule path, e.g. LEDGER/UTXOW/UTXO */
+    rule: string;
+}
+export type PhaseOneCheck =
+    | "missingScripts"
+    | "extraneousScripts"
+    | "missingRedeemers"
+    | "extraneousRedeemers"
+    | "other";
+export interface PhaseOneDisagreement {
+    check: PhaseOneCheck;
+    /** The side that reported the failure the other side didn't */
+    reported_by: "cquisitor" | "uplc";
+    /** Script hash, redeemer as Tag[index] or the uplc error message */
+    subject: string;
+}
+export interface PhaseOneConsistencyReport {
+    disagreements: PhaseOneDisagreement[];
+    /** Checks uplc didn't reach because it stops at the first failing one */
+    not_compared: PhaseOneCheck[];
+    uplc_error: string | null;
+}
+/** Plutus data as CBOR hex and as JSON in the detailed schema */
+export interface ScriptArgument {
+    cbor: string;
+    json: unknown;
+}
+export interface RedeemerScriptContext {
+    /** Datum of the spent output. V1 and V2 scripts get it as their first argument. */
+    datum: ScriptArgument | null;
+    /** Why the script context couldn't be built or an argument couldn't be converted */
+    error: string | null;
+    index: bigint;
+    language: "PlutusV1" | "PlutusV2" | "PlutusV3" | null;
+    redeemer: ScriptArgument | null;
+    /** Last argument of V1 and V2 scripts, the only one of V3 scripts */
+    script_context: ScriptArgument | null;
+    tag: RedeemerTag;
+}
+export interface BlockValidationResult {
+    block_errors: BlockValidationError[];
+    block_hash: string;
+    slot: bigint;
+    transactions: BlockTransactionValidationResult[];
+}
+export interface BlockTransactionValidationResult {
+    index: bigint;
+    is_valid: boolean;
+    result: ValidationResult;
+    tx_hash: string;
+}
+export interface TransactionChainResult {
+    conflicts: InputConflict[];
+    transactions: ChainTransactionResult[];
+}
+export interface ChainTransactionResult {
+    index: bigint;
+    result: ValidationResult;
+    tx_hash: string;
+}
+export interface InputConflict {
+    input: TxInput;
+    tx_indexes: bigint[];
+}
+export interface ApplyTransactionResult {
+    delta: LedgerDelta;
+    result: ValidationResult;
+}
+export interface LedgerDelta {
+    accounts: AccountInputContext[];
+    committeeMembers: CommitteeInputContext[];
+    consumedUtxos: UTxO[];
+    deposits: DepositType[];
+    dreps: DrepInputContext[];
+    /** Fee collected by the ledger, the collateral for an invalid transaction */
+    fee: bigint;
+    isValid: boolean;
+    newGovActions: GovActionInputContext[];
+    pools: PoolInputContext[];
+    producedUtxos: UTxO[];
+    refunds: RefundType[];
+    treasuryDonation: bigint;
+    /** Consumed inputs missing from the UTxO set of the context */
+    unresolvedInputs: TxInput[];
+    withdrawals: Withdrawal[];
+}
+export type VoteChoice = "yes" | "no" | "abstain";
+export type RatificationVoter =
+    | { constitutionalCommitteeHotScriptHash: number[] }
+    | { constitutionalCommitteeHotKeyHash: number[] }
+    | { dRepScriptHash: number[] }
+    | { dRepKeyHash: number[] }
+    | { stakingPoolKeyHash: number[] };
+export interface RatificationInput {
+    alwaysAbstainStake?: bigint;
+    alwaysNoConfidenceStake?: bigint;
+    /** null when the ledger is in a state of no confidence */
+    committee: CommitteeState | null;
+    currentEpoch: bigint;
+    drepStake: DrepStake[];
+    /** Hex encoded DRepVotingThresholds */
+    drepVotingThresholds: string;
+    /** Hex encoded GovernanceAction */
+    governanceAction: string;
+    poolStake: PoolStake[];
+    /** Hex encoded PoolVotingThresholds */
+    poolVotingThresholds: string;
+    protocolMajorVersion: bigint;
+    votes: { voter: RatificationVoter; vote: VoteChoice }[];
+}
+export interface DrepStake {
+    drep: string;
+    expiryEpoch: bigint | null;
+    stake: bigint;
+}
+export interface PoolStake {
+    poolId: string;
+    /** Default vote of a pool that did not vote */
+    rewardAccountDrep?: "alwaysAbstain" | "alwaysNoConfidence" | null;
+    stake: bigint;
+}
+export interface CommitteeState {
+    members: {
+        coldCredential: LocalCredential;
+        expiryEpoch: bigint;
+        hotCredential: LocalCredential | null;
+        isResigned?: boolean;
+    }[];
+    minSize: number;
+    threshold: SubCoin;
+}
+export interface RatificationResult {
+    committee: BodyVotes;
+    dreps: BodyVotes;
+    ratified: boolean;
+    spos: BodyVotes;
+}
+export interface BodyVotes {
+    abstain: bigint;
+    /** Relative to yes + no + abstain */
+    abstainPercentage: number;
+    no: bigint;
+    /** Relative to yes + no */
+    noPercentage: number;
+    passed: boolean;
+    required: boolean;
+    threshold: SubCoin | null;
+    yes: bigint;
+    /** Relative to yes + no, the ratio compared with the threshold */
+    yesPercentage: number;
+}
+export type DepositType =
+    | { VotingProposal: { amount: bigint; index: number } }
+    | { StakeRegistration: { amount: bigint; index: number } }
+    | { DrepRegistration: { amount: bigint; index: number } }
+    | { PoolRegistration: { amount: bigint; index: number } };
+export type RefundType =
+    | {
+    StakeDeregistration: {
+        amount: bigint;
+        expected_amount?: bigint | null;
+        index: number;
+        reward_address: string;
+    };
+}
+    | {
+    DrepDeregistration: {
+        amount: bigint;
+        drep_bech32: string;
+        expected_amount?: bigint | null;
+        index: number;
+    };
+};
+export interface Withdrawal {
+    amount: bigint;
+    index: number;
+    reward_address: string;
+}
+export type BlockValidationError =
+    | {
+    BlockBodyTooBig: {
+        actual_size: bigint;
+        max_size: bigint;
+    };
+}
+    | {
+    WrongBlockBodySize: {
+        actual_size: bigint;
+        header_size: bigint;
+    };
+}
+    | {
+    BlockExUnitsTooBig: {
+        actual_ex_units: ExUnits;
+        max_ex_units: ExUnits;
+    };
+}
+    | {
+    InvalidTransactionIndexOutOfRange: {
+        index: bigint;
+        tx_count: bigint;
+    };
+}
+    | {
+    ValidationTagMismatch: {
+        is_valid: boolean;
+        tx_index: bigint;
+    };
+}
+    | {
+    BlockBodyHashMismatch: {
+        actual_hash: string;
+        expected_hash: string;
+    };
+};
 export interface ValidationPhase1Error {
     error: Phase1Error;
     error_message: string;
     hint?: string | null;
     locations: string[];
+    /** Protocol major version from which the rule that produced the error applies, unset for rules that apply in every protocol version or are gated by era */
+    rule_version?: number | null;
 }
 /**
  * The invalid input UTxO
@@ -1116,10 +1670,33 @@ export interface EvalRedeemerResult {
     error?: string | null;
     index: bigint;
     logs: string[];
+    /** Cost breakdown of the evaluation, set when `profile_scripts` is enabled in the context */
+    profile?: ExecutionProfile | null;
     provided_ex_units: ExUnits;
     success: boolean;
     tag: RedeemerTag;
 }
+export interface ExecutionProfile {
+    /** Builtin functions called by the script, most expensive in CPU first */
+    builtins: BuiltinProfile[];
+    /** Lovelace paid for the ex units declared by the redeemer, given `execution_prices` */
+    execution_fee: bigint;
+    machine_steps: MachineStepProfile[];
+    /** Share of the transaction fee that `execution_fee` represents, between 0 and 1 */
+    tx_fee_share: number;
+}
+/** Calls to one builtin function during a script evaluation */
+export interface BuiltinProfile {
+    builtin: string;
+    calls: bigint;
+    ex_units: ExUnits;
+}
+/** CEK machine steps of one kind during a script evaluation */
+export interface MachineStepProfile {
+    count: bigint;
+    ex_units: ExUnits;
+    step: string;
+}
 
 export interface ValidationPhase2Error {
     error: Phase2Error;
@@ -1135,6 +1712,8 @@ export interface ValidationPhase2Warning {
 export interface ValidationPhase1Warning {
     hint?: string | null;
     locations: string[];
+    /** Same as `ValidationPhase1Error.rule_version` */
+    rule_version?: number | null;
     warning: Phase1Warning;
 }
 
@@ -1154,20 +1733,38 @@ export type GovernanceActionType =
     | "newConstitutionAction"
     | "infoAction";
 export type NetworkType = "mainnet" | "preview" | "preprod";
+export type TransactionEra = "shelley" | "allegra" | "mary" | "alonzo" | "babbage" | "conway";
 
 export interface ValidationInputContext {
     accountContexts: AccountInputContext[];
     currentCommitteeMembers: CommitteeInputContext[];
+    /**
+     * History of the deposit parameters, used to infer deregistration refunds
+     */
+    depositHistory?: DepositParametersInputContext[];
     drepContexts: DrepInputContext[];
+    /**
+     * Era to validate against, inferred from the protocol version when not set
+     */
+    era?: TransactionEra | null;
     govActionContexts: GovActionInputContext[];
     lastEnactedGovAction: GovActionInputContext[];
     networkType: NetworkType;
+    outputLintOptions?: OutputLintOptions;
     poolContexts: PoolInputContext[];
     potentialCommitteeMembers: CommitteeInputContext[];
+    /**
+     * Adds a cost breakdown to each redeemer result. Scripts are evaluated a second time.
+     */
+    profileScripts?: boolean;
     protocolParameters: ProtocolParameters;
     slot: bigint;
     treasuryValue: bigint;
     utxoSet: UtxoInputContext[];
+    /**
+     * VRF key hashes already used by registered pools
+     */
+    vrfKeyHashContexts?: VrfKeyHashInputContext[];
 }
 export interface AccountInputContext {
     balance?: number | null;
@@ -1176,6 +1773,35 @@ export interface AccountInputContext {
     delegatedToPool?: string | null;
     isRegistered: boolean;
     payedDeposit?: number | null;
+    /**
+     * Epoch of the registration, used with `deposit_history` when `payed_deposit` is unknown
+     */
+    registrationEpoch?: number | null;
+}
+/**
+ * Deposit parameters in effect from `epoch` until the next entry
+ */
+export interface DepositParametersInputContext {
+    drepDeposit: number;
+    epoch: number;
+    stakeKeyDeposit: number;
+}
+/**
+ * Toggles for the output safety lints. All lints are enabled by default.
+ */
+export interface OutputLintOptions {
+    datumHashNotPublished?: boolean;
+    /**
+     * Inline datums bigger than this size (in bytes) produce a warning, `None` disables the lint
+     */
+    maxInlineDatumSize?: number | null;
+    outputRepeatsInput?: boolean;
+    scriptOutputWithoutDatum?: boolean;
+    tokensToUnusualAddress?: boolean;
+}
+export interface VrfKeyHashInputContext {
+    poolId: string;
+    vrfKeyHash: string;
 }
 export interface CommitteeInputContext {
     committeeMemberCold: LocalCredential;
@@ -1184,8 +1810,13 @@ export interface CommitteeInputContext {
 }
 export interface DrepInputContext {
     bech32Drep: string;
+    expiryEpoch?: number | null;
     isRegistered: boolean;
     payedDeposit?: number | null;
+    /**
+     * Epoch of the registration, used with `deposit_history` when `payed_deposit` is unknown
+     */
+    registrationEpoch?: number | null;
 }
 export interface GovActionInputContext {
     actionId: GovernanceActionId;
@@ -1200,6 +1831,10 @@ export interface PoolInputContext {
     isRegistered: boolean;
     poolId: string;
     retirementEpoch?: number | null;
+    /**
+     * Reward account the deposit is refunded to when the pool retires
+     */
+    rewardAccount?: string | null;
 }
 export interface ProtocolParameters {
     /**